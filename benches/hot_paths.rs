@@ -0,0 +1,67 @@
+//! Throughput of the MFT hot paths over a synthetic in-memory MFT.
+//!
+//! Fixups, filename collection and path resolution are measured separately so a
+//! regression in one stage is not hidden by the others. Criterion reports each stage
+//! in bytes per second of MFT processed. No volume is opened.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo bench --features testing --bench hot_paths
+//! ```
+//!
+//! Set `TEAMY_MFT_BENCH_RECORDS` to change the number of generated files (default 200000).
+
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use std::hint::black_box;
+use std::path::Path;
+use teamy_mft::mft::fast_entry::collect_filenames;
+use teamy_mft::mft::fast_fixup::apply_fixups_parallel;
+use teamy_mft::mft::mft_file::MftFile;
+use teamy_mft::mft::path_resolve::resolve_paths_all_parallel;
+use teamy_mft::mft::testing::SYNTHETIC_RECORD_SIZE;
+use teamy_mft::mft::testing::synthetic_mft;
+
+const DEFAULT_FILE_COUNT: usize = 200_000;
+const FILES_PER_DIRECTORY: usize = 100;
+
+fn hot_paths(c: &mut Criterion) {
+    let file_count = std::env::var("TEAMY_MFT_BENCH_RECORDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_FILE_COUNT);
+    let raw = synthetic_mft(file_count, FILES_PER_DIRECTORY);
+    let mft = MftFile::from_vec(raw.clone()).expect("synthetic MFT should apply fixups");
+    let file_names = collect_filenames(&mft);
+
+    let mut group = c.benchmark_group("hot_paths");
+    group.throughput(Throughput::Bytes(raw.len() as u64));
+    group.sample_size(20);
+    group.bench_function("fixups", |b| {
+        b.iter_batched_ref(
+            || raw.clone(),
+            |bytes| black_box(apply_fixups_parallel(bytes, SYNTHETIC_RECORD_SIZE)),
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("collect_filenames", |b| {
+        b.iter(|| black_box(collect_filenames(&mft).x30_count()));
+    });
+    group.bench_function("resolve_paths", |b| {
+        b.iter(|| {
+            black_box(
+                resolve_paths_all_parallel(&file_names, Path::new("C:\\"))
+                    .expect("path resolution should succeed")
+                    .total_paths(),
+            )
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
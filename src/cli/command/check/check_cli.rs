@@ -0,0 +1,130 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::is_compressed_mft_path;
+use crate::machine::config::published_drive_paths;
+use crate::mft::fast_fixup::collect_invalid_fixups;
+use crate::mft::fast_fixup::detect_entry_size;
+use crate::mft::fast_fixup::find_first_invalid_fixup;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::Context;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use thousands::Separable;
+use tracing::info;
+
+/// Validate update sequence array fixups in cached `.mft` files.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct CheckArgs {
+    /// Drive letter pattern to match drives whose cached MFTs will be checked (e.g., "*", "C", "CD", "C,D")
+    #[facet(args::positional, default)]
+    pub drive_letter_pattern: DriveLetterPattern,
+
+    /// Maximum number of invalid entries to print per drive; all failures are still counted
+    #[facet(args::named, default)]
+    pub max_report: Option<usize>,
+
+    /// Stop at the first entry with invalid fixups and report only that one
+    #[facet(args::named, default)]
+    pub fail_fast: bool,
+}
+
+impl CheckArgs {
+    /// Check fixups for every matching cached MFT.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the machine cache cannot be retrieved, no matching drive has a
+    /// cached MFT, a cached MFT cannot be read, or if any entry fails fixup validation. With `--fail-fast`, the scan stops at the
+    /// first invalid entry.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let drive_letters = self.drive_letter_pattern.into_drive_letters()?;
+        let max_report = self.max_report.unwrap_or(usize::MAX);
+
+        let mut total_invalid = 0usize;
+        let mut checked_drives = 0usize;
+        for drive_letter in drive_letters {
+            if cancellation_token.is_cancelled() {
+                bail!("Cancelled while checking cached MFTs");
+            }
+            let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+            if !mft_path.is_file() {
+                println!(
+                    "{drive_letter}: no cached MFT at {}; run `sync` first",
+                    mft_path.display()
+                );
+                continue;
+            }
+            checked_drives += 1;
+
+            let mut raw = std::fs::read(&mft_path)
+                .wrap_err_with(|| format!("Failed to read {}", mft_path.display()))?;
+            if is_compressed_mft_path(&mft_path) {
+                raw = zstd::stream::decode_all(raw.as_slice())
+                    .wrap_err_with(|| format!("Failed to decompress {}", mft_path.display()))?;
+            }
+            let Some(entry_size) = detect_entry_size(&raw) else {
+                bail!("Cannot detect entry size for {}", mft_path.display());
+            };
+            let entry_size = entry_size as usize;
+            if !raw.len().is_multiple_of(entry_size) {
+                bail!(
+                    "{} length ({}) is not a multiple of entry size ({})",
+                    mft_path.display(),
+                    raw.len(),
+                    entry_size
+                );
+            }
+
+            let invalid = if self.fail_fast {
+                find_first_invalid_fixup(&mut raw, entry_size)
+                    .into_iter()
+                    .collect()
+            } else {
+                collect_invalid_fixups(&mut raw, entry_size)
+            };
+            info!(
+                drive = %drive_letter,
+                entries = (raw.len() / entry_size).separate_with_commas(),
+                invalid = invalid.len().separate_with_commas(),
+                "Checked fixups for {}",
+                mft_path.display()
+            );
+
+            for entry in invalid.iter().take(max_report) {
+                println!(
+                    "{drive_letter}: entry {} record {} signature \"{}\" has invalid fixups",
+                    entry.entry_index,
+                    entry.record_number,
+                    entry.signature.escape_ascii()
+                );
+            }
+            if invalid.len() > max_report {
+                println!(
+                    "{drive_letter}: {} more invalid entries not shown",
+                    (invalid.len() - max_report).separate_with_commas()
+                );
+            }
+            total_invalid += invalid.len();
+            if self.fail_fast && total_invalid > 0 {
+                bail!("Stopped at the first entry with invalid fixups on drive {drive_letter}");
+            }
+        }
+
+        if checked_drives == 0 {
+            bail!(
+                "No cached MFTs in {} for the requested drives; run `sync` first",
+                sync_dir.display()
+            );
+        }
+        if total_invalid > 0 {
+            bail!(
+                "Found {} entries with invalid fixups",
+                total_invalid.separate_with_commas()
+            );
+        }
+        Ok(())
+    }
+}
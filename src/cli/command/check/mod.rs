@@ -0,0 +1,3 @@
+mod check_cli;
+
+pub use check_cli::CheckArgs;
@@ -1,8 +1,16 @@
 use crate::cancellation::CancellationToken;
+use crate::cli::command::check::CheckArgs;
+use crate::cli::command::export_delta::ExportDeltaArgs;
+use crate::cli::command::export_sqlite::ExportSqliteArgs;
+use crate::cli::command::file_extents::FileExtentsArgs;
 use crate::cli::command::fsutil::FsutilArgs;
+use crate::cli::command::get_record::GetRecordArgs;
 use crate::cli::command::install::InstallArgs;
+use crate::cli::command::list_cached::ListCachedArgs;
 use crate::cli::command::list_paths::ListPathsArgs;
+use crate::cli::command::mft_runs::MftRunsArgs;
 use crate::cli::command::r#move::MoveArgs;
+use crate::cli::command::plan::PlanArgs;
 use crate::cli::command::profile::ProfileArgs;
 use crate::cli::command::protection::ProtectionArgs;
 use crate::cli::command::query::QueryArgs;
@@ -12,6 +20,8 @@ use crate::cli::command::status::StatusArgs;
 use crate::cli::command::sync::SyncArgs;
 use crate::cli::command::tray::TrayArgs;
 use crate::cli::command::uninstall::UninstallArgs;
+#[cfg(any(test, feature = "diagnostics"))]
+use crate::cli::command::verify_parsers::VerifyParsersArgs;
 use arbitrary::Arbitrary;
 use facet::Facet;
 use figue::{self as args};
@@ -34,6 +44,25 @@ pub enum Command {
     Uninstall(UninstallArgs),
     /// Produce newline-delimited list of file paths for matching drives from cached `.mft` files
     ListPaths(ListPathsArgs),
+    /// List drives with a cached `.mft` or `.mft.zst` in the sync dir, with file size and modified time
+    ListCached(ListCachedArgs),
+    /// Validate update sequence array fixups in cached `.mft` files and report corrupted entries
+    Check(CheckArgs),
+    /// Export resolved paths from cached `.mft` files into a `SQLite` `files` table
+    ExportSqlite(ExportSqliteArgs),
+    /// Write the paths added, removed or renamed between an older `.mft` snapshot and a drive's cached `.mft`
+    ExportDelta(ExportDeltaArgs),
+    /// Print the header fields or a hex dump of one record from a cached `.mft` file
+    GetRecord(GetRecordArgs),
+    /// Print the physical disk offsets of a file's `$DATA` runs, decoded from the cached `.mft`
+    FileExtents(FileExtentsArgs),
+    /// Print the logical `$MFT` segments and derived physical read plan for one drive (requires administrator)
+    Plan(PlanArgs),
+    /// Print the disk extents of one drive's live `$MFT` data runs with a fragmentation summary (requires administrator)
+    MftRuns(MftRunsArgs),
+    /// Diagnostic: compare paths resolved by the `mft` crate and the `fast_entry` scanner for one cached `.mft`
+    #[cfg(any(test, feature = "diagnostics"))]
+    VerifyParsers(VerifyParsersArgs),
     /// Move one file and automatically refresh the published overlay for the old and new paths
     #[facet(args::alias = "mv")]
     Move(MoveArgs),
@@ -74,6 +103,16 @@ impl Command {
             Command::Install(args) => args.invoke(),
             Command::Uninstall(args) => args.invoke(),
             Command::ListPaths(args) => args.invoke(&cancellation_token),
+            Command::ListCached(args) => args.invoke(),
+            Command::Check(args) => args.invoke(&cancellation_token),
+            Command::ExportSqlite(args) => args.invoke(&cancellation_token),
+            Command::ExportDelta(args) => args.invoke(&cancellation_token),
+            Command::GetRecord(args) => args.invoke(&cancellation_token),
+            Command::FileExtents(args) => args.invoke(&cancellation_token),
+            Command::Plan(args) => args.invoke(),
+            Command::MftRuns(args) => args.invoke(),
+            #[cfg(any(test, feature = "diagnostics"))]
+            Command::VerifyParsers(args) => args.invoke(&cancellation_token),
             Command::Move(args) => args.invoke(),
             Command::Rule(args) => args.invoke(),
             Command::Profile(args) => args.invoke(),
@@ -0,0 +1,211 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::published_drive_paths;
+use crate::mft::fast_entry;
+use crate::mft::mft_file::MftFile;
+use crate::mft::mft_record::MftRecord;
+use crate::mft::path_resolve::ResolvedPath;
+use crate::mft::path_resolve::resolve_paths_all_parallel;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::Context;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use std::collections::BTreeMap;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::info;
+use uom::si::information::byte;
+
+/// Write the paths added, removed or renamed between an older MFT snapshot and a drive's
+/// cached MFT.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct ExportDeltaArgs {
+    /// Drive letter whose cached `.mft` is compared against `--base`
+    #[facet(args::named, default)]
+    pub drive: String,
+
+    /// Older `.mft` or `.mft.zst` snapshot of the same drive
+    #[facet(args::named, default)]
+    pub base: String,
+
+    /// Delta file to write; one `+`, `-` or `>` line per changed path
+    #[facet(args::named, default)]
+    pub out: String,
+}
+
+/// One changed path between two snapshots of a drive.
+///
+/// Variants are ordered the way they are written: removals, then additions, then renames.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PathDelta {
+    Removed(String),
+    Added(String),
+    /// The same entry and sequence number now resolves to a different path.
+    Renamed {
+        from: String,
+        to: String,
+    },
+}
+
+impl ExportDeltaArgs {
+    /// Resolve paths in `--base` and the drive's cached MFT and write their difference to
+    /// `--out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an argument is missing, either MFT cannot be read, or the delta
+    /// file cannot be written.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let drive_letter = DriveLetterPattern(self.drive.clone()).into_single_drive_letter()?;
+        let base_path = PathBuf::from(self.base.trim());
+        let out_path = PathBuf::from(self.out.trim());
+        if base_path.as_os_str().is_empty() || out_path.as_os_str().is_empty() {
+            bail!("--base and --out must not be empty");
+        }
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+        if !mft_path.is_file() {
+            bail!(
+                "No cached MFT for drive {drive_letter} at {}; run `sync` first",
+                mft_path.display()
+            );
+        }
+
+        let base = MftFile::from_path(&base_path, cancellation_token)?;
+        let current = MftFile::from_path(&mft_path, cancellation_token)?;
+        let delta = path_delta(
+            &live_paths(drive_letter, &base)?,
+            &live_paths(drive_letter, &current)?,
+        );
+
+        let mut writer = BufWriter::new(
+            std::fs::File::create(&out_path)
+                .wrap_err_with(|| format!("Failed to create {}", out_path.display()))?,
+        );
+        write_delta(&mut writer, &delta)?;
+        writer.flush()?;
+        info!(
+            drive = %drive_letter,
+            changes = delta.len(),
+            "Wrote delta from {} to {} into {}",
+            base_path.display(),
+            mft_path.display(),
+            out_path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Map each in-use entry with a live path to its sequence number and primary path.
+///
+/// Paths through deleted entries are ignored so that a file deleted between the snapshots
+/// shows up as removed rather than as a rename into its stale location.
+fn live_paths(
+    drive_letter: char,
+    mft_file: &MftFile,
+) -> eyre::Result<BTreeMap<usize, (u16, String)>> {
+    let file_names = fast_entry::collect_filenames(mft_file);
+    let root_prefix = PathBuf::from(format!("{drive_letter}:\\"));
+    let paths = resolve_paths_all_parallel(&file_names, &root_prefix)?;
+    let record_size = mft_file.record_size().get::<byte>();
+    Ok(mft_file
+        .chunks_exact(record_size)
+        .enumerate()
+        .filter_map(|(entry_id, record)| {
+            // The primary path has the fewest deleted components, so it is live whenever
+            // any of the entry's paths is.
+            if paths
+                .paths_for(entry_id)
+                .iter()
+                .all(ResolvedPath::has_deleted_entries)
+            {
+                return None;
+            }
+            let path = paths.primary_path(entry_id)?.to_string_lossy();
+            let record = MftRecord::from_bytes_unchecked(mft_file.slice_ref(record));
+            Some((entry_id, (record.get_sequence_number(), path.into_owned())))
+        })
+        .collect())
+}
+
+/// Compare two snapshots entry by entry.
+///
+/// An entry whose sequence number changed was reused for a different file, so it counts as
+/// one removal and one addition rather than a rename.
+fn path_delta(
+    base: &BTreeMap<usize, (u16, String)>,
+    current: &BTreeMap<usize, (u16, String)>,
+) -> Vec<PathDelta> {
+    let mut delta = Vec::new();
+    for (entry_id, (sequence, path)) in base {
+        match current.get(entry_id) {
+            Some((current_sequence, current_path)) if current_sequence == sequence => {
+                if current_path != path {
+                    delta.push(PathDelta::Renamed {
+                        from: path.clone(),
+                        to: current_path.clone(),
+                    });
+                }
+            }
+            Some((_, current_path)) => {
+                delta.push(PathDelta::Removed(path.clone()));
+                delta.push(PathDelta::Added(current_path.clone()));
+            }
+            None => delta.push(PathDelta::Removed(path.clone())),
+        }
+    }
+    delta.extend(
+        current
+            .iter()
+            .filter(|(entry_id, _)| !base.contains_key(entry_id))
+            .map(|(_, (_, path))| PathDelta::Added(path.clone())),
+    );
+    delta.sort();
+    delta
+}
+
+/// Write one tab-separated line per change: `-\tpath`, `+\tpath` or `>\tfrom\tto`.
+fn write_delta(writer: &mut impl Write, delta: &[PathDelta]) -> std::io::Result<()> {
+    for change in delta {
+        match change {
+            PathDelta::Removed(path) => writeln!(writer, "-\t{path}")?,
+            PathDelta::Added(path) => writeln!(writer, "+\t{path}")?,
+            PathDelta::Renamed { from, to } => writeln!(writer, ">\t{from}\t{to}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::live_paths;
+    use super::path_delta;
+    use super::write_delta;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+
+    #[test]
+    fn delta_lists_one_added_and_one_removed_file() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        builder.file(SYNTHETIC_ROOT_RECORD, "kept.txt");
+        let removed = builder.file(SYNTHETIC_ROOT_RECORD, "removed.txt");
+        let base = MftFile::from_vec(builder.build())?;
+        builder.mark_deleted(removed);
+        builder.file(SYNTHETIC_ROOT_RECORD, "added.txt");
+        let current = MftFile::from_vec(builder.build())?;
+
+        let delta = path_delta(&live_paths('C', &base)?, &live_paths('C', &current)?);
+        let mut output = Vec::new();
+        write_delta(&mut output, &delta)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "-\tC:\\removed.txt\n+\tC:\\added.txt\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod export_delta_cli;
+
+pub use export_delta_cli::ExportDeltaArgs;
@@ -0,0 +1,284 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::published_drive_paths;
+use crate::mft::fast_entry;
+use crate::mft::mft_file::MftFile;
+use crate::mft::mft_record::MftRecord;
+use crate::mft::path_resolve::resolve_paths_all_parallel;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::Context;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use rusqlite::Connection;
+use rusqlite::params;
+use std::path::PathBuf;
+use thousands::Separable;
+use tracing::info;
+use uom::si::information::byte;
+
+/// Rows inserted per transaction.
+const EXPORT_BATCH_ROWS: usize = 10_000;
+
+/// Export resolved paths from cached `.mft` files into a `SQLite` database.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct ExportSqliteArgs {
+    /// Drive letter pattern to match drives whose cached MFTs will be exported (e.g., "*", "C", "CD", "C,D")
+    #[facet(args::positional, default)]
+    pub drive_letter_pattern: DriveLetterPattern,
+
+    /// Database file to create or update; the `files` table is created when missing and each
+    /// exported drive's earlier rows are replaced
+    #[facet(args::named)]
+    pub db: String,
+
+    /// Store the on-disk size of each file's data (compressed size for compressed files)
+    /// instead of its logical size
+    #[facet(args::named, default)]
+    pub allocated: bool,
+}
+
+impl ExportSqliteArgs {
+    /// Export every matching cached MFT into the `files` table of `--db`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the machine cache cannot be retrieved, a cached MFT cannot be read,
+    /// or the database cannot be written.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let db_path = PathBuf::from(self.db.trim());
+        if db_path.as_os_str().is_empty() {
+            bail!("--db must not be empty");
+        }
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let drive_letters = self.drive_letter_pattern.into_drive_letters()?;
+        let mut conn = Connection::open(&db_path)
+            .wrap_err_with(|| format!("Failed to open {}", db_path.display()))?;
+        create_files_table(&conn)?;
+
+        for drive_letter in drive_letters {
+            if cancellation_token.is_cancelled() {
+                bail!("Cancelled while exporting cached MFTs");
+            }
+            let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+            if !mft_path.is_file() {
+                continue;
+            }
+            let mft_file = MftFile::from_path(&mft_path, cancellation_token)?;
+            let rows = export_drive(&mut conn, drive_letter, &mft_file, self.allocated)?;
+            info!(
+                drive = %drive_letter,
+                rows = rows.separate_with_commas(),
+                "Exported {} into {}",
+                mft_path.display(),
+                db_path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn create_files_table(conn: &Connection) -> eyre::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS files (
+            drive TEXT NOT NULL,
+            entry INTEGER NOT NULL,
+            sequence INTEGER NOT NULL,
+            parent INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            path TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            is_dir INTEGER NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Insert one row per entry with a resolved path, using the entry's primary path.
+///
+/// Rows from an earlier export of the same drive are deleted first, so re-exporting does not
+/// duplicate them.
+///
+/// `size` is the logical length of the unnamed `$DATA` stream, or its on-disk size when
+/// `allocated` is set, falling back to the `$FILE_NAME` real size for records whose data
+/// lives in an extension record.
+///
+/// Returns the number of rows inserted.
+fn export_drive(
+    conn: &mut Connection,
+    drive_letter: char,
+    mft_file: &MftFile,
+    allocated: bool,
+) -> eyre::Result<usize> {
+    let file_names = fast_entry::collect_filenames(mft_file);
+    let root_prefix = PathBuf::from(format!("{drive_letter}:\\"));
+    let paths = resolve_paths_all_parallel(&file_names, &root_prefix)?;
+    let record_size = mft_file.record_size().get::<byte>();
+    let drive = drive_letter.to_string();
+    conn.execute("DELETE FROM files WHERE drive = ?1", params![drive])?;
+
+    let mut rows = 0usize;
+    let mut entries = mft_file
+        .chunks_exact(record_size)
+        .enumerate()
+        .filter_map(|(entry_id, record)| {
+            let path = paths.primary_path(entry_id)?;
+            let record = MftRecord::from_bytes_unchecked(mft_file.slice_ref(record));
+            let file_name = record.first_file_name()?;
+            Some((
+                entry_id,
+                path.to_string_lossy().into_owned(),
+                record,
+                file_name,
+            ))
+        })
+        .peekable();
+    while entries.peek().is_some() {
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO files (drive, entry, sequence, parent, name, path, size, is_dir)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (entry_id, path, record, file_name) in entries.by_ref().take(EXPORT_BATCH_ROWS) {
+                insert.execute(params![
+                    drive,
+                    i64::try_from(entry_id)?,
+                    record.get_sequence_number(),
+                    i64::try_from(file_name.parent_ref & 0xFFFF_FFFF_FFFF)?,
+                    file_name.name,
+                    path,
+                    i64::try_from(record.data_sizes().map_or(file_name.real_size, |sizes| {
+                        if allocated {
+                            sizes.on_disk
+                        } else {
+                            sizes.logical
+                        }
+                    }))?,
+                    record.flags().is_directory(),
+                ])?;
+                rows += 1;
+            }
+        }
+        tx.commit()?;
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::create_files_table;
+    use super::export_drive;
+    use crate::mft::mft_file::MftFile;
+    use rusqlite::Connection;
+
+    fn record(entry: u32, flags: u16, parent: u64, name: &str, size: u64) -> Vec<u8> {
+        let name_utf16 = name.encode_utf16().collect::<Vec<_>>();
+        let mut file_name = vec![0u8; 0x42 + name_utf16.len() * 2];
+        file_name[0..8].copy_from_slice(&((1u64 << 48) | parent).to_le_bytes());
+        file_name[0x30..0x38].copy_from_slice(&size.to_le_bytes());
+        file_name[0x40] = u8::try_from(name_utf16.len()).unwrap();
+        file_name[0x41] = 1;
+        for (index, unit) in name_utf16.iter().enumerate() {
+            file_name[0x42 + index * 2..0x44 + index * 2].copy_from_slice(&unit.to_le_bytes());
+        }
+        let attr_len = (0x18 + file_name.len()).next_multiple_of(8);
+
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x10..0x12].copy_from_slice(&1u16.to_le_bytes());
+        record[0x14..0x16].copy_from_slice(&0x38u16.to_le_bytes());
+        record[0x16..0x18].copy_from_slice(&flags.to_le_bytes());
+        record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        record[0x2C..0x30].copy_from_slice(&entry.to_le_bytes());
+        let attribute = &mut record[0x38..0x38 + attr_len];
+        attribute[0..4].copy_from_slice(&0x30u32.to_le_bytes());
+        attribute[4..8].copy_from_slice(&u32::try_from(attr_len).unwrap().to_le_bytes());
+        attribute[0x10..0x14]
+            .copy_from_slice(&u32::try_from(file_name.len()).unwrap().to_le_bytes());
+        attribute[0x14..0x16].copy_from_slice(&0x18u16.to_le_bytes());
+        attribute[0x18..0x18 + file_name.len()].copy_from_slice(&file_name);
+        record[0x38 + attr_len..0x3C + attr_len].copy_from_slice(&u32::MAX.to_le_bytes());
+        record
+    }
+
+    /// `$MFT`, the root, `docs` and `docs\notes.txt`, with empty records in between.
+    fn fixture_mft() -> eyre::Result<MftFile> {
+        let mut raw = Vec::new();
+        for entry in 0..5 {
+            let mut empty = vec![0u8; 1024];
+            empty[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+            if entry == 0 {
+                raw.extend_from_slice(&record(0, 0x01, 5, "$MFT", 0));
+            } else {
+                raw.extend_from_slice(&empty);
+            }
+        }
+        raw.extend_from_slice(&record(5, 0x03, 5, ".", 0));
+        raw.extend_from_slice(&record(6, 0x03, 5, "docs", 0));
+        raw.extend_from_slice(&record(7, 0x01, 6, "notes.txt", 1234));
+        MftFile::from_vec(raw)
+    }
+
+    fn row_count(conn: &Connection, drive: &str) -> eyre::Result<i64> {
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM files WHERE drive = ?1",
+            [drive],
+            |row| row.get(0),
+        )?)
+    }
+
+    #[test]
+    fn exported_rows_can_be_queried_back() -> eyre::Result<()> {
+        let mft_file = fixture_mft()?;
+
+        let mut conn = Connection::open_in_memory()?;
+        create_files_table(&conn)?;
+        let rows = export_drive(&mut conn, 'C', &mft_file, false)?;
+        assert_eq!(rows, 4);
+        assert_eq!(row_count(&conn, "C")?, 4);
+
+        let (entry, parent, name, size, is_dir): (i64, i64, String, i64, bool) = conn.query_row(
+            "SELECT entry, parent, name, size, is_dir FROM files WHERE path = ?1",
+            [r"C:\docs\notes.txt"],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )?;
+        assert_eq!(
+            (entry, parent, name.as_str(), size, is_dir),
+            (7, 6, "notes.txt", 1234, false)
+        );
+
+        let docs_is_dir: bool = conn.query_row(
+            "SELECT is_dir FROM files WHERE drive = 'C' AND name = 'docs'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert!(docs_is_dir);
+        Ok(())
+    }
+
+    #[test]
+    fn exporting_a_drive_twice_replaces_its_rows() -> eyre::Result<()> {
+        let mft_file = fixture_mft()?;
+
+        let mut conn = Connection::open_in_memory()?;
+        create_files_table(&conn)?;
+        export_drive(&mut conn, 'D', &mft_file, false)?;
+        export_drive(&mut conn, 'C', &mft_file, false)?;
+        let rows = export_drive(&mut conn, 'C', &mft_file, true)?;
+
+        assert_eq!(rows, 4);
+        assert_eq!(row_count(&conn, "C")?, 4);
+        assert_eq!(row_count(&conn, "D")?, 4);
+        Ok(())
+    }
+}
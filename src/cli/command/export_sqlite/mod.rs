@@ -0,0 +1,3 @@
+mod export_sqlite_cli;
+
+pub use export_sqlite_cli::ExportSqliteArgs;
@@ -0,0 +1,210 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::published_drive_paths;
+use crate::mft::mft_convert_to_path_collection::convert_mft_file_to_path_collection;
+use crate::mft::mft_file::MftFile;
+use crate::mft::mft_record::MftRecord;
+use crate::mft::mft_record_attribute::MftRecordAttribute;
+use crate::sync::SYNC_MANIFEST_FILE_NAME;
+use crate::sync::SyncManifest;
+use arbitrary::Arbitrary;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use std::io::Write;
+
+/// Print where a file's data lives on the physical disk, decoded from the cached MFT.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct FileExtentsArgs {
+    /// Absolute path of the file, e.g. `C:\Windows\notepad.exe`
+    #[facet(args::positional, default)]
+    pub path: String,
+}
+
+/// One run of a file's unnamed `$DATA`, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataExtent {
+    /// Clusters on disk, starting at `offset` from the start of the disk (or of the volume
+    /// when its disk offset is unknown).
+    Allocated { offset: u64, length: u64 },
+    /// A sparse run with no clusters behind it.
+    Sparse { length: u64 },
+}
+
+impl FileExtentsArgs {
+    /// Resolve `path` in its drive's cached MFT and print the disk extents of its `$DATA`.
+    ///
+    /// Cluster size and the volume's disk offset come from the sync manifest written when
+    /// the MFT was captured. Runs stored in extension records (files whose attributes spill
+    /// into an `$ATTRIBUTE_LIST`) are not followed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drive has no cached MFT or manifest entry, the path is not
+    /// found, or the record has no unnamed `$DATA` attribute.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let mut letters = self.path.chars();
+        let (Some(drive_letter), Some(':')) = (letters.next(), letters.next()) else {
+            bail!(
+                "Path {} must be an absolute Windows drive path like C:\\dir\\file.txt",
+                self.path
+            );
+        };
+        let drive_letter = drive_letter.to_ascii_uppercase();
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+        if !mft_path.is_file() {
+            bail!(
+                "No cached MFT for drive {drive_letter} at {}; run `sync` first",
+                mft_path.display()
+            );
+        }
+        let manifest = SyncManifest::load(&sync_dir.join(SYNC_MANIFEST_FILE_NAME))?;
+        let Some(capture) = manifest
+            .drives
+            .iter()
+            .find(|drive| drive.drive_letter == drive_letter)
+        else {
+            bail!(
+                "The sync manifest has no entry for drive {drive_letter}; run `sync` again to record its cluster size"
+            );
+        };
+
+        let mft_file = MftFile::from_path(&mft_path, cancellation_token)?;
+        let paths = convert_mft_file_to_path_collection(&drive_letter.to_string(), &mft_file)?;
+        let Some(entry) = paths.0.iter().position(|entry_paths| {
+            entry_paths.iter().any(|resolved| {
+                resolved
+                    .path
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(&self.path)
+            })
+        }) else {
+            bail!("{} was not found in the cached MFT", self.path);
+        };
+        let record = mft_file.record_at(entry as u64)?;
+        let extents = data_extents(
+            &record,
+            capture.bytes_per_cluster,
+            capture.starting_offset.unwrap_or(0),
+        )?;
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "record:         {entry}")?;
+        match capture.disk_number {
+            Some(disk_number) if capture.starting_offset.is_some() => {
+                writeln!(stdout, "offsets from:   PhysicalDrive{disk_number}")?;
+            }
+            _ => writeln!(stdout, "offsets from:   start of volume {drive_letter}:")?,
+        }
+        if let Some(sizes) = record.data_sizes() {
+            writeln!(stdout, "logical size:   {}", sizes.logical)?;
+            writeln!(
+                stdout,
+                "on-disk size:   {}{}",
+                sizes.on_disk,
+                if sizes.is_compressed {
+                    " (compressed)"
+                } else {
+                    ""
+                }
+            )?;
+        }
+        write_extents(&mut stdout, extents.as_deref())?;
+        Ok(())
+    }
+}
+
+/// Decode the unnamed `$DATA` runs of `record` into byte extents, adding `volume_offset` to
+/// every allocated run. Returns `None` when the data is resident in the record.
+fn data_extents(
+    record: &MftRecord,
+    bytes_per_cluster: u64,
+    volume_offset: u64,
+) -> eyre::Result<Option<Vec<DataExtent>>> {
+    let Some(data) = record.iter_attributes().find(|attribute| {
+        attribute.get_attr_type() == MftRecordAttribute::TYPE_DOLLAR_DATA
+            && attribute.get_name_len() == 0
+    }) else {
+        bail!(
+            "Record {} has no unnamed $DATA attribute",
+            *record.get_record_number()
+        );
+    };
+    let Some(run_list) = data.get_run_list()? else {
+        return Ok(None);
+    };
+    run_list
+        .iter()
+        .map(|run| {
+            let run = run?;
+            let length = run.length_clusters * bytes_per_cluster;
+            Ok(match run.local_cluster_network_start_entry_index {
+                Some(lcn) => DataExtent::Allocated {
+                    offset: volume_offset + lcn * bytes_per_cluster,
+                    length,
+                },
+                None => DataExtent::Sparse { length },
+            })
+        })
+        .collect::<eyre::Result<Vec<_>>>()
+        .map(Some)
+}
+
+fn write_extents(writer: &mut impl Write, extents: Option<&[DataExtent]>) -> std::io::Result<()> {
+    let Some(extents) = extents else {
+        return writeln!(writer, "resident");
+    };
+    for extent in extents {
+        match extent {
+            DataExtent::Allocated { offset, length } => {
+                writeln!(writer, "offset {offset:#014x}  length {length}")?;
+            }
+            DataExtent::Sparse { length } => writeln!(writer, "sparse          length {length}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataExtent;
+    use super::data_extents;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::testing::SYNTHETIC_CLUSTER_SIZE;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+
+    #[test]
+    fn non_resident_data_decodes_to_disk_extents() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        let file = builder.file_with_size(SYNTHETIC_ROOT_RECORD, "disk.img", 100_000);
+        builder.set_non_resident_data(
+            file,
+            &[(16, Some(0x100)), (4, None), (8, Some(0x80))],
+            100_000,
+        );
+        let mft = MftFile::from_vec(builder.build())?;
+
+        let volume_offset = 1_048_576;
+        let extents = data_extents(&mft.record_at(file)?, SYNTHETIC_CLUSTER_SIZE, volume_offset)?;
+
+        assert_eq!(
+            extents,
+            Some(vec![
+                DataExtent::Allocated {
+                    offset: volume_offset + 0x100 * SYNTHETIC_CLUSTER_SIZE,
+                    length: 16 * SYNTHETIC_CLUSTER_SIZE,
+                },
+                DataExtent::Sparse {
+                    length: 4 * SYNTHETIC_CLUSTER_SIZE,
+                },
+                DataExtent::Allocated {
+                    offset: volume_offset + 0x80 * SYNTHETIC_CLUSTER_SIZE,
+                    length: 8 * SYNTHETIC_CLUSTER_SIZE,
+                },
+            ])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod file_extents_cli;
+
+pub use file_extents_cli::FileExtentsArgs;
@@ -0,0 +1,153 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::published_drive_paths;
+use crate::mft::mft_file::MftFile;
+use crate::mft::mft_record::MftRecord;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use std::io::Write;
+
+/// Print one record from a cached `.mft` file.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct GetRecordArgs {
+    /// Drive letter whose cached MFT holds the record
+    #[facet(args::named, default)]
+    pub drive: String,
+
+    /// Record number (index into the cached MFT)
+    #[facet(args::named, default)]
+    pub record: u64,
+
+    /// Print a hex dump of the fixed-up record bytes instead of the parsed header
+    #[facet(args::named, default)]
+    pub hex: bool,
+}
+
+impl GetRecordArgs {
+    /// Load the cached MFT for `--drive` and print record `--record`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drive has no cached MFT, the MFT cannot be read,
+    /// or the record number is out of range.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let drive_letter = DriveLetterPattern(self.drive.clone()).into_single_drive_letter()?;
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+        if !mft_path.is_file() {
+            bail!(
+                "No cached MFT for drive {drive_letter} at {}; run `sync` first",
+                mft_path.display()
+            );
+        }
+        let mft_file = MftFile::from_path(&mft_path, cancellation_token)?;
+        let record = mft_file.record_at(self.record)?;
+
+        let mut stdout = std::io::stdout().lock();
+        if self.hex {
+            write_hex_dump(&mut stdout, &record)?;
+        } else {
+            write_header(&mut stdout, self.record, &record)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_header(writer: &mut impl Write, index: u64, record: &MftRecord) -> eyre::Result<()> {
+    writeln!(writer, "index:                  {index}")?;
+    writeln!(
+        writer,
+        "signature:              {}",
+        record.get_signature().escape_ascii()
+    )?;
+    writeln!(
+        writer,
+        "record number:          {}",
+        *record.get_record_number()
+    )?;
+    writeln!(
+        writer,
+        "sequence:               {}",
+        record.get_sequence_number()
+    )?;
+    writeln!(
+        writer,
+        "flags:                  {:#06x}",
+        record.flags().raw()
+    )?;
+    writeln!(writer, "in use:                 {}", record.is_in_use())?;
+    writeln!(
+        writer,
+        "directory:              {}",
+        record.flags().is_directory()
+    )?;
+    writeln!(
+        writer,
+        "hard links:             {}",
+        record.get_hard_link_count()
+    )?;
+    writeln!(writer, "used size:              {}", record.get_used_size())?;
+    writeln!(
+        writer,
+        "allocated size:         {}",
+        record.get_allocated_size()
+    )?;
+    writeln!(
+        writer,
+        "first attribute offset: {:#x}",
+        record.get_first_attribute_offset()
+    )?;
+    writeln!(
+        writer,
+        "base reference:         {:#x}",
+        record.get_base_reference_raw()
+    )?;
+    Ok(())
+}
+
+fn write_hex_dump(writer: &mut impl Write, bytes: &[u8]) -> std::io::Result<()> {
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        write!(writer, "{:08x}  ", line * 16)?;
+        for column in 0..16 {
+            match chunk.get(column) {
+                Some(byte) => write!(writer, "{byte:02x} ")?,
+                None => write!(writer, "   ")?,
+            }
+            if column == 7 {
+                write!(writer, " ")?;
+            }
+        }
+        let ascii = chunk
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        writeln!(writer, " |{ascii}|")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_hex_dump;
+
+    #[test]
+    fn hex_dump_prints_offsets_bytes_and_ascii() {
+        let mut output = Vec::new();
+        write_hex_dump(&mut output, b"FILE0\x00\x03\x00 record bytes!").unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("00000000  46 49 4c 45 30 00 03 00  20 72"));
+        assert!(lines[0].ends_with("|FILE0... record |"));
+        assert!(lines[1].starts_with("00000010  62 79 74 65 73 21"));
+    }
+}
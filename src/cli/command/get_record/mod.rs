@@ -0,0 +1,3 @@
+mod get_record_cli;
+
+pub use get_record_cli::GetRecordArgs;
@@ -0,0 +1,143 @@
+use crate::machine::config::COMPRESSED_MFT_CACHE_FILE_EXTENSION;
+use crate::machine::config::MFT_CACHE_FILE_EXTENSION;
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use eyre::Context;
+use facet::Facet;
+use figue::{self as args};
+use humansize::BINARY;
+use std::io::Write;
+use std::path::Path;
+
+/// List the drives that have a cached `.mft` or `.mft.zst` in the sync dir.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct ListCachedArgs {
+    /// Print the cached files as JSON instead of a table
+    #[facet(args::named, default)]
+    pub json: bool,
+}
+
+/// One cached MFT snapshot as printed by `list-cached`.
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+struct CachedMft {
+    drive: char,
+    path: String,
+    size: u64,
+    /// Last modified time as an RFC 3339 UTC timestamp
+    modified: String,
+    compressed: bool,
+}
+
+impl ListCachedArgs {
+    /// Scan the configured sync dir and print each cached MFT.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sync dir cannot be resolved or read.
+    pub fn invoke(self) -> eyre::Result<()> {
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let cached = scan_cached_mfts(&sync_dir)?;
+        let mut stdout = std::io::stdout().lock();
+        if self.json {
+            stdout.write_all(&facet_json::to_vec_pretty(&cached)?)?;
+            writeln!(stdout)?;
+        } else if cached.is_empty() {
+            writeln!(stdout, "No cached MFTs in {}", sync_dir.display())?;
+        } else {
+            write_cached_mfts(&mut stdout, &cached)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find `<letter>.mft` and `<letter>.mft.zst` files directly inside `sync_dir`, ordered by drive.
+fn scan_cached_mfts(sync_dir: &Path) -> eyre::Result<Vec<CachedMft>> {
+    let entries = std::fs::read_dir(sync_dir)
+        .wrap_err_with(|| format!("Failed to read sync dir {}", sync_dir.display()))?;
+    let mut cached = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        let (stem, compressed) =
+            if let Some(stem) = file_name.strip_suffix(COMPRESSED_MFT_CACHE_FILE_EXTENSION) {
+                (stem, true)
+            } else if let Some(stem) = file_name.strip_suffix(MFT_CACHE_FILE_EXTENSION) {
+                (stem, false)
+            } else {
+                continue;
+            };
+        let mut chars = stem.chars();
+        let (Some(drive), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        if !drive.is_ascii_alphabetic() {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        cached.push(CachedMft {
+            drive: drive.to_ascii_uppercase(),
+            path: entry.path().display().to_string(),
+            size: metadata.len(),
+            modified: DateTime::<Utc>::from(metadata.modified()?).to_rfc3339(),
+            compressed,
+        });
+    }
+    cached
+        .sort_by(|left, right| (left.drive, left.compressed).cmp(&(right.drive, right.compressed)));
+    Ok(cached)
+}
+
+fn write_cached_mfts(writer: &mut impl Write, cached: &[CachedMft]) -> eyre::Result<()> {
+    writeln!(
+        writer,
+        "{:<6} {:>12} {:<19}  Path",
+        "Drive", "Size", "Modified"
+    )?;
+    for file in cached {
+        let modified = DateTime::parse_from_rfc3339(&file.modified)?
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S");
+        writeln!(
+            writer,
+            "{:<6} {:>12} {modified}  {}",
+            file.drive,
+            humansize::format_size(file.size, BINARY),
+            file.path
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scan_cached_mfts;
+
+    #[test]
+    fn scan_finds_plain_and_compressed_snapshots_only() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("D.mft.zst"), [0_u8; 8])?;
+        std::fs::write(dir.path().join("C.mft"), [0_u8; 1024])?;
+        std::fs::write(dir.path().join("C.mft_search_index"), [0_u8; 4])?;
+        std::fs::write(dir.path().join("C.mft.partial"), [0_u8; 4])?;
+        std::fs::write(dir.path().join("notes.mft"), [0_u8; 4])?;
+
+        let cached = scan_cached_mfts(dir.path())?;
+
+        let summary = cached
+            .iter()
+            .map(|file| (file.drive, file.size, file.compressed))
+            .collect::<Vec<_>>();
+        assert_eq!(summary, vec![('C', 1024, false), ('D', 8, true)]);
+        assert!(cached.iter().all(|file| !file.modified.is_empty()));
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod list_cached_cli;
+
+pub use list_cached_cli::ListCachedArgs;
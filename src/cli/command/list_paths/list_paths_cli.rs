@@ -1,16 +1,27 @@
 use crate::cancellation::CancellationToken;
+use crate::machine::config::published_drive_paths;
 use crate::mft::mft_file::MftFile;
+use crate::mft::path_resolve::ORPHAN_DIR_NAME;
+use crate::presentation::DriveMatchCounts;
+use crate::presentation::OutputFile;
+use crate::presentation::PathStyle;
 use crate::windows_utils::storage::DriveLetterPattern;
 use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
 use eyre::Context;
 use facet::Facet;
 use figue::{self as args};
 use mft::FileNameAttr;
 use mft::MftParser;
 use mft::attribute::MftAttributeContent;
+use mft::attribute::MftAttributeType;
 use mft::attribute::x30::FileNamespace;
+use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 use std::io::Cursor;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Instant;
 use tracing::info;
@@ -30,148 +41,751 @@ fn choose_dir<'a>(
 }
 
 #[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
 pub struct ListPathsArgs {
     /// Drive letter pattern to match drives whose cached MFTs will be traversed (e.g., "*", "C", "CD", "C,D")
     #[facet(args::positional, default)]
     pub drive_letter_pattern: DriveLetterPattern,
+
+    /// Output format: `text` prints `\path` lines, `json` an array of records, `ndjson` one record per line
+    #[facet(args::named, default)]
+    pub format: ListPathsOutputFormat,
+
+    #[facet(flatten)]
+    pub path_style: PathStyle,
+
+    #[facet(flatten)]
+    pub output: OutputFile,
+
+    /// Print only the highest-precedence path per entry instead of one path per hard link
+    #[facet(args::named, default)]
+    pub primary: bool,
+
+    /// Print every duplicate same-precedence `FILE_NAME` conflict to stderr after the listing
+    #[facet(args::named, default)]
+    pub report_conflicts: bool,
+
+    /// Skip update sequence fixups, for cached MFTs saved after fixups were already applied
+    #[facet(args::named, default)]
+    pub assume_fixed: bool,
+
+    /// Only list entries whose `$STANDARD_INFORMATION` modified time is at or after this RFC 3339 timestamp
+    #[facet(args::named)]
+    pub since: Option<String>,
+
+    /// Only list entries whose `$STANDARD_INFORMATION` modified time is at or before this RFC 3339 timestamp
+    #[facet(args::named)]
+    pub until: Option<String>,
+
+    /// Also list `path:stream` for every named `$DATA` stream (alternate data stream) on an entry
+    #[facet(args::named, default)]
+    pub include_streams: bool,
+
+    /// Print only the number of listed paths per drive and in total instead of the paths
+    #[facet(args::named, default)]
+    pub count_only: bool,
+
+    /// List entries whose parent chain never reaches the root under `\$Orphan\` instead of skipping them
+    #[facet(args::named, default)]
+    pub include_orphans: bool,
+}
+
+/// Inclusive modified-time window applied by `--since` / `--until`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ModifiedWindow {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl ModifiedWindow {
+    fn parse(since: Option<&str>, until: Option<&str>) -> eyre::Result<Self> {
+        let parse = |flag: &str, value: Option<&str>| {
+            value
+                .map(|value| {
+                    DateTime::parse_from_rfc3339(value)
+                        .map(|time| time.with_timezone(&Utc))
+                        .wrap_err_with(|| {
+                            format!("{flag} must be an RFC 3339 timestamp, got {value:?}")
+                        })
+                })
+                .transpose()
+        };
+        let window = Self {
+            since: parse("--since", since)?,
+            until: parse("--until", until)?,
+        };
+        if let (Some(since), Some(until)) = (window.since, window.until) {
+            eyre::ensure!(since <= until, "--since {since} is after --until {until}");
+        }
+        Ok(window)
+    }
+
+    fn is_active(self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    /// Entries without a modified time never match an active window.
+    fn contains(self, modified: Option<DateTime<Utc>>) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        modified.is_some_and(|modified| {
+            self.since.is_none_or(|since| modified >= since)
+                && self.until.is_none_or(|until| modified <= until)
+        })
+    }
+}
+
+#[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[repr(u8)]
+#[strum(serialize_all = "kebab-case")]
+#[facet(rename_all = "kebab-case")]
+pub enum ListPathsOutputFormat {
+    #[default]
+    Text,
+    Json,
+    Ndjson,
+}
+
+/// A listed path as emitted by the `json` and `ndjson` output formats.
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct ListedPath {
+    pub drive: String,
+    pub path: String,
+    pub entry: u64,
+    pub sequence: u16,
+}
+
+/// Two `FILE_NAME` attributes on one record sharing parent, name, and namespace precedence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileNameConflict {
+    record: MftReference,
+    parent: MftReference,
+    name: String,
+    namespace: String,
+}
+
+/// Paths listed from a single drive's cached MFT, in traversal order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DriveListedPaths {
+    drive_letter: char,
+    pub(crate) paths: Vec<(MftReference, String)>,
+    conflicts: Vec<FileNameConflict>,
 }
 
 impl ListPathsArgs {
     /// List paths from cached MFT files.
     ///
+    /// Drives are processed in parallel and printed in drive letter order once all have finished.
+    ///
     /// # Errors
     ///
     /// Returns an error if the machine cache cannot be retrieved, drive letters cannot be resolved,
-    /// or if reading/parsing MFT files fails.
+    /// reading/parsing MFT files fails, or the output file cannot be written.
     // cli[impl command.list-paths.cached-mft-input]
-    #[expect(
-        clippy::too_many_lines,
-        reason = "function processes MFT data in a single pass for performance"
-    )]
     pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let modified_window = ModifiedWindow::parse(self.since.as_deref(), self.until.as_deref())?;
         let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
         // Resolve drive letters from pattern
         let drive_letters = self.drive_letter_pattern.into_drive_letters()?;
         // Build list of existing cached MFT files for matching drives
-        let mft_files: Vec<PathBuf> = drive_letters
+        let mft_files: Vec<(char, PathBuf)> = drive_letters
             .into_iter()
-            .map(|d| sync_dir.join(format!("{d}.mft")))
-            .filter(|p| p.is_file())
+            .map(|d| (d, published_drive_paths(&sync_dir, d).mft_path))
+            .filter(|(_, p)| p.is_file())
             .collect();
 
-        for mft_file_path in &mft_files {
-            let mft_file = MftFile::from_path(mft_file_path, cancellation_token)?;
-            let mft_bytes: &[u8] = &mft_file;
-            info!("Loaded MFT file: {}", mft_file_path.display());
-
-            info!("Parsing MFT file: {}", mft_file_path.display());
-            let start = Instant::now();
-            let mut parser =
-                MftParser::from_read_seek(Cursor::new(mft_bytes), Some(mft_bytes.len() as u64))
-                    .wrap_err_with(|| {
-                        format!("Failed to parse MFT bytes from {}", mft_file_path.display())
-                    })?;
+        let mut drives = mft_files
+            .par_iter()
+            .map(|(drive_letter, mft_file_path)| {
+                list_drive_paths(
+                    *drive_letter,
+                    mft_file_path,
+                    self.primary,
+                    self.assume_fixed,
+                    self.include_streams,
+                    self.include_orphans,
+                    modified_window,
+                    cancellation_token,
+                )
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
 
-            // Collect canonical FILE_NAME (x30) attributes per MFT entry.
-            // For each (parent, name) pair keep only highest precedence namespace.
-            let mut x30_map = FxHashMap::<MftReference, Vec<FileNameAttr>>::default();
-            let precedence = [
-                FileNamespace::Win32,
-                FileNamespace::Win32AndDos,
-                FileNamespace::POSIX,
-                FileNamespace::DOS,
-            ];
-            let prec_index = |ns: &FileNamespace| {
-                precedence
-                    .iter()
-                    .position(|p| p == ns)
-                    .unwrap_or(precedence.len())
-            };
-
-            for entry in parser.iter_entries() {
-                let entry = match entry {
-                    Ok(x) => x,
-                    Err(e) => {
-                        warn!(
-                            "Failed to parse entry from {}: {}",
-                            mft_file_path.display(),
-                            e
-                        );
-                        continue;
-                    }
-                };
-                for x30 in entry
-                    .iter_attributes()
-                    .filter_map(Result::ok)
-                    .filter_map(|attr| match attr.data {
-                        MftAttributeContent::AttrX30(data) => Some(data),
-                        _ => None,
-                    })
-                {
-                    let key = MftReference {
-                        entry: entry.header.record_number,
-                        sequence: entry.header.sequence,
-                    };
-                    let list = x30_map.entry(key).or_default();
-                    if let Some(existing) = list
-                        .iter_mut()
-                        .find(|f| f.parent == x30.parent && f.name == x30.name)
-                    {
-                        let existing_rank = prec_index(&existing.namespace);
-                        let new_rank = prec_index(&x30.namespace);
-                        if new_rank < existing_rank {
-                            *existing = x30; // better namespace precedence
-                        } else if new_rank == existing_rank {
-                            warn!(
-                                "Duplicate FILE_NAME same precedence for {:?} parent {:?} name {:?} {:?}",
-                                key, x30.parent, x30.name, x30.namespace
-                            );
-                        }
-                        // lower precedence ignored
-                    } else {
-                        list.push(x30);
-                    }
+        let conflicts = drives
+            .iter_mut()
+            .map(|drive| (drive.drive_letter, std::mem::take(&mut drive.conflicts)))
+            .collect::<Vec<_>>();
+        {
+            let mut output = self.output.open()?;
+            if self.count_only {
+                listed_path_counts(&drives).write(&mut output)?;
+            } else {
+                write_listed_paths(&mut output, self.format, self.path_style, drives)?;
+            }
+            output.flush()?;
+        }
+        for (drive_letter, conflicts) in conflicts {
+            if conflicts.is_empty() {
+                continue;
+            }
+            if !self.report_conflicts {
+                warn!(
+                    "Drive {drive_letter} has {} duplicate same-precedence FILE_NAME conflict(s); pass --report-conflicts to list them",
+                    conflicts.len()
+                );
+                continue;
+            }
+            for conflict in conflicts {
+                eprintln!(
+                    "{drive_letter}: duplicate FILE_NAME on record {} (seq {}) parent {} (seq {}) name {:?} namespace {}",
+                    conflict.record.entry,
+                    conflict.record.sequence,
+                    conflict.parent.entry,
+                    conflict.parent.sequence,
+                    conflict.name,
+                    conflict.namespace
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keep the highest-precedence `FILE_NAME` per (parent, name) pair on `record`, recording
+/// ties in `conflicts` once per pair.
+fn insert_canonical_link(
+    x30_map: &mut FxHashMap<MftReference, Vec<FileNameAttr>>,
+    conflicts: &mut Vec<FileNameConflict>,
+    record: MftReference,
+    x30: FileNameAttr,
+    prec_index: &impl Fn(&FileNamespace) -> usize,
+) {
+    let list = x30_map.entry(record).or_default();
+    if let Some(existing) = list
+        .iter_mut()
+        .find(|f| f.parent == x30.parent && f.name == x30.name)
+    {
+        let existing_rank = prec_index(&existing.namespace);
+        let new_rank = prec_index(&x30.namespace);
+        if new_rank < existing_rank {
+            *existing = x30; // better namespace precedence
+        } else if new_rank == existing_rank
+            && !conflicts.iter().any(|conflict| {
+                conflict.record == record
+                    && conflict.parent == x30.parent
+                    && conflict.name == x30.name
+            })
+        {
+            conflicts.push(FileNameConflict {
+                record,
+                parent: x30.parent,
+                name: x30.name,
+                namespace: format!("{:?}", x30.namespace),
+            });
+        }
+        // lower precedence ignored
+    } else {
+        list.push(x30);
+    }
+}
+
+/// Parse one cached MFT and build a path for every canonical `FILE_NAME` link.
+#[expect(
+    clippy::too_many_lines,
+    reason = "function processes MFT data in a single pass for performance"
+)]
+pub(crate) fn list_drive_paths(
+    drive_letter: char,
+    mft_file_path: &Path,
+    primary_only: bool,
+    assume_fixed: bool,
+    include_streams: bool,
+    include_orphans: bool,
+    modified_window: ModifiedWindow,
+    cancellation_token: &CancellationToken,
+) -> eyre::Result<DriveListedPaths> {
+    let mft_file = if assume_fixed {
+        MftFile::from_path_assume_fixed(mft_file_path, cancellation_token)?
+    } else {
+        MftFile::from_path(mft_file_path, cancellation_token)?
+    };
+    let mft_bytes: &[u8] = &mft_file;
+    info!("Loaded MFT file: {}", mft_file_path.display());
+
+    info!("Parsing MFT file: {}", mft_file_path.display());
+    let start = Instant::now();
+    let mut parser =
+        MftParser::from_read_seek(Cursor::new(mft_bytes), Some(mft_bytes.len() as u64))
+            .wrap_err_with(|| {
+                format!("Failed to parse MFT bytes from {}", mft_file_path.display())
+            })?;
+
+    // Collect canonical FILE_NAME (x30) attributes per MFT entry.
+    // For each (parent, name) pair keep only highest precedence namespace.
+    let mut x30_map = FxHashMap::<MftReference, Vec<FileNameAttr>>::default();
+    let mut modified_times = FxHashMap::<MftReference, DateTime<Utc>>::default();
+    let mut stream_names = FxHashMap::<MftReference, Vec<String>>::default();
+    let mut conflicts = Vec::new();
+    let precedence = [
+        FileNamespace::Win32,
+        FileNamespace::Win32AndDos,
+        FileNamespace::POSIX,
+        FileNamespace::DOS,
+    ];
+    let prec_index = |ns: &FileNamespace| {
+        precedence
+            .iter()
+            .position(|p| p == ns)
+            .unwrap_or(precedence.len())
+    };
+
+    for entry in parser.iter_entries() {
+        let entry = match entry {
+            Ok(x) => x,
+            Err(e) => {
+                warn!(
+                    "Failed to parse entry from {}: {}",
+                    mft_file_path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        let key = MftReference {
+            entry: entry.header.record_number,
+            sequence: entry.header.sequence,
+        };
+        for attr in entry.iter_attributes().filter_map(Result::ok) {
+            if include_streams
+                && attr.header.type_code == MftAttributeType::DATA
+                && !attr.header.name.is_empty()
+            {
+                let names = stream_names.entry(key).or_default();
+                if !names.contains(&attr.header.name) {
+                    names.push(attr.header.name.clone());
                 }
             }
-            let elapsed = start.elapsed();
-            let entry_count = x30_map.len();
-            let link_count: usize = x30_map.values().map(std::vec::Vec::len).sum();
-            info!(
-                "Indexed {} MFT entries ({} canonical FILE_NAME links) in {:.2?}",
-                entry_count, link_count, elapsed
-            );
+            match attr.data {
+                MftAttributeContent::AttrX30(x30) => {
+                    insert_canonical_link(&mut x30_map, &mut conflicts, key, x30, &prec_index);
+                }
+                MftAttributeContent::AttrX10(x10) if modified_window.is_active() => {
+                    modified_times.insert(key, x10.modified);
+                }
+                _ => {}
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    let entry_count = x30_map.len();
+    let link_count: usize = x30_map.values().map(std::vec::Vec::len).sum();
+    info!(
+        "Indexed {} MFT entries ({} canonical FILE_NAME links) in {:.2?}",
+        entry_count, link_count, elapsed
+    );
 
-            for (entry_ref, links) in &x30_map {
-                if entry_ref.entry == ROOT_ENTRY {
+    let mut paths = build_listed_paths(
+        &x30_map,
+        link_count,
+        primary_only,
+        include_orphans,
+        &prec_index,
+    );
+    if modified_window.is_active() {
+        paths.retain(|(entry_ref, _)| {
+            modified_window.contains(modified_times.get(entry_ref).copied())
+        });
+        info!(
+            "{} paths on drive {drive_letter} fall within the modified-time window",
+            paths.len()
+        );
+    }
+    if include_streams {
+        paths = with_stream_paths(paths, &stream_names);
+    }
+    Ok(DriveListedPaths {
+        drive_letter,
+        paths,
+        conflicts,
+    })
+}
+
+/// Follow each listed path with one `path:stream` entry per named `$DATA` stream on its record.
+fn with_stream_paths(
+    paths: Vec<(MftReference, String)>,
+    stream_names: &FxHashMap<MftReference, Vec<String>>,
+) -> Vec<(MftReference, String)> {
+    let stream_count = paths
+        .iter()
+        .filter_map(|(entry_ref, _)| stream_names.get(entry_ref))
+        .map(Vec::len)
+        .sum::<usize>();
+    let mut rtn = Vec::with_capacity(paths.len() + stream_count);
+    for (entry_ref, path) in paths {
+        let stream_paths = stream_names
+            .get(&entry_ref)
+            .into_iter()
+            .flatten()
+            .map(|stream| (entry_ref, format!("{path}:{stream}")))
+            .collect::<Vec<_>>();
+        rtn.push((entry_ref, path));
+        rtn.extend(stream_paths);
+    }
+    rtn
+}
+
+/// Build a `\`-rooted path for every canonical link, or only the highest-precedence link
+/// per entry when `primary_only` is set.
+///
+/// Links whose parent chain stops short of the root (a missing or reused parent record, or
+/// a cycle) are orphans: skipped, or listed as `\$Orphan\<name>` when `include_orphans` is
+/// set, matching [`crate::mft::path_resolve::OrphanEntry::path`].
+fn build_listed_paths(
+    x30_map: &FxHashMap<MftReference, Vec<FileNameAttr>>,
+    link_count: usize,
+    primary_only: bool,
+    include_orphans: bool,
+    prec_index: &impl Fn(&FileNamespace) -> usize,
+) -> Vec<(MftReference, String)> {
+    let mut paths = Vec::with_capacity(link_count);
+    for (entry_ref, links) in x30_map {
+        if entry_ref.entry == ROOT_ENTRY {
+            continue;
+        }
+        let mut seen = std::collections::HashSet::<String>::new();
+        let links = if primary_only {
+            std::slice::from_ref(choose_dir(links, prec_index))
+        } else {
+            links.as_slice()
+        };
+        for link in links {
+            // one output per hard link
+            // Build path components
+            let mut components: Vec<&str> = Vec::new();
+            components.push(&link.name);
+            let mut parent_ref = link.parent;
+            let mut is_orphan = false;
+            while parent_ref.entry != ROOT_ENTRY {
+                let Some(parent_links) = x30_map
+                    .get(&parent_ref)
+                    .filter(|_| components.len() <= x30_map.len())
+                else {
+                    is_orphan = true;
+                    break;
+                };
+                let parent_attr = choose_dir(parent_links, prec_index);
+                components.push(parent_attr.name.as_str());
+                parent_ref = parent_attr.parent;
+            }
+            if is_orphan {
+                if !include_orphans {
                     continue;
                 }
-                let mut seen = std::collections::HashSet::<String>::new();
-                for link in links {
-                    // one output per hard link
-                    // Build path components
-                    let mut components: Vec<&str> = Vec::new();
-                    components.push(&link.name);
-                    let mut parent_ref = link.parent;
-                    while parent_ref.entry != ROOT_ENTRY {
-                        if let Some(parent_links) = x30_map.get(&parent_ref) {
-                            let parent_attr = choose_dir(parent_links, &prec_index);
-                            components.push(parent_attr.name.as_str());
-                            parent_ref = parent_attr.parent;
-                        } else {
-                            break;
-                        }
-                    }
-                    let mut full = String::new();
-                    for comp in components.iter().rev() {
-                        full.push('\\');
-                        full.push_str(comp);
-                    }
-                    if seen.insert(full.clone()) {
-                        println!("{full}");
+                components.truncate(1);
+                components.push(ORPHAN_DIR_NAME);
+            }
+            let mut full = String::new();
+            for comp in components.iter().rev() {
+                full.push('\\');
+                full.push_str(comp);
+            }
+            if seen.insert(full.clone()) {
+                paths.push((*entry_ref, full));
+            }
+        }
+    }
+    paths
+}
+
+/// Number of listed paths per drive.
+fn listed_path_counts(drives: &[DriveListedPaths]) -> DriveMatchCounts {
+    let mut counts = DriveMatchCounts::default();
+    for drive in drives {
+        counts.add(drive.drive_letter, drive.paths.len());
+    }
+    counts
+}
+
+/// Write listed paths for all drives in drive letter order, independent of the order the
+/// drives finished processing.
+fn write_listed_paths(
+    writer: &mut impl Write,
+    format: ListPathsOutputFormat,
+    path_style: PathStyle,
+    mut drives: Vec<DriveListedPaths>,
+) -> eyre::Result<()> {
+    drives.sort_by_key(|drive| drive.drive_letter);
+    let mut emitted_any = false;
+    if format == ListPathsOutputFormat::Json {
+        write!(writer, "[")?;
+    }
+    for drive in &drives {
+        for (entry_ref, path) in &drive.paths {
+            let path = path_style.apply(path);
+            match format {
+                ListPathsOutputFormat::Text => writeln!(writer, "{path}")?,
+                ListPathsOutputFormat::Json | ListPathsOutputFormat::Ndjson => {
+                    let record = facet_json::to_string(&ListedPath {
+                        drive: drive.drive_letter.to_string(),
+                        path: format!("{}:{path}", drive.drive_letter),
+                        entry: entry_ref.entry,
+                        sequence: entry_ref.sequence,
+                    })?;
+                    if format == ListPathsOutputFormat::Ndjson {
+                        writeln!(writer, "{record}")?;
+                    } else if emitted_any {
+                        write!(writer, ",{record}")?;
+                    } else {
+                        write!(writer, "{record}")?;
                     }
                 }
             }
+            emitted_any = true;
         }
+    }
+    if format == ListPathsOutputFormat::Json {
+        writeln!(writer, "]")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modified_window_keeps_only_timestamps_inside_the_range() -> eyre::Result<()> {
+        let window = ModifiedWindow::parse(
+            Some("2024-01-01T00:00:00Z"),
+            Some("2024-06-30T23:59:59+00:00"),
+        )?;
+        let at = |value: &str| -> eyre::Result<Option<DateTime<Utc>>> {
+            Ok(Some(
+                DateTime::parse_from_rfc3339(value)?.with_timezone(&Utc),
+            ))
+        };
+        let dataset = [
+            ("before.txt", at("2023-12-31T23:59:59Z")?),
+            ("start.txt", at("2024-01-01T00:00:00Z")?),
+            ("middle.txt", at("2024-03-15T12:00:00+02:00")?),
+            ("after.txt", at("2024-07-01T00:00:00Z")?),
+            ("no-timestamp.txt", None),
+        ];
+
+        let kept = dataset
+            .iter()
+            .filter(|(_, modified)| window.contains(*modified))
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(kept, vec!["start.txt", "middle.txt"]);
+        assert!(ModifiedWindow::default().contains(None));
+        assert!(ModifiedWindow::parse(Some("yesterday"), None).is_err());
+        assert!(
+            ModifiedWindow::parse(Some("2024-02-01T00:00:00Z"), Some("2024-01-01T00:00:00Z"))
+                .is_err()
+        );
         Ok(())
     }
+
+    fn drive(drive_letter: char, paths: &[(u64, &str)]) -> DriveListedPaths {
+        DriveListedPaths {
+            drive_letter,
+            paths: paths
+                .iter()
+                .map(|(entry, path)| {
+                    (
+                        MftReference {
+                            entry: *entry,
+                            sequence: 1,
+                        },
+                        (*path).to_owned(),
+                    )
+                })
+                .collect(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    fn file_name_attr(parent_entry: u64, name: &str, namespace: u8) -> FileNameAttr {
+        let name_utf16 = name.encode_utf16().collect::<Vec<_>>();
+        let mut raw = vec![0u8; 66];
+        raw[0..8].copy_from_slice(&(parent_entry | (1 << 48)).to_le_bytes());
+        raw[64] = u8::try_from(name_utf16.len()).unwrap();
+        raw[65] = namespace;
+        for unit in name_utf16 {
+            raw.extend_from_slice(&unit.to_le_bytes());
+        }
+        FileNameAttr::from_stream(&mut Cursor::new(raw)).unwrap()
+    }
+
+    #[test]
+    fn same_precedence_file_names_are_recorded_as_one_conflict() {
+        let precedence = |ns: &FileNamespace| usize::from(ns != &FileNamespace::Win32);
+        let record = MftReference {
+            entry: 40,
+            sequence: 1,
+        };
+        let mut x30_map = FxHashMap::default();
+        let mut conflicts = Vec::new();
+        for _ in 0..3 {
+            insert_canonical_link(
+                &mut x30_map,
+                &mut conflicts,
+                record,
+                file_name_attr(ROOT_ENTRY, "a.txt", 1),
+                &precedence,
+            );
+        }
+
+        assert_eq!(x30_map[&record].len(), 1);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].record, record);
+        assert_eq!(conflicts[0].parent.entry, ROOT_ENTRY);
+        assert_eq!(conflicts[0].name, "a.txt");
+        assert_eq!(conflicts[0].namespace, "Win32");
+    }
+
+    #[test]
+    fn output_is_stable_regardless_of_completion_order() {
+        let c = drive('C', &[(40, r"\Users"), (41, r"\Users\a.txt")]);
+        let d = drive('D', &[(50, r"\Games")]);
+
+        let mut c_first = Vec::new();
+        write_listed_paths(
+            &mut c_first,
+            ListPathsOutputFormat::Text,
+            PathStyle::default(),
+            vec![c.clone(), d.clone()],
+        )
+        .unwrap();
+        let mut d_first = Vec::new();
+        write_listed_paths(
+            &mut d_first,
+            ListPathsOutputFormat::Text,
+            PathStyle::default(),
+            vec![d, c],
+        )
+        .unwrap();
+
+        assert_eq!(c_first, d_first);
+        assert_eq!(
+            String::from_utf8(c_first).unwrap(),
+            "\\Users\n\\Users\\a.txt\n\\Games\n"
+        );
+    }
+
+    #[test]
+    fn count_only_output_has_totals_and_no_path_lines() {
+        let c = drive('C', &[(40, r"\Users"), (41, r"\Users\a.txt")]);
+        let d = drive('D', &[(50, r"\Games")]);
+
+        let mut output = Vec::new();
+        listed_path_counts(&[d, c]).write(&mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "C: 2\nD: 1\ntotal: 3\n");
+        assert!(!output.contains('\\'));
+    }
+
+    #[test]
+    fn named_stream_adds_a_stream_path_after_the_base_path() {
+        let with_stream = MftReference {
+            entry: 40,
+            sequence: 1,
+        };
+        let without_stream = MftReference {
+            entry: 41,
+            sequence: 1,
+        };
+        let mut stream_names = FxHashMap::default();
+        stream_names.insert(with_stream, vec!["Zone.Identifier".to_owned()]);
+
+        let paths = with_stream_paths(
+            vec![
+                (with_stream, r"\Users\file.txt".to_owned()),
+                (without_stream, r"\Users\other.txt".to_owned()),
+            ],
+            &stream_names,
+        );
+
+        assert_eq!(
+            paths,
+            vec![
+                (with_stream, r"\Users\file.txt".to_owned()),
+                (with_stream, r"\Users\file.txt:Zone.Identifier".to_owned()),
+                (without_stream, r"\Users\other.txt".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn primary_mode_lists_one_path_per_hard_linked_entry() {
+        let precedence = |ns: &FileNamespace| usize::from(ns != &FileNamespace::Win32);
+        let dir = MftReference {
+            entry: 41,
+            sequence: 1,
+        };
+        let file = MftReference {
+            entry: 40,
+            sequence: 1,
+        };
+        let mut x30_map = FxHashMap::default();
+        x30_map.insert(dir, vec![file_name_attr(ROOT_ENTRY, "docs", 1)]);
+        x30_map.insert(
+            file,
+            vec![
+                file_name_attr(ROOT_ENTRY, "a.txt", 1),
+                file_name_attr(41, "b.txt", 1),
+            ],
+        );
+        let paths_for_file = |primary_only| {
+            build_listed_paths(&x30_map, 3, primary_only, false, &precedence)
+                .into_iter()
+                .filter(|(entry_ref, _)| *entry_ref == file)
+                .map(|(_, path)| path)
+                .collect::<Vec<_>>()
+        };
+
+        let mut all = paths_for_file(false);
+        all.sort();
+        assert_eq!(all, vec![r"\a.txt", r"\docs\b.txt"]);
+        assert_eq!(paths_for_file(true), vec![r"\a.txt"]);
+    }
+
+    #[test]
+    fn links_with_a_missing_parent_are_listed_only_as_orphans() {
+        let precedence = |ns: &FileNamespace| usize::from(ns != &FileNamespace::Win32);
+        let rooted = MftReference {
+            entry: 40,
+            sequence: 1,
+        };
+        let dangling_dir = MftReference {
+            entry: 41,
+            sequence: 1,
+        };
+        let dangling_file = MftReference {
+            entry: 42,
+            sequence: 1,
+        };
+        let mut x30_map = FxHashMap::default();
+        x30_map.insert(rooted, vec![file_name_attr(ROOT_ENTRY, "kept.txt", 1)]);
+        // Entry 999 has no record, as when a deleted directory's record is gone.
+        x30_map.insert(dangling_dir, vec![file_name_attr(999, "old", 1)]);
+        x30_map.insert(dangling_file, vec![file_name_attr(41, "lost.txt", 1)]);
+        let listed = |include_orphans| {
+            let mut paths = build_listed_paths(&x30_map, 3, false, include_orphans, &precedence)
+                .into_iter()
+                .map(|(_, path)| path)
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(listed(false), vec![r"\kept.txt"]);
+        assert_eq!(
+            listed(true),
+            vec![r"\$Orphan\lost.txt", r"\$Orphan\old", r"\kept.txt"]
+        );
+    }
 }
@@ -1,3 +1,7 @@
 mod list_paths_cli;
 
 pub use list_paths_cli::ListPathsArgs;
+pub use list_paths_cli::ListPathsOutputFormat;
+pub use list_paths_cli::ListedPath;
+pub(crate) use list_paths_cli::ModifiedWindow;
+pub(crate) use list_paths_cli::list_drive_paths;
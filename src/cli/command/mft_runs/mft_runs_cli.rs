@@ -0,0 +1,172 @@
+use crate::mft::mft_physical_read::plan_physical_stream;
+use crate::mft::mft_record_number::MftRecordNumber;
+use crate::ntfs::ntfs_boot_sector::NtfsBootSector;
+use crate::ntfs::ntfs_drive_handle::NtfsDriveHandle;
+use crate::ntfs::ntfs_drive_handle::enumerate_ntfs_volumes;
+use crate::read::logical_read_plan::LogicalFileSegmentKind;
+use crate::read::logical_read_plan::LogicalReadPlan;
+use crate::windows_utils::elevation::ensure_elevated_for_raw_reads;
+use crate::windows_utils::handle::get_read_only_drive_handle;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::Context;
+use facet::Facet;
+use figue::{self as args};
+use std::io::Write;
+use uom::si::information::byte;
+
+/// Print the data runs of one drive's live `$MFT` and how fragmented it is.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct MftRunsArgs {
+    /// Drive letter whose `$MFT` data runs are printed
+    #[facet(args::named, default)]
+    pub drive: String,
+
+    /// Skip relaunching as administrator and attempt raw volume reads with the current privileges
+    #[facet(args::named, default)]
+    pub no_elevate: bool,
+}
+
+/// One data run of the `$MFT`, in bytes and clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MftRun {
+    /// Byte offset of the run from the start of the disk (or of the volume when its disk
+    /// offset is unknown); `None` for a sparse run.
+    offset: Option<u64>,
+    clusters: u64,
+    bytes: u64,
+}
+
+impl MftRunsArgs {
+    /// Decode record 0's unnamed `$DATA` runs for `--drive` and print them with a
+    /// fragmentation summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drive letter is invalid, elevation fails, the drive cannot be
+    /// opened, or the `$MFT` record has no non-resident data runs.
+    pub fn invoke(self) -> eyre::Result<()> {
+        let drive_letter = DriveLetterPattern(self.drive.clone()).into_single_drive_letter()?;
+        ensure_elevated_for_raw_reads(self.no_elevate)?;
+        let drive_handle: NtfsDriveHandle = get_read_only_drive_handle(drive_letter)?
+            .try_into()
+            .wrap_err("Failed to convert drive handle to NtfsDriveHandle")?;
+        let bytes_per_cluster = NtfsBootSector::try_from_handle(&drive_handle)?.bytes_per_cluster();
+        drop(drive_handle);
+        let volume = enumerate_ntfs_volumes()?
+            .into_iter()
+            .find(|volume| volume.drive_letters.contains(&drive_letter));
+        let logical_read_plan =
+            plan_physical_stream(drive_letter, MftRecordNumber::DOLLAR_MFT, None, None)?;
+
+        let disk_offset = volume.as_ref().and_then(|volume| volume.starting_offset);
+        let runs = mft_runs(
+            &logical_read_plan,
+            bytes_per_cluster as u64,
+            disk_offset.unwrap_or(0),
+        );
+
+        let mut stdout = std::io::stdout().lock();
+        match volume.and_then(|volume| volume.disk_number) {
+            Some(disk_number) if disk_offset.is_some() => {
+                writeln!(stdout, "offsets from:   PhysicalDrive{disk_number}")?;
+            }
+            _ => writeln!(stdout, "offsets from:   start of volume {drive_letter}:")?,
+        }
+        write_runs(&mut stdout, &runs)?;
+        Ok(())
+    }
+}
+
+/// Turn each segment of the `$MFT` plan back into a run, adding `volume_offset` to every
+/// allocated one.
+fn mft_runs(
+    logical_read_plan: &LogicalReadPlan,
+    bytes_per_cluster: u64,
+    volume_offset: u64,
+) -> Vec<MftRun> {
+    logical_read_plan
+        .segments
+        .iter()
+        .map(|segment| {
+            let bytes = segment.length.get::<byte>() as u64;
+            MftRun {
+                offset: match segment.kind {
+                    LogicalFileSegmentKind::Physical { physical_offset } => {
+                        Some(volume_offset + physical_offset.get::<byte>() as u64)
+                    }
+                    LogicalFileSegmentKind::Sparse => None,
+                },
+                clusters: bytes / bytes_per_cluster.max(1),
+                bytes,
+            }
+        })
+        .collect()
+}
+
+fn write_runs(writer: &mut impl Write, runs: &[MftRun]) -> std::io::Result<()> {
+    for run in runs {
+        match run.offset {
+            Some(offset) => write!(writer, "offset {offset:#014x}")?,
+            None => write!(writer, "sparse               ")?,
+        }
+        writeln!(
+            writer,
+            "  clusters {:>10}  bytes {:>14}",
+            run.clusters, run.bytes
+        )?;
+    }
+    let allocated = || runs.iter().filter(|run| run.offset.is_some());
+    writeln!(writer, "runs:            {}", allocated().count())?;
+    if let Some(largest) = allocated().max_by_key(|run| run.bytes) {
+        writeln!(
+            writer,
+            "largest extent:  {} clusters ({} bytes)",
+            largest.clusters, largest.bytes
+        )?;
+    }
+    if let Some(smallest) = allocated().min_by_key(|run| run.bytes) {
+        writeln!(
+            writer,
+            "smallest extent: {} clusters ({} bytes)",
+            smallest.clusters, smallest.bytes
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mft_runs;
+    use super::write_runs;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::mft_record_attribute_run_list::MftRecordAttributeRunListOwned;
+    use crate::mft::testing::SYNTHETIC_CLUSTER_SIZE;
+    use crate::mft::testing::SyntheticMftBuilder;
+    use uom::si::information::byte;
+    use uom::si::usize::Information;
+
+    #[test]
+    fn dollar_mft_run_list_prints_disk_extents_and_summary() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        builder.set_non_resident_data(0, &[(64, Some(0xC_0000)), (16, Some(0x200))], 80 * 4096);
+        let mft = MftFile::from_vec(builder.build())?;
+        let logical_read_plan = MftRecordAttributeRunListOwned::from_mft_record(&mft.record_at(0)?)
+            .into_logical_read_plan(Information::new::<byte>(SYNTHETIC_CLUSTER_SIZE as usize));
+
+        let runs = mft_runs(&logical_read_plan, SYNTHETIC_CLUSTER_SIZE, 1_048_576);
+        let mut output = Vec::new();
+        write_runs(&mut output, &runs)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "offset 0x0000c0100000  clusters         64  bytes         262144\n\
+             offset 0x000000300000  clusters         16  bytes          65536\n\
+             runs:            2\n\
+             largest extent:  64 clusters (262144 bytes)\n\
+             smallest extent: 16 clusters (65536 bytes)\n"
+        );
+        Ok(())
+    }
+}
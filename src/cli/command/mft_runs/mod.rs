@@ -0,0 +1,3 @@
+mod mft_runs_cli;
+
+pub use mft_runs_cli::MftRunsArgs;
@@ -1,7 +1,15 @@
+pub mod check;
+pub mod export_delta;
+pub mod export_sqlite;
+pub mod file_extents;
 pub mod fsutil;
+pub mod get_record;
 pub mod install;
+pub mod list_cached;
 pub mod list_paths;
+pub mod mft_runs;
 pub mod r#move;
+pub mod plan;
 pub mod profile;
 pub mod protection;
 pub mod query;
@@ -11,6 +19,8 @@ pub mod status;
 pub mod sync;
 pub mod tray;
 pub mod uninstall;
+#[cfg(any(test, feature = "diagnostics"))]
+pub mod verify_parsers;
 
 mod command_cli;
 
@@ -0,0 +1,3 @@
+mod plan_cli;
+
+pub use plan_cli::PlanArgs;
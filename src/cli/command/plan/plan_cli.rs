@@ -0,0 +1,160 @@
+use crate::mft::mft_physical_read::build_physical_read_plan;
+use crate::mft::mft_physical_read::plan_physical_stream;
+use crate::mft::mft_record_number::MftRecordNumber;
+use crate::mft::mft_resumable_read::PartialMftSegment;
+use crate::mft::mft_resumable_read::PartialMftSidecar;
+use crate::read::logical_read_plan::LogicalReadPlan;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
+use crate::windows_utils::elevation::ensure_elevated_for_raw_reads;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use facet::Facet;
+use figue::{self as args};
+use std::io::Write;
+use uom::si::information::byte;
+use uom::si::usize::Information;
+
+/// Print the `$MFT` read plan for one drive without reading the MFT itself.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct PlanArgs {
+    /// Drive letter whose `$MFT` layout is printed
+    #[facet(args::named, default)]
+    pub drive: String,
+
+    /// Print the plan as JSON instead of a table
+    #[facet(args::named, default)]
+    pub json: bool,
+
+    /// Skip relaunching as administrator and attempt raw volume reads with the current privileges
+    #[facet(args::named, default)]
+    pub no_elevate: bool,
+}
+
+/// JSON shape printed by `plan --json`.
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+struct ReadPlanDump {
+    drive: char,
+    logical_size: u64,
+    segments: Vec<PartialMftSegment>,
+    physical_requests: u64,
+    physical_bytes: u64,
+    chunk_size: u64,
+}
+
+impl ReadPlanDump {
+    fn new(drive: char, logical_read_plan: &LogicalReadPlan, chunk_size: Information) -> Self {
+        let physical_read_plan = build_physical_read_plan(logical_read_plan, chunk_size);
+        Self {
+            drive,
+            logical_size: logical_read_plan.total_logical_size().get::<byte>() as u64,
+            segments: PartialMftSidecar::for_plan(logical_read_plan).segments,
+            physical_requests: physical_read_plan.len() as u64,
+            physical_bytes: physical_read_plan.total_size().get::<byte>() as u64,
+            chunk_size: chunk_size.get::<byte>() as u64,
+        }
+    }
+}
+
+impl PlanArgs {
+    /// Build the logical and physical read plans for `--drive` and print them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drive letter is invalid, elevation fails, the drive cannot be
+    /// opened, or the `$MFT` record has no non-resident data runs.
+    pub fn invoke(self) -> eyre::Result<()> {
+        let drive_letter = DriveLetterPattern(self.drive.clone()).into_single_drive_letter()?;
+        ensure_elevated_for_raw_reads(self.no_elevate)?;
+        let logical_read_plan =
+            plan_physical_stream(drive_letter, MftRecordNumber::DOLLAR_MFT, None, None)?;
+        let dump = ReadPlanDump::new(
+            drive_letter,
+            &logical_read_plan,
+            PhysicalReadTuning::default().chunk_size,
+        );
+        let mut stdout = std::io::stdout().lock();
+        if self.json {
+            stdout.write_all(&facet_json::to_vec_pretty(&dump)?)?;
+            writeln!(stdout)?;
+        } else {
+            write_plan(&mut stdout, &dump)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_plan(writer: &mut impl Write, dump: &ReadPlanDump) -> eyre::Result<()> {
+    writeln!(
+        writer,
+        "drive {}: {} logical segments, {} bytes",
+        dump.drive,
+        dump.segments.len(),
+        dump.logical_size
+    )?;
+    writeln!(
+        writer,
+        "{:>20} {:>16} {:>20}",
+        "logical offset", "length", "physical offset"
+    )?;
+    for segment in &dump.segments {
+        let physical = segment
+            .physical_offset
+            .map_or_else(|| String::from("sparse"), |offset| offset.to_string());
+        writeln!(
+            writer,
+            "{:>20} {:>16} {:>20}",
+            segment.logical_offset, segment.length, physical
+        )?;
+    }
+    writeln!(
+        writer,
+        "physical plan: {} requests, {} bytes, chunk size {} bytes",
+        dump.physical_requests, dump.physical_bytes, dump.chunk_size
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReadPlanDump;
+    use super::write_plan;
+    use crate::read::logical_read_plan::LogicalFileSegment;
+    use crate::read::logical_read_plan::LogicalFileSegmentKind;
+    use crate::read::logical_read_plan::LogicalReadPlan;
+    use uom::si::information::byte;
+    use uom::si::usize::Information;
+
+    #[test]
+    fn plan_output_lists_every_segment() -> eyre::Result<()> {
+        let logical_read_plan = LogicalReadPlan {
+            segments: [
+                (0, 4096, Some(8192)),
+                (4096, 4096, None),
+                (8192, 2048, Some(65536)),
+            ]
+            .into_iter()
+            .map(|(logical, length, physical)| LogicalFileSegment {
+                logical_offset: Information::new::<byte>(logical),
+                length: Information::new::<byte>(length),
+                kind: physical.map_or(LogicalFileSegmentKind::Sparse, |physical| {
+                    LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(physical),
+                    }
+                }),
+            })
+            .collect(),
+        };
+        let dump = ReadPlanDump::new('C', &logical_read_plan, Information::new::<byte>(4096));
+
+        let mut output = Vec::new();
+        write_plan(&mut output, &dump)?;
+        let output = String::from_utf8(output)?;
+
+        assert!(output.starts_with("drive C: 3 logical segments, 10240 bytes"));
+        assert_eq!(output.lines().count(), 6);
+        assert_eq!(output.matches("sparse").count(), 1);
+        assert!(output.contains("physical plan: 2 requests, 6144 bytes, chunk size 4096 bytes"));
+        Ok(())
+    }
+}
@@ -1,8 +1,16 @@
 use crate::cancellation::CancellationToken;
+use crate::domain::Pathlike;
+use crate::presentation::DriveGrouping;
+use crate::presentation::DriveMatchCounts;
+use crate::presentation::OutputFile;
+use crate::presentation::PathStyle;
 use crate::presentation::ResultListPresentation;
+use crate::query::QueryLimit;
 use crate::query::QueryPlan;
 use crate::query::QueryResultRow;
 use crate::query::QueryRuntime;
+use crate::query::QuerySample;
+use crate::query::QuerySortOrder;
 use arbitrary::Arbitrary;
 use eyre::ensure;
 use facet::Facet;
@@ -10,8 +18,12 @@ use figue::{self as args};
 use std::io::IsTerminal;
 use std::io::Write;
 use std::ops::ControlFlow;
+use std::sync::mpsc;
+use std::time::Duration;
 use tracing::debug;
+use tracing::info;
 use tracing::instrument;
+use tracing::warn;
 
 #[derive(Facet, PartialEq, Debug, Arbitrary, Default, Clone)]
 #[facet(rename_all = "kebab-case")]
@@ -21,12 +33,30 @@ pub struct QueryArgs {
     /// Output density mode
     #[facet(args::named, default)]
     pub density: QueryResultsOutputDensity,
+    #[facet(flatten)]
+    pub path_style: PathStyle,
+    #[facet(flatten)]
+    pub output: OutputFile,
+    #[facet(flatten)]
+    pub grouping: DriveGrouping,
+    /// Keep only this fraction (0.0-1.0) of matching paths, chosen deterministically by path hash
+    #[facet(args::named)]
+    pub sample: Option<f64>,
+    /// Result ordering; `path` buffers every match and sorts before applying `--limit`
+    #[facet(args::named, default)]
+    pub sort: QuerySortOrder,
+    /// Print only the number of matches per drive and in total instead of the paths
+    #[facet(args::named, default)]
+    pub count_only: bool,
     /// Bypass the machine daemon and read published indexes directly
     #[facet(args::named, default)]
     pub no_daemon: bool,
     /// Ask the machine daemon to run the query
     #[facet(args::named, default)]
     pub daemon: bool,
+    /// Stop scanning after this many seconds and return the matches found so far
+    #[facet(args::named)]
+    pub timeout: Option<f64>,
 }
 
 #[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
@@ -49,41 +79,60 @@ impl QueryArgs {
         }
     }
 
-    /// Run the query and print results to stdout.
+    /// Run the query and print results to stdout or `--output-file`.
     ///
     /// # Errors
     ///
-    /// Returns an error if the query is empty, machine cache cannot be retrieved,
+    /// Returns an error if the query is empty, the output file cannot be created, machine cache cannot be retrieved,
     /// drive letters cannot be resolved, the query scope cannot be canonicalized,
     /// or if reading/parsing index files fails.
-    #[instrument(level = "info", skip_all, fields(query = ?self.plan.query, query_scope = ?self.plan.r#in, profile = ?self.plan.profile, limit = ?self.plan.limit, include_deleted = self.plan.include_deleted, only_deleted = self.plan.only_deleted, show_filtered = self.plan.show_filtered, only_filtered = self.plan.only_filtered, density = ?self.density))]
+    #[instrument(level = "info", skip_all, fields(query = ?self.plan.query, query_scope = ?self.plan.r#in, profile = ?self.plan.profile, limit = ?self.plan.limit, include_deleted = self.plan.include_deleted, only_deleted = self.plan.only_deleted, show_filtered = self.plan.show_filtered, only_filtered = self.plan.only_filtered, density = ?self.density, sort = %self.sort))]
     pub fn invoke_and_print(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
-        let stdout_is_terminal = std::io::stdout().is_terminal();
+        if self.count_only {
+            let counts = self.count_rows(cancellation_token)?;
+            let mut output = self.output.open()?;
+            counts.write(&mut output)?;
+            output.flush()?;
+            return Ok(());
+        }
+
+        let stdout_is_terminal = self.output.is_stdout() && std::io::stdout().is_terminal();
         let colorize = stdout_is_terminal
             && (self.plan.include_deleted
                 || self.plan.only_deleted
                 || self.plan.show_filtered
                 || self.plan.only_filtered);
         let presentation = ResultListPresentation::for_terminal();
-        let use_columns = match self.density {
-            QueryResultsOutputDensity::Auto => stdout_is_terminal,
-            QueryResultsOutputDensity::Lines => false,
-            QueryResultsOutputDensity::Columns => true,
+        // Grouped output is always one path per line under its drive header.
+        let use_columns = !self.grouping.group_by_drive
+            && match self.density {
+                QueryResultsOutputDensity::Auto => stdout_is_terminal,
+                QueryResultsOutputDensity::Lines => false,
+                QueryResultsOutputDensity::Columns => true,
+            };
+
+        let path_style = self.path_style;
+        let restyle = |mut row: QueryResultRow| {
+            if path_style != PathStyle::default() {
+                row.path = Pathlike::from(path_style.apply(row.path.as_str()).into_owned());
+            }
+            row
         };
 
-        if !use_columns {
-            let mut stdout = std::io::stdout().lock();
+        let mut output = self.output.open()?;
+        if !use_columns && !self.grouping.group_by_drive {
             self.visit_rows(cancellation_token, |row| {
-                row.render_path(&mut stdout, colorize)?;
-                writeln!(&mut stdout)?;
+                restyle(row).render_path(&mut output, colorize)?;
+                writeln!(&mut output)?;
                 Ok(ControlFlow::Continue(()))
             })?;
+            output.flush()?;
             return Ok(());
         }
 
         let mut results = Vec::new();
         self.visit_rows(cancellation_token, |row| {
-            results.push(row);
+            results.push(restyle(row));
             Ok(ControlFlow::Continue(()))
         })?;
         let result_limit = self
@@ -93,18 +142,46 @@ impl QueryArgs {
             .unwrap_or(results.len())
             .min(results.len());
         let display_results = &results[..result_limit];
-        let mut stdout = std::io::stdout().lock();
+        if self.grouping.group_by_drive {
+            DriveGrouping::write_groups(
+                display_results,
+                &mut output,
+                stdout_is_terminal,
+                |row| row.path.as_str(),
+                |row, writer| row.render_path(writer, colorize),
+            )?;
+            output.flush()?;
+            return Ok(());
+        }
         presentation.write_result_list(
             display_results,
-            &mut stdout,
+            &mut output,
             use_columns,
             |row| row.path.as_str().chars().count(),
             |row, writer| row.render_path(writer, colorize),
         )?;
+        output.flush()?;
 
         Ok(())
     }
 
+    /// Count matching rows per drive without rendering any paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Self::visit_rows`].
+    pub fn count_rows(
+        &self,
+        cancellation_token: &CancellationToken,
+    ) -> eyre::Result<DriveMatchCounts> {
+        let mut counts = DriveMatchCounts::default();
+        self.visit_rows(cancellation_token, |row| {
+            counts.add_path(row.path.as_str());
+            Ok(ControlFlow::Continue(()))
+        })?;
+        Ok(counts)
+    }
+
     /// Emit warnings for any potentially unintentional query patterns and return an error if the query is empty.
     ///
     /// # Errors
@@ -134,18 +211,129 @@ impl QueryArgs {
         Ok(())
     }
 
+    /// Visit matching rows, stopping early with a warning once `--timeout` elapses.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the query is empty or invalid, drive letters cannot be resolved,
-    /// the daemon transport fails, the machine cache is unavailable, the query scope cannot
-    /// be canonicalized, or if daemon/disk-backed index reads fail.
+    /// Returns an error if the query is empty or invalid, `--timeout` is not positive, drive
+    /// letters cannot be resolved, the daemon transport fails, the machine cache is unavailable,
+    /// the query scope cannot be canonicalized, or if daemon/disk-backed index reads fail.
     pub fn visit_rows(
         &self,
         cancellation_token: &CancellationToken,
         visit: impl FnMut(QueryResultRow) -> eyre::Result<ControlFlow<(), ()>>,
     ) -> eyre::Result<()> {
-        self.prepare_runtime()?
-            .visit_rows(self.plan.clone(), cancellation_token, visit)
+        let Some(timeout) = self.timeout()? else {
+            return self.visit_sorted_rows(cancellation_token, visit);
+        };
+        let (result, timed_out) = run_with_timeout(cancellation_token, timeout, |token| {
+            self.visit_sorted_rows(token, visit)
+        });
+        if timed_out {
+            warn!(
+                timeout_secs = timeout.as_secs_f64(),
+                "Query timed out; results may be incomplete"
+            );
+        }
+        result
+    }
+
+    fn visit_sorted_rows(
+        &self,
+        cancellation_token: &CancellationToken,
+        mut visit: impl FnMut(QueryResultRow) -> eyre::Result<ControlFlow<(), ()>>,
+    ) -> eyre::Result<()> {
+        if self.sort.is_streaming() {
+            return self.visit_sampled_rows(cancellation_token, visit);
+        }
+
+        // Sorting needs every match, so the limit is applied after the sort.
+        let limit = self.plan.limit.get();
+        let unlimited = Self {
+            plan: QueryPlan {
+                limit: QueryLimit::default(),
+                ..self.plan.clone()
+            },
+            ..self.clone()
+        };
+        let mut rows = Vec::new();
+        unlimited.visit_sampled_rows(cancellation_token, |row| {
+            rows.push(row);
+            Ok(ControlFlow::Continue(()))
+        })?;
+        self.sort.sort(&mut rows);
+        if let Some(limit) = limit {
+            rows.truncate(limit);
+        }
+        for row in rows {
+            if visit(row)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_sampled_rows(
+        &self,
+        cancellation_token: &CancellationToken,
+        mut visit: impl FnMut(QueryResultRow) -> eyre::Result<ControlFlow<(), ()>>,
+    ) -> eyre::Result<()> {
+        let runtime = self.prepare_runtime()?;
+        let sample = self.sample()?;
+        if sample.is_full() {
+            return runtime.visit_rows(self.plan.clone(), cancellation_token, visit);
+        }
+
+        // The limit applies to sampled rows, so enforce it here rather than in the runtime.
+        let limit = self.plan.limit.get();
+        let plan = QueryPlan {
+            limit: QueryLimit::default(),
+            ..self.plan.clone()
+        };
+        info!(
+            fraction = sample.fraction(),
+            "Sampling is active; results are approximate"
+        );
+        let mut seen = 0_usize;
+        let mut kept = 0_usize;
+        runtime.visit_rows(plan, cancellation_token, |row| {
+            seen += 1;
+            if !sample.keeps(row.path.as_str()) {
+                return Ok(ControlFlow::Continue(()));
+            }
+            kept += 1;
+            let control_flow = visit(row)?;
+            if limit.is_some_and(|limit| kept >= limit) {
+                return Ok(ControlFlow::Break(()));
+            }
+            Ok(control_flow)
+        })?;
+        info!(seen, kept, "Sampled query results");
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `--sample` is outside `0.0..=1.0`.
+    pub fn sample(&self) -> eyre::Result<QuerySample> {
+        self.sample
+            .map_or_else(|| Ok(QuerySample::default()), QuerySample::new)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `--timeout` is not a positive number of seconds.
+    pub fn timeout(&self) -> eyre::Result<Option<Duration>> {
+        self.timeout
+            .map(|secs| {
+                Duration::try_from_secs_f64(secs)
+                    .ok()
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| {
+                        eyre::eyre!("--timeout must be a positive number of seconds, got {secs}")
+                    })
+            })
+            .transpose()
     }
 
     fn runtime(&self) -> QueryRuntime {
@@ -172,12 +360,92 @@ impl QueryArgs {
     }
 }
 
+/// Run `work` with a child of `cancellation_token` that is cancelled once `timeout` elapses.
+///
+/// The index scans stop at their next cancellation check and return the rows visited so far.
+/// Returns the result of `work` and whether the timeout fired.
+fn run_with_timeout<T>(
+    cancellation_token: &CancellationToken,
+    timeout: Duration,
+    work: impl FnOnce(&CancellationToken) -> T,
+) -> (T, bool) {
+    let deadline = cancellation_token.child_token();
+    let (finished_tx, finished_rx) = mpsc::channel::<()>();
+    let watcher = std::thread::spawn({
+        let deadline = deadline.clone();
+        move || match finished_rx.recv_timeout(timeout) {
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                deadline.request_cancel("query --timeout elapsed");
+                true
+            }
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => false,
+        }
+    });
+    let output = work(&deadline);
+    let _ = finished_tx.send(());
+    let timed_out = watcher.join().unwrap_or(false);
+    (output, timed_out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::QueryArgs;
+    use super::run_with_timeout;
     use crate::cancellation::CancellationToken;
     use crate::query::QueryRuntime;
     use std::ops::ControlFlow;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[test]
+    fn short_timeout_returns_partial_results_without_hanging() {
+        let started = Instant::now();
+        let (rows, timed_out) = run_with_timeout(
+            &CancellationToken::new(),
+            Duration::from_millis(20),
+            |token| {
+                // Stands in for an index scan that would never finish on its own.
+                let mut rows = Vec::new();
+                while !token.is_cancelled() {
+                    rows.push(rows.len());
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                rows
+            },
+        );
+
+        assert!(timed_out);
+        assert!(!rows.is_empty());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn work_finishing_before_the_timeout_is_not_reported_as_timed_out() {
+        let started = Instant::now();
+        let (rows, timed_out) = run_with_timeout(
+            &CancellationToken::new(),
+            Duration::from_secs(60),
+            |token| {
+                assert!(!token.is_cancelled());
+                vec![1, 2, 3]
+            },
+        );
+
+        assert!(!timed_out);
+        assert_eq!(rows, vec![1, 2, 3]);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn non_positive_timeouts_are_rejected() {
+        for timeout in [0.0, -1.0, f64::NAN] {
+            let args = QueryArgs {
+                timeout: Some(timeout),
+                ..QueryArgs::new("Cargo.toml")
+            };
+            assert!(args.timeout().is_err(), "{timeout} should be rejected");
+        }
+    }
 
     #[test]
     fn default_and_no_daemon_query_args_use_published_index_runtime() {
@@ -1,5 +1,7 @@
 use crate::cancellation::CancellationToken;
+use crate::sync::DryRunDriveSummary;
 use crate::sync::SyncPlan;
+use crate::windows_utils::elevation::ensure_elevated_for_raw_reads;
 use arbitrary::Arbitrary;
 use facet::Facet;
 use figue::{self as args};
@@ -16,6 +18,10 @@ pub struct SyncArgs {
     /// Ask the machine daemon to run sync work
     #[facet(args::named, default)]
     pub daemon: bool,
+
+    /// Print each drive's planned MFT read size and output path without reading or writing anything
+    #[facet(args::named, default)]
+    pub dry_run: bool,
 }
 
 impl SyncArgs {
@@ -35,6 +41,18 @@ impl SyncArgs {
             !plan.recursive || plan.path.is_some(),
             "`--recursive` requires a target path"
         );
+        eyre::ensure!(
+            !self.daemon || plan.map.is_empty(),
+            "`--map` cannot be sent to the machine daemon; use `--no-daemon` to write snapshots outside the sync dir"
+        );
+
+        if self.dry_run {
+            eyre::ensure!(
+                !self.daemon && plan.path.is_none(),
+                "`--dry-run` plans full drive syncs in this process; it cannot be combined with `--daemon` or a target path"
+            );
+            return Self::dry_run(&plan);
+        }
 
         if self.daemon {
             let config = crate::machine::ipc::load_machine_daemon_client_config()?;
@@ -64,7 +82,7 @@ impl SyncArgs {
                 crate::machine::daemon::sync_machine_cache(
                     &sync_dir,
                     &drive_letters,
-                    plan.if_exists,
+                    &plan,
                     cancellation_token,
                 )?;
             }
@@ -72,6 +90,24 @@ impl SyncArgs {
 
         Ok(())
     }
+
+    fn dry_run(plan: &SyncPlan) -> eyre::Result<()> {
+        ensure_elevated_for_raw_reads(plan.no_elevate)?;
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let drive_letters = plan.drive_letter_pattern.clone().into_drive_letters()?;
+        let mut mft_output_overrides = plan.mft_output_overrides(&drive_letters)?;
+        let tuning = plan.read_tuning()?;
+        for drive_letter in drive_letters {
+            let output_path = mft_output_overrides
+                .remove(&drive_letter)
+                .unwrap_or_else(|| plan.default_mft_output_path(&sync_dir, drive_letter));
+            println!(
+                "{}",
+                DryRunDriveSummary::plan(drive_letter, output_path, &tuning)?
+            );
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -98,4 +134,20 @@ mod tests {
                 .contains("`--recursive` requires a target path")
         );
     }
+
+    #[test]
+    fn daemon_sync_rejects_map() {
+        let error = SyncArgs {
+            plan: SyncPlan {
+                map: vec![r"C=D:\captures\c.mft".to_owned()],
+                ..SyncPlan::default()
+            },
+            daemon: true,
+            ..SyncArgs::default()
+        }
+        .invoke(&CancellationToken::new())
+        .expect_err("the daemon must not be asked to write outside the sync dir");
+
+        assert!(error.to_string().contains("`--map` cannot be sent"));
+    }
 }
@@ -0,0 +1,3 @@
+mod verify_parsers_cli;
+
+pub use verify_parsers_cli::VerifyParsersArgs;
@@ -0,0 +1,261 @@
+use crate::cancellation::CancellationToken;
+use crate::cli::command::list_paths::ModifiedWindow;
+use crate::cli::command::list_paths::list_drive_paths;
+use crate::machine::config::published_drive_paths;
+use crate::mft::mft_convert_to_path_collection::convert_mft_file_to_path_collection;
+use crate::mft::mft_file::MftFile;
+use crate::windows_utils::storage::DriveLetterPattern;
+use arbitrary::Arbitrary;
+use eyre::bail;
+use facet::Facet;
+use figue::{self as args};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+
+/// Number of mismatching entries printed when `--sample` is not given.
+const DEFAULT_MISMATCH_SAMPLE: usize = 10;
+
+/// Root directory record; `list-paths` never emits it, so it is excluded from the comparison.
+const ROOT_ENTRY: u64 = 5;
+
+/// Resolve paths from one cached MFT with both the `mft` crate (used by `list-paths`) and the
+/// in-house `fast_entry` scanner (used to build search indexes), and report where they disagree.
+#[derive(Facet, PartialEq, Debug, Arbitrary, Default)]
+#[facet(rename_all = "kebab-case")]
+pub struct VerifyParsersArgs {
+    /// Drive letter whose cached MFT is parsed both ways
+    #[facet(args::named, default)]
+    pub drive: String,
+
+    /// Number of mismatching entries to print; defaults to 10
+    #[facet(args::named)]
+    pub sample: Option<usize>,
+}
+
+/// Entry whose resolved path sets differ between the two parsers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PathSetMismatch {
+    entry: u64,
+    only_mft_crate: Vec<String>,
+    only_fast_entry: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ParserComparison {
+    agreeing_entries: usize,
+    mismatches: Vec<PathSetMismatch>,
+}
+
+impl VerifyParsersArgs {
+    /// # Errors
+    ///
+    /// Returns an error if the drive has no cached MFT, either parser fails, or any
+    /// entry's resolved paths differ between the parsers.
+    pub fn invoke(self, cancellation_token: &CancellationToken) -> eyre::Result<()> {
+        let drive_letter = DriveLetterPattern(self.drive.clone()).into_single_drive_letter()?;
+        let sync_dir = crate::machine::config::load_sync_dir_from_config()?;
+        let mft_path = published_drive_paths(&sync_dir, drive_letter).mft_path;
+        if !mft_path.is_file() {
+            bail!(
+                "No cached MFT for drive {drive_letter} at {}; run `sync` first",
+                mft_path.display()
+            );
+        }
+
+        let comparison = compare_parsers(drive_letter, &mft_path, cancellation_token)?;
+        let mut stdout = std::io::stdout().lock();
+        write_comparison(
+            &mut stdout,
+            &comparison,
+            self.sample.unwrap_or(DEFAULT_MISMATCH_SAMPLE),
+        )?;
+        if !comparison.mismatches.is_empty() {
+            bail!(
+                "Parsers disagree on {} entr(y/ies) for drive {drive_letter}",
+                comparison.mismatches.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the paths of `mft_path` with both parsers and compare them per entry.
+fn compare_parsers(
+    drive_letter: char,
+    mft_path: &Path,
+    cancellation_token: &CancellationToken,
+) -> eyre::Result<ParserComparison> {
+    let mut mft_crate_paths = BTreeMap::<u64, BTreeSet<String>>::new();
+    let listed = list_drive_paths(
+        drive_letter,
+        mft_path,
+        false,
+        false,
+        false,
+        false,
+        ModifiedWindow::default(),
+        cancellation_token,
+    )?;
+    for (entry_ref, path) in listed.paths {
+        mft_crate_paths
+            .entry(entry_ref.entry)
+            .or_default()
+            .insert(format!("{drive_letter}:{path}"));
+    }
+
+    let mut fast_entry_paths = BTreeMap::<u64, BTreeSet<String>>::new();
+    let mft_file = MftFile::from_path(mft_path, cancellation_token)?;
+    let collection = convert_mft_file_to_path_collection(&drive_letter.to_string(), &mft_file)?;
+    for (entry, paths) in collection.0.iter().enumerate() {
+        let entry = entry as u64;
+        if paths.is_empty() || entry == ROOT_ENTRY {
+            continue;
+        }
+        fast_entry_paths.insert(
+            entry,
+            paths
+                .iter()
+                .map(|path| path.path.display().to_string())
+                .collect(),
+        );
+    }
+
+    Ok(compare_path_sets(&mft_crate_paths, &fast_entry_paths))
+}
+
+fn compare_path_sets(
+    mft_crate_paths: &BTreeMap<u64, BTreeSet<String>>,
+    fast_entry_paths: &BTreeMap<u64, BTreeSet<String>>,
+) -> ParserComparison {
+    let empty = BTreeSet::new();
+    let entries = mft_crate_paths
+        .keys()
+        .chain(fast_entry_paths.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+    let mut comparison = ParserComparison::default();
+    for entry in entries {
+        let left = mft_crate_paths.get(&entry).unwrap_or(&empty);
+        let right = fast_entry_paths.get(&entry).unwrap_or(&empty);
+        if left == right {
+            comparison.agreeing_entries += 1;
+            continue;
+        }
+        comparison.mismatches.push(PathSetMismatch {
+            entry,
+            only_mft_crate: left.difference(right).cloned().collect(),
+            only_fast_entry: right.difference(left).cloned().collect(),
+        });
+    }
+    comparison
+}
+
+fn write_comparison(
+    writer: &mut impl Write,
+    comparison: &ParserComparison,
+    sample: usize,
+) -> eyre::Result<()> {
+    writeln!(
+        writer,
+        "agreeing entries:    {}",
+        comparison.agreeing_entries
+    )?;
+    writeln!(
+        writer,
+        "mismatching entries: {}",
+        comparison.mismatches.len()
+    )?;
+    for mismatch in comparison.mismatches.iter().take(sample) {
+        writeln!(writer, "entry {}:", mismatch.entry)?;
+        for path in &mismatch.only_mft_crate {
+            writeln!(writer, "  mft crate only:  {path}")?;
+        }
+        for path in &mismatch.only_fast_entry {
+            writeln!(writer, "  fast_entry only: {path}")?;
+        }
+    }
+    if comparison.mismatches.len() > sample {
+        writeln!(
+            writer,
+            "... {} more mismatching entries not shown",
+            comparison.mismatches.len() - sample
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+
+    fn paths(entries: &[(u64, &[&str])]) -> BTreeMap<u64, BTreeSet<String>> {
+        entries
+            .iter()
+            .map(|(entry, paths)| {
+                (
+                    *entry,
+                    paths.iter().map(|path| (*path).to_owned()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn agreeing_parsers_report_no_mismatches() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        let users = builder.directory(SYNTHETIC_ROOT_RECORD, "Users");
+        builder.file(users, "a.txt");
+        builder.file(users, "b.txt");
+        let removed = builder.file(SYNTHETIC_ROOT_RECORD, "removed.txt");
+        builder.mark_deleted(removed);
+        let dir = tempfile::tempdir()?;
+        let mft_path = dir.path().join("C.mft");
+        std::fs::write(&mft_path, builder.build())?;
+
+        let comparison = compare_parsers('C', &mft_path, &CancellationToken::new())?;
+        let mut output = Vec::new();
+        write_comparison(&mut output, &comparison, DEFAULT_MISMATCH_SAMPLE)?;
+
+        assert_eq!(comparison.mismatches, vec![]);
+        // `Users`, `a.txt` and `b.txt` at least, alongside the system files.
+        assert!(comparison.agreeing_entries >= 3);
+        assert!(String::from_utf8(output)?.ends_with("mismatching entries: 0\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn differing_path_sets_are_listed_per_side() {
+        let mft_crate = paths(&[(40, &[r"C:\Users"]), (41, &[r"C:\Users\a.txt"])]);
+        let fast_entry = paths(&[(40, &[r"C:\Users"]), (42, &[r"C:\Users\b.txt"])]);
+
+        let comparison = compare_path_sets(&mft_crate, &fast_entry);
+        let mut output = Vec::new();
+        write_comparison(&mut output, &comparison, 1).unwrap();
+
+        assert_eq!(comparison.agreeing_entries, 1);
+        assert_eq!(
+            comparison.mismatches,
+            vec![
+                PathSetMismatch {
+                    entry: 41,
+                    only_mft_crate: vec![r"C:\Users\a.txt".to_owned()],
+                    only_fast_entry: vec![],
+                },
+                PathSetMismatch {
+                    entry: 42,
+                    only_mft_crate: vec![],
+                    only_fast_entry: vec![r"C:\Users\b.txt".to_owned()],
+                },
+            ]
+        );
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .ends_with("... 1 more mismatching entries not shown\n")
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use arbitrary::Arbitrary;
 use facet::Facet;
 use figue::{self as args};
+use rayon::ThreadPoolBuilder;
 
 // tool[impl cli.help.describes-argv]
 #[derive(Facet, Default, Arbitrary, PartialEq, Debug)]
@@ -13,10 +14,26 @@ pub struct GlobalArgs {
     #[facet(args::named)]
     pub log_filter: Option<String>,
 
+    /// Extra tracing directive layered over the log filter (e.g. `teamy_mft::read=debug`). Repeat `--log` for several.
+    #[facet(args::named, default)]
+    pub log: Vec<String>,
+
     // tool[impl logging.file-path-option]
     #[facet(args::named)]
     pub log_file: Option<String>,
 
+    /// Log file name used when `--log-file` is a directory or omitted; `{timestamp}` and `{pid}` are expanded
+    #[facet(args::named)]
+    pub log_file_name: Option<String>,
+
+    /// Rotate the log file once it would grow past this many bytes
+    #[facet(args::named)]
+    pub log_file_max_bytes: Option<u64>,
+
+    /// Number of rotated log files (`<name>.1.<ext>`, `<name>.2.<ext>`, ...) to keep; defaults to 5
+    #[facet(args::named)]
+    pub log_file_keep: Option<usize>,
+
     /// Emit structured JSON logs alongside stderr output.
     /// Optionally specify a filename; if not provided, a timestamped filename will be generated.
     #[facet(args::named)]
@@ -26,7 +43,62 @@ pub struct GlobalArgs {
     #[facet(rename = "stop-after", default, args::named)]
     pub stop_after: Option<String>,
 
+    /// Worker threads for parallel MFT processing; defaults to the number of logical CPUs
+    #[facet(args::named)]
+    pub threads: Option<usize>,
+
     /// Console PID for console reuse (hidden)
     #[facet(args::named)]
     pub console_pid: Option<u32>,
 }
+
+impl GlobalArgs {
+    /// Build the rayon thread pool configuration requested by `--threads`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--threads` is zero.
+    pub fn thread_pool_builder(&self) -> eyre::Result<ThreadPoolBuilder> {
+        let builder = ThreadPoolBuilder::new();
+        match self.threads {
+            None => Ok(builder),
+            Some(0) => eyre::bail!("--threads must be at least 1"),
+            Some(threads) => Ok(builder.num_threads(threads)),
+        }
+    }
+
+    /// Configure the global rayon thread pool from `--threads`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--threads` is zero or the global pool was already initialized.
+    pub fn init_thread_pool(&self) -> eyre::Result<()> {
+        if self.threads.is_none() {
+            return Ok(());
+        }
+        self.thread_pool_builder()?.build_global()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GlobalArgs;
+
+    #[test]
+    fn threads_configures_rayon_pool_size() -> eyre::Result<()> {
+        let args = GlobalArgs {
+            threads: Some(3),
+            ..Default::default()
+        };
+        let pool = args.thread_pool_builder()?.build()?;
+        assert_eq!(pool.current_num_threads(), 3);
+
+        let zero = GlobalArgs {
+            threads: Some(0),
+            ..Default::default()
+        };
+        assert!(zero.thread_pool_builder().is_err());
+        Ok(())
+    }
+}
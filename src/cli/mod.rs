@@ -169,6 +169,528 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_accepts_resume() {
+        let cli: Cli = figue::from_slice(&["sync", "--resume"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.plan.resume);
+    }
+
+    #[test]
+    fn sync_accepts_keep_going() {
+        let cli: Cli = figue::from_slice(&["sync", "--keep-going"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.plan.keep_going);
+    }
+
+    #[test]
+    fn sync_accepts_no_elevate() {
+        let cli: Cli = figue::from_slice(&["sync", "--no-elevate"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.plan.no_elevate);
+    }
+
+    #[test]
+    fn sync_accepts_multiple_output_mappings() {
+        let cli: Cli = figue::from_slice(&[
+            "sync",
+            "--drive",
+            "CE",
+            "--map",
+            r"C=D:\captures\c.mft",
+            "--map",
+            r"E=D:\captures\e.mft",
+        ])
+        .unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        let overrides = args.plan.mft_output_overrides(&['C', 'E']).unwrap();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(
+            overrides[&'C'],
+            std::path::PathBuf::from(r"D:\captures\c.mft")
+        );
+    }
+
+    #[test]
+    fn sync_accepts_compress() {
+        let cli: Cli = figue::from_slice(&["sync", "--compress"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.plan.compress);
+    }
+
+    #[test]
+    fn query_accepts_match_target() {
+        let cli: Cli = figue::from_slice(&["query", "flower", "--match", "name"]).unwrap();
+
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.plan.r#match, crate::query::QueryMatchTarget::Name);
+    }
+
+    #[test]
+    fn global_threads_parses_before_subcommand() {
+        let cli: Cli = figue::from_slice(&["--threads", "4", "query", "foo"]).unwrap();
+
+        assert_eq!(cli.global_args.threads, Some(4));
+        assert!(matches!(cli.command, Command::Query(_)));
+    }
+
+    #[test]
+    fn global_log_directives_are_repeatable() {
+        let cli: Cli = figue::from_slice(&[
+            "--log",
+            "teamy_mft::read=debug",
+            "--log",
+            "mft=trace",
+            "query",
+            "foo",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            cli.global_args.log,
+            vec!["teamy_mft::read=debug", "mft=trace"]
+        );
+    }
+
+    #[test]
+    fn sync_accepts_dry_run() {
+        let cli: Cli = figue::from_slice(&["sync", "--drive", "C", "--dry-run"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.dry_run);
+        assert_eq!(args.plan.drive_letter_pattern.as_ref(), "C");
+    }
+
+    #[test]
+    fn list_paths_accepts_report_conflicts() {
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--report-conflicts"]).unwrap();
+
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.report_conflicts);
+    }
+
+    #[test]
+    fn sync_accepts_chunk_size_and_queue_depth() {
+        let cli: Cli =
+            figue::from_slice(&["sync", "--chunk-size", "4194304", "--queue-depth", "64"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert_eq!(args.plan.chunk_size, Some(4_194_304));
+        assert_eq!(args.plan.queue_depth, Some(64));
+    }
+
+    #[test]
+    fn list_paths_accepts_primary() {
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--primary"]).unwrap();
+
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.primary);
+    }
+
+    #[test]
+    fn query_and_list_paths_accept_path_style() {
+        use crate::presentation::PathCase;
+        use crate::presentation::PathSlash;
+
+        let cli: Cli =
+            figue::from_slice(&["query", "foo", "--slash", "forward", "--case", "lower"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.path_style.slash, PathSlash::Forward);
+        assert_eq!(args.path_style.case, PathCase::Lower);
+
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--slash", "forward"]).unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert_eq!(args.path_style.slash, PathSlash::Forward);
+        assert_eq!(args.path_style.case, PathCase::Keep);
+    }
+
+    #[test]
+    fn query_accepts_sample_fraction() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--sample", "0.25"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.sample, Some(0.25));
+
+        let cli: Cli = figue::from_slice(&["query", "foo"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert!(args.sample().unwrap().is_full());
+    }
+
+    #[test]
+    fn query_round_trips_group_by_drive() {
+        use crate::presentation::DriveGrouping;
+        use crate::windows_utils::invocation::to_args::ToArgs;
+
+        let grouping = DriveGrouping {
+            group_by_drive: true,
+        };
+        let grouping_args = grouping
+            .to_args()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect::<Vec<_>>();
+        let mut query_args = vec!["query", "foo"];
+        query_args.extend(grouping_args.iter().map(String::as_str));
+
+        let cli: Cli = figue::from_slice(&query_args).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.grouping, grouping);
+
+        let cli: Cli = figue::from_slice(&["query", "foo"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert!(!args.grouping.group_by_drive);
+    }
+
+    #[test]
+    fn query_accepts_timeout() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--timeout", "1.5"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(
+            args.timeout().unwrap(),
+            Some(std::time::Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn plan_accepts_drive_and_json() {
+        let cli: Cli = figue::from_slice(&["plan", "--drive", "C", "--json"]).unwrap();
+        let Command::Plan(args) = cli.command else {
+            panic!("expected plan command");
+        };
+        assert_eq!(args.drive, "C");
+        assert!(args.json);
+        assert!(!args.no_elevate);
+    }
+
+    #[test]
+    fn plan_accepts_no_elevate() {
+        let cli: Cli = figue::from_slice(&["plan", "--drive", "C", "--no-elevate"]).unwrap();
+        let Command::Plan(args) = cli.command else {
+            panic!("expected plan command");
+        };
+        assert!(args.no_elevate);
+    }
+
+    #[test]
+    fn export_delta_accepts_drive_base_and_out() {
+        let cli: Cli = figue::from_slice(&[
+            "export-delta",
+            "--drive",
+            "C",
+            "--base",
+            "old.mft",
+            "--out",
+            "C.delta",
+        ])
+        .unwrap();
+        let Command::ExportDelta(args) = cli.command else {
+            panic!("expected export-delta command");
+        };
+        assert_eq!(args.drive, "C");
+        assert_eq!(args.base, "old.mft");
+        assert_eq!(args.out, "C.delta");
+    }
+
+    #[test]
+    fn mft_runs_accepts_drive() {
+        let cli: Cli = figue::from_slice(&["mft-runs", "--drive", "C"]).unwrap();
+        let Command::MftRuns(args) = cli.command else {
+            panic!("expected mft-runs command");
+        };
+        assert_eq!(args.drive, "C");
+        assert!(!args.no_elevate);
+    }
+
+    #[test]
+    fn list_paths_accepts_assume_fixed() {
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--assume-fixed"]).unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.assume_fixed);
+    }
+
+    #[test]
+    fn list_paths_accepts_since_and_until() {
+        let cli: Cli = figue::from_slice(&[
+            "list-paths",
+            "C",
+            "--since",
+            "2024-01-01T00:00:00Z",
+            "--until",
+            "2024-06-30T00:00:00Z",
+        ])
+        .unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert_eq!(args.since.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(args.until.as_deref(), Some("2024-06-30T00:00:00Z"));
+    }
+
+    #[test]
+    fn query_accepts_sort_order() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--sort", "path"]).unwrap();
+
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.sort, crate::query::QuerySortOrder::Path);
+    }
+
+    #[test]
+    fn query_and_list_paths_accept_count_only() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--count-only"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert!(args.count_only);
+
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--count-only"]).unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.count_only);
+    }
+
+    #[test]
+    fn query_and_list_paths_round_trip_output_file() {
+        use crate::presentation::OutputFile;
+        use crate::windows_utils::invocation::to_args::ToArgs;
+
+        let output = OutputFile {
+            output_file: Some(r"C:\exports\results.csv".to_string()),
+            bom: true,
+        };
+        let output_args = output
+            .to_args()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect::<Vec<_>>();
+        let output_args = output_args.iter().map(String::as_str);
+
+        let query_args = ["query", "foo"].into_iter().chain(output_args.clone());
+        let cli: Cli = figue::from_slice(&query_args.collect::<Vec<_>>()).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.output, output);
+
+        let list_args = ["list-paths", "C"].into_iter().chain(output_args);
+        let cli: Cli = figue::from_slice(&list_args.collect::<Vec<_>>()).unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert_eq!(args.output, output);
+    }
+
+    #[test]
+    fn query_and_list_paths_accept_include_orphans() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--include-orphans"]).unwrap();
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert!(args.plan.include_orphans);
+
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--include-orphans"]).unwrap();
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.include_orphans);
+    }
+
+    #[test]
+    fn list_cached_accepts_json() {
+        let cli: Cli = figue::from_slice(&["list-cached", "--json"]).unwrap();
+
+        let Command::ListCached(args) = cli.command else {
+            panic!("expected list-cached command");
+        };
+        assert!(args.json);
+    }
+
+    #[test]
+    fn list_paths_accepts_include_streams() {
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--include-streams"]).unwrap();
+
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert!(args.include_streams);
+    }
+
+    #[test]
+    fn verify_parsers_accepts_drive() {
+        let cli: Cli = figue::from_slice(&["verify-parsers", "--drive", "C"]).unwrap();
+
+        let Command::VerifyParsers(args) = cli.command else {
+            panic!("expected verify-parsers command");
+        };
+        assert_eq!(args.drive, "C");
+        assert_eq!(args.sample, None);
+    }
+
+    #[test]
+    fn query_accepts_prefix() {
+        let cli: Cli = figue::from_slice(&["query", "foo", "--prefix", r"C:\Users\"]).unwrap();
+
+        let Command::Query(args) = cli.command else {
+            panic!("expected query command");
+        };
+        assert_eq!(args.plan.prefix.as_deref(), Some(r"C:\Users\"));
+    }
+
+    #[test]
+    fn sync_accepts_max_entries() {
+        let cli: Cli = figue::from_slice(&["sync", "--max-entries", "1000"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        let tuning = args.plan.read_tuning().unwrap();
+        assert_eq!(tuning.max_entries, Some(1000));
+    }
+
+    #[test]
+    fn sync_accepts_verify_after() {
+        let cli: Cli = figue::from_slice(&["sync", "--verify-after"]).unwrap();
+
+        let Command::Sync(args) = cli.command else {
+            panic!("expected sync command");
+        };
+        assert!(args.plan.verify_after);
+    }
+
+    #[test]
+    fn file_extents_accepts_path() {
+        let cli: Cli = figue::from_slice(&["file-extents", r"C:\pagefile.sys"]).unwrap();
+
+        let Command::FileExtents(args) = cli.command else {
+            panic!("expected file-extents command");
+        };
+        assert_eq!(args.path, r"C:\pagefile.sys");
+    }
+
+    #[test]
+    fn export_sqlite_accepts_pattern_and_db() {
+        let cli: Cli =
+            figue::from_slice(&["export-sqlite", "CD", "--db", r".\mft.sqlite"]).unwrap();
+
+        let Command::ExportSqlite(args) = cli.command else {
+            panic!("expected export-sqlite command");
+        };
+        assert_eq!(args.drive_letter_pattern.as_ref(), "CD");
+        assert_eq!(args.db, r".\mft.sqlite");
+        assert!(!args.allocated);
+    }
+
+    #[test]
+    fn export_sqlite_accepts_allocated() {
+        let cli: Cli =
+            figue::from_slice(&["export-sqlite", "C", "--db", "mft.sqlite", "--allocated"])
+                .unwrap();
+
+        let Command::ExportSqlite(args) = cli.command else {
+            panic!("expected export-sqlite command");
+        };
+        assert!(args.allocated);
+    }
+
+    #[test]
+    fn get_record_accepts_drive_record_and_hex() {
+        let cli: Cli =
+            figue::from_slice(&["get-record", "--drive", "C", "--record", "42", "--hex"]).unwrap();
+
+        let Command::GetRecord(args) = cli.command else {
+            panic!("expected get-record command");
+        };
+        assert_eq!(args.drive, "C");
+        assert_eq!(args.record, 42);
+        assert!(args.hex);
+    }
+
+    #[test]
+    fn list_paths_accepts_format() {
+        let cli: Cli = figue::from_slice(&["list-paths", "C", "--format", "ndjson"]).unwrap();
+
+        let Command::ListPaths(args) = cli.command else {
+            panic!("expected list-paths command");
+        };
+        assert_eq!(
+            args.format,
+            crate::cli::command::list_paths::ListPathsOutputFormat::Ndjson
+        );
+
+        let default: Cli = figue::from_slice(&["list-paths", "C"]).unwrap();
+        let Command::ListPaths(args) = default.command else {
+            panic!("expected list-paths command");
+        };
+        assert_eq!(
+            args.format,
+            crate::cli::command::list_paths::ListPathsOutputFormat::Text
+        );
+    }
+
+    #[test]
+    fn check_accepts_max_report() {
+        let cli: Cli = figue::from_slice(&["check", "CD", "--max-report", "5"]).unwrap();
+
+        let Command::Check(args) = cli.command else {
+            panic!("expected check command");
+        };
+        assert_eq!(args.drive_letter_pattern.as_ref(), "CD");
+        assert_eq!(args.max_report, Some(5));
+        assert!(!args.fail_fast);
+    }
+
+    #[test]
+    fn check_accepts_fail_fast() {
+        let cli: Cli = figue::from_slice(&["check", "C", "--fail-fast"]).unwrap();
+
+        let Command::Check(args) = cli.command else {
+            panic!("expected check command");
+        };
+        assert!(args.fail_fast);
+    }
+
     #[test]
     fn query_accepts_drive_long_alias() {
         let cli: Cli = figue::from_slice(&["query", "flowers", "--drive", "CD"]).unwrap();
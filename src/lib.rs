@@ -103,6 +103,7 @@ pub fn main() -> eyre::Result<()> {
 
     // Initialize logging
     logging_init::init_logging(&cli.global_args, cancellation_token.clone())?;
+    cli.global_args.init_thread_pool()?;
 
     if let Some(pid) = cli.global_args.console_pid {
         console_attach(pid)?;
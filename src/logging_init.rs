@@ -2,12 +2,18 @@ use crate::cancellation::CancellationToken;
 use crate::cli::global_args::GlobalArgs;
 use chrono::Local;
 use color_eyre::owo_colors::OwoColorize;
+use eyre::Context;
 use eyre::bail;
 use std::fmt;
 use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Instant;
 use tracing::Event;
 use tracing::debug;
@@ -15,14 +21,15 @@ use tracing::field::Field;
 use tracing::field::Visit;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Registry;
+use tracing_subscriber::filter::Directive;
 use tracing_subscriber::filter::FilterFn;
 use tracing_subscriber::fmt::FmtContext;
 use tracing_subscriber::fmt::FormatEvent;
 use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::MakeWriter;
 #[cfg(all(feature = "tracy", not(test)))]
 use tracing_subscriber::fmt::format::DefaultFields;
 use tracing_subscriber::fmt::format::Writer;
-use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::registry::LookupSpan;
@@ -34,6 +41,115 @@ struct SourceAwareEventFormat<E> {
 
 static LOG_START: LazyLock<Instant> = LazyLock::new(Instant::now);
 
+/// File name used for `--log-file <dir>` when `--log-file-name` is not given.
+const DEFAULT_LOG_FILE_NAME_TEMPLATE: &str = "log_{timestamp}.ndjson";
+
+/// Rotated log files kept when `--log-file-max-bytes` is set without `--log-file-keep`.
+const DEFAULT_LOG_FILE_KEEP: usize = 5;
+
+/// Log file that rolls over to `<name>.1.<ext>` once it would exceed `max_bytes`.
+#[derive(Debug, Clone)]
+struct RotatingLogFile {
+    state: Arc<Mutex<RotatingLogFileState>>,
+}
+
+#[derive(Debug)]
+struct RotatingLogFileState {
+    path: PathBuf,
+    /// `None` between closing the old file and opening its replacement during rotation.
+    file: Option<File>,
+    written: u64,
+    max_bytes: Option<u64>,
+    keep: usize,
+}
+
+impl RotatingLogFile {
+    fn create(path: &Path, max_bytes: Option<u64>, keep: usize) -> io::Result<Self> {
+        Ok(Self {
+            state: Arc::new(Mutex::new(RotatingLogFileState {
+                path: path.to_path_buf(),
+                file: Some(File::create(path)?),
+                written: 0,
+                max_bytes,
+                keep,
+            })),
+        })
+    }
+}
+
+impl RotatingLogFileState {
+    /// Path of the `index`th rotated file, e.g. `log.2.ndjson` for `log.ndjson`.
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match self.path.extension() {
+            Some(extension) => format!("{stem}.{index}.{}", extension.to_string_lossy()),
+            None => format!("{stem}.{index}"),
+        };
+        self.path.with_file_name(name)
+    }
+
+    /// The open log file, reopened for appending if an earlier rotation failed part way.
+    fn file(&mut self) -> io::Result<&mut File> {
+        let file = match self.file.take() {
+            Some(file) => file,
+            None => File::options().create(true).append(true).open(&self.path)?,
+        };
+        Ok(self.file.insert(file))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        // Windows refuses to rename a file that is still open.
+        drop(self.file.take());
+        if self.keep > 0 {
+            let oldest = self.rotated_path(self.keep);
+            if oldest.exists() {
+                std::fs::remove_file(oldest)?;
+            }
+            for index in (1..self.keep).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = Some(File::create(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let would_overflow = state
+            .max_bytes
+            .is_some_and(|max_bytes| state.written + buf.len() as u64 > max_bytes);
+        if would_overflow && state.written > 0 {
+            state.rotate()?;
+        }
+        let written = state.file()?.write(buf)?;
+        state.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .file()?
+            .flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingLogFile {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
 #[derive(Debug, Default)]
 struct DaemonRemotePrettyFields {
     message: Option<String>,
@@ -325,16 +441,51 @@ fn style_daemon_field_list(ansi: bool, level: tracing::Level, fields: &str) -> S
 }
 
 fn default_log_filter(global_args: &GlobalArgs) -> eyre::Result<EnvFilter> {
-    if let Some(filter) = global_args.log_filter.as_ref() {
+    let mut filter = if let Some(filter) = global_args.log_filter.as_ref() {
         if global_args.debug {
             bail!("cannot specify log filter with --debug");
         }
-        return EnvFilter::builder().parse(filter).map_err(Into::into);
+        EnvFilter::builder().parse(filter)?
+    } else {
+        let own_level = if global_args.debug { "debug" } else { "info" };
+        EnvFilter::builder().parse(format!("warn,teamy_mft={own_level}"))?
+    };
+
+    for directive in &global_args.log {
+        let directive = directive
+            .parse::<Directive>()
+            .wrap_err_with(|| format!("Invalid --log directive {directive:?}"))?;
+        filter = filter.add_directive(directive);
     }
+    Ok(filter)
+}
+
+/// Expand `{timestamp}` and `{pid}` in a `--log-file-name` template.
+fn expand_log_file_name(template: &str, timestamp: &str, pid: u32) -> String {
+    template
+        .replace("{timestamp}", timestamp)
+        .replace("{pid}", &pid.to_string())
+}
 
-    let own_level = if global_args.debug { "debug" } else { "info" };
-    let filter = format!("warn,teamy_mft={own_level}");
-    EnvFilter::builder().parse(filter).map_err(Into::into)
+/// Resolve where structured logs go: `--log-file` as-is when it names a file, otherwise the
+/// expanded `--log-file-name` inside the `--log-file` directory or the working directory.
+fn json_log_path(global_args: &GlobalArgs) -> Option<PathBuf> {
+    let directory = match (&global_args.log_file, &global_args.log_file_name) {
+        (None, None) => return None,
+        (Some(path), _) if !PathBuf::from(path).is_dir() => return Some(PathBuf::from(path)),
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(_)) => PathBuf::new(),
+    };
+    let template = global_args
+        .log_file_name
+        .as_deref()
+        .unwrap_or(DEFAULT_LOG_FILE_NAME_TEMPLATE);
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+    Some(directory.join(expand_log_file_name(
+        template,
+        &timestamp,
+        std::process::id(),
+    )))
 }
 
 fn stderr_event_filter(metadata: &tracing::Metadata<'_>) -> bool {
@@ -367,10 +518,6 @@ fn tracy_log_filter() -> eyre::Result<EnvFilter> {
 /// # Errors
 ///
 /// This function will return an error if creating the log file or directories fails.
-///
-/// # Panics
-///
-/// This function may panic if locking or cloning the log file handle fails.
 pub fn init_logging(
     global_args: &GlobalArgs,
     cancellation_token: CancellationToken,
@@ -398,27 +545,16 @@ pub fn init_logging(
         crate::cancellation::StopAfterLayer::new(stop_after, cancellation_token)
     }));
 
-    let json_log_path = match global_args.log_file.as_ref() {
-        None => None,
-        Some(path) if std::path::PathBuf::from(path).is_dir() => {
-            let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
-            let filename = format!("log_{timestamp}.ndjson");
-            Some(std::path::PathBuf::from(path).join(filename))
-        }
-        Some(path) => Some(std::path::PathBuf::from(path)),
-    };
+    let json_log_path = json_log_path(global_args);
     let json_layer = if let Some(ref json_log_path) = json_log_path {
         if let Some(parent) = json_log_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        let file = File::create(json_log_path)?;
-        let file = Arc::new(Mutex::new(file));
-        let json_writer = BoxMakeWriter::new(move || {
-            file.lock()
-                .expect("failed to lock json log file")
-                .try_clone()
-                .expect("failed to clone json log file handle")
-        });
+        let json_writer = RotatingLogFile::create(
+            json_log_path,
+            global_args.log_file_max_bytes,
+            global_args.log_file_keep.unwrap_or(DEFAULT_LOG_FILE_KEEP),
+        )?;
 
         let json_layer = tracing_subscriber::fmt::layer()
             .event_format(tracing_subscriber::fmt::format().json())
@@ -462,3 +598,86 @@ pub fn init_logging(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DEFAULT_LOG_FILE_NAME_TEMPLATE;
+    use super::RotatingLogFile;
+    use super::default_log_filter;
+    use super::expand_log_file_name;
+    use crate::cli::global_args::GlobalArgs;
+    use std::io::Write;
+    use tracing_subscriber::Registry;
+    use tracing_subscriber::fmt::MakeWriter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn log_file_name_template_expands_placeholders() {
+        assert_eq!(
+            expand_log_file_name(
+                "teamy_{pid}_{timestamp}.ndjson",
+                "2024-05-06_07-08-09",
+                4242
+            ),
+            "teamy_4242_2024-05-06_07-08-09.ndjson"
+        );
+        assert_eq!(
+            expand_log_file_name(DEFAULT_LOG_FILE_NAME_TEMPLATE, "2024-05-06_07-08-09", 1),
+            "log_2024-05-06_07-08-09.ndjson"
+        );
+    }
+
+    #[test]
+    fn log_directive_raises_level_for_named_target() -> eyre::Result<()> {
+        let filter = default_log_filter(&GlobalArgs {
+            log: vec!["teamy_mft::read=trace".to_owned()],
+            ..GlobalArgs::default()
+        })?;
+
+        tracing::subscriber::with_default(Registry::default().with(filter), || {
+            assert!(tracing::enabled!(target: "teamy_mft::read", tracing::Level::TRACE));
+            assert!(!tracing::enabled!(target: "teamy_mft::query", tracing::Level::DEBUG));
+            assert!(tracing::enabled!(target: "teamy_mft::query", tracing::Level::INFO));
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_log_directive_is_rejected() {
+        let error = default_log_filter(&GlobalArgs {
+            log: vec!["teamy_mft=loud".to_owned()],
+            ..GlobalArgs::default()
+        })
+        .expect_err("unknown level should not parse");
+
+        assert!(error.to_string().contains("Invalid --log directive"));
+    }
+
+    #[test]
+    fn writing_past_the_size_limit_rotates_the_log_file() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("log.ndjson");
+        let log_file = RotatingLogFile::create(&path, Some(16), 2)?;
+
+        for line in [
+            "first line\n",
+            "second line\n",
+            "third line\n",
+            "fourth line\n",
+        ] {
+            log_file.make_writer().write_all(line.as_bytes())?;
+        }
+
+        assert_eq!(std::fs::read_to_string(&path)?, "fourth line\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("log.1.ndjson"))?,
+            "third line\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("log.2.ndjson"))?,
+            "second line\n"
+        );
+        assert!(!dir.path().join("log.3.ndjson").exists());
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@ pub const DEFAULT_SERVICE_NAME: &str = "teamy-mft-daemon";
 pub const DEFAULT_PIPE_NAME: &str = r"\\.\pipe\teamy-mft-daemon";
 pub const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 300;
 pub const MFT_CACHE_FILE_EXTENSION: &str = ".mft";
+pub const COMPRESSED_MFT_CACHE_FILE_EXTENSION: &str = ".mft.zst";
 pub const SEARCH_INDEX_FILE_EXTENSION: &str = ".mft_search_index";
 pub const SEARCH_INDEX_TEMP_FILE_EXTENSION: &str = "mft_search_index.tmp";
 pub const OVERLAY_SEARCH_INDEX_FILE_EXTENSION: &str = ".mft_overlay_search_index";
@@ -229,6 +230,12 @@ pub struct PublishedDrivePaths {
     pub checkpoint_path: PathBuf,
 }
 
+/// Whether `path` names a zstd-compressed MFT snapshot (`*.zst`).
+#[must_use]
+pub fn is_compressed_mft_path(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "zst")
+}
+
 #[must_use]
 pub fn program_data_dir() -> PathBuf {
     std::env::var_os("PROGRAMDATA").map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from)
@@ -249,11 +256,23 @@ pub fn default_sync_dir() -> PathBuf {
     machine_root_dir().join("cache")
 }
 
+/// Published artifact paths for `drive_letter` in `sync_dir`.
+///
+/// `mft_path` is `<letter>.mft`, unless only a zstd-compressed `<letter>.mft.zst`
+/// snapshot exists, in which case it points at that instead.
 #[must_use]
 pub fn published_drive_paths(sync_dir: &Path, drive_letter: char) -> PublishedDrivePaths {
+    let mft_path = sync_dir.join(format!("{drive_letter}{MFT_CACHE_FILE_EXTENSION}"));
+    let compressed_mft_path = sync_dir.join(format!(
+        "{drive_letter}{COMPRESSED_MFT_CACHE_FILE_EXTENSION}"
+    ));
     PublishedDrivePaths {
         drive_letter,
-        mft_path: sync_dir.join(format!("{drive_letter}{MFT_CACHE_FILE_EXTENSION}")),
+        mft_path: if !mft_path.is_file() && compressed_mft_path.is_file() {
+            compressed_mft_path
+        } else {
+            mft_path
+        },
         base_index_path: sync_dir.join(format!("{drive_letter}{SEARCH_INDEX_FILE_EXTENSION}")),
         overlay_index_path: sync_dir.join(format!(
             "{drive_letter}{OVERLAY_SEARCH_INDEX_FILE_EXTENSION}"
@@ -1,4 +1,6 @@
 use crate::cancellation::CancellationToken;
+use crate::machine::config::COMPRESSED_MFT_CACHE_FILE_EXTENSION;
+use crate::machine::config::MFT_CACHE_FILE_EXTENSION;
 use crate::machine::config::MachineConfig;
 use crate::machine::config::PublishedCheckpoint;
 use crate::machine::config::current_unix_ms;
@@ -28,18 +30,23 @@ use crate::query::QueryRowFilter;
 use crate::query::resolve_query_scopes;
 use crate::query::visit_drive_search_index_rows;
 use crate::search_index::format::SEARCH_INDEX_VERSION;
-use crate::sync::IfExistsOutputBehaviour;
+use crate::sync::DriveSyncInfo;
 use crate::sync::SyncPlan;
+use crate::sync::SyncSummary;
 use crate::sync::execute_sync;
 use crate::sync::resolve_drive_infos_in_dir_for_letters;
 use crate::windows_utils::string::EasyPCWSTR;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
+use eyre::Context;
 use eyre::ContextCompat;
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::ffi::c_void;
 use std::ops::ControlFlow;
 use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::sync::atomic::AtomicIsize;
@@ -88,9 +95,36 @@ struct MachineDaemonPipeAcceptor {
 
 #[derive(Debug, Clone)]
 pub struct MachineCacheSyncResult {
+    /// Drives whose snapshot, overlay and checkpoint were written.
     pub synced_drives: Vec<char>,
+    /// Synced drives with an active USN journal.
     pub live_drives: Vec<char>,
     pub skipped_drives: Vec<(char, String)>,
+    /// Drives whose sync failed under `--keep-going`, with the error.
+    pub failed_drives: Vec<(char, String)>,
+}
+
+impl MachineCacheSyncResult {
+    /// Split a finished sync into synced and failed drives, keeping only synced drives live.
+    fn from_summary(
+        summary: &SyncSummary,
+        live_drives: Vec<char>,
+        skipped_drives: Vec<(char, String)>,
+    ) -> Self {
+        let synced_drives = summary.succeeded().collect::<Vec<_>>();
+        Self {
+            live_drives: live_drives
+                .into_iter()
+                .filter(|drive| synced_drives.contains(drive))
+                .collect(),
+            failed_drives: summary
+                .failed()
+                .map(|(drive, error)| (drive, error.to_owned()))
+                .collect(),
+            synced_drives,
+            skipped_drives,
+        }
+    }
 }
 
 type SupportedDriveSyncOutcome = (
@@ -1085,6 +1119,13 @@ impl DaemonRuntimeState {
         request: SyncPlan,
         cancel: &CancellationToken,
     ) -> Result<(), MachineError> {
+        // The daemon runs as SYSTEM, so it only ever writes inside the owner-restricted sync
+        // dir; honouring a client's `--map` would let any caller write files as SYSTEM.
+        if !request.map.is_empty() {
+            return Err(MachineError::degraded(String::from(
+                "`--map` is not accepted by the machine daemon; use `--no-daemon` to write snapshots outside the sync dir",
+            )));
+        }
         self.flush_dirty_drives(cancel);
         crate::machine::security::restrict_path_to_owner(&self.sync_dir, &self.owner_sid)
             .map_err(|error| MachineError::degraded(error.to_string()))?;
@@ -1120,7 +1161,7 @@ impl DaemonRuntimeState {
             repair_published_drive_permissions(&self.sync_dir, &self.owner_sid, &drive_letters)
                 .map_err(|error| MachineError::degraded(error.to_string()))?;
             let sync_result =
-                sync_machine_cache_async(&self.sync_dir, &drive_letters, request.if_exists, cancel)
+                sync_machine_cache_async(&self.sync_dir, &drive_letters, &request, cancel)
                     .await
                     .map_err(|error| MachineError::degraded(error.to_string()))?;
 
@@ -1128,8 +1169,12 @@ impl DaemonRuntimeState {
                 synced_drives = ?sync_result.synced_drives,
                 live_drives = ?sync_result.live_drives,
                 skipped_drives = ?sync_result.skipped_drives,
+                failed_drives = ?sync_result.failed_drives,
                 "Machine-managed sync completed"
             );
+            for (drive, error) in &sync_result.failed_drives {
+                warn!(drive = %drive, error = %error, "Drive sync failed; keeping its previous snapshot and checkpoint");
+            }
 
             for &drive in &drive_letters {
                 self.drives.remove(&drive);
@@ -2076,13 +2121,16 @@ fn machine_error_from_panic(
     MachineError::degraded(format!("{context}: {detail}"))
 }
 
+/// Drives that synced are published even when another drive failed.
+///
 /// # Errors
 ///
-/// Returns an error if sync fails or if overlay/checkpoint sidecars cannot be written.
+/// Returns an error if sync fails, if overlay/checkpoint sidecars cannot be written, or if
+/// any drive failed and `plan.keep_going` is not set.
 pub fn sync_machine_cache(
     sync_dir: &std::path::Path,
     drive_letters: &[char],
-    if_exists: IfExistsOutputBehaviour,
+    plan: &SyncPlan,
     cancel: &CancellationToken,
 ) -> eyre::Result<MachineCacheSyncResult> {
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -2091,7 +2139,7 @@ pub fn sync_machine_cache(
     runtime.block_on(sync_machine_cache_async(
         sync_dir,
         drive_letters,
-        if_exists,
+        plan,
         cancel,
     ))
 }
@@ -2099,17 +2147,86 @@ pub fn sync_machine_cache(
 async fn sync_machine_cache_async(
     sync_dir: &std::path::Path,
     drive_letters: &[char],
-    if_exists: IfExistsOutputBehaviour,
+    plan: &SyncPlan,
     cancel: &CancellationToken,
 ) -> eyre::Result<MachineCacheSyncResult> {
     std::fs::create_dir_all(sync_dir)?;
     let (live_drives, snapshot_cursors, skipped_drives) =
         collect_supported_drives_for_machine_sync(drive_letters);
-    let drive_infos =
+    eyre::ensure!(
+        !(plan.compress && plan.resume),
+        "`--compress` cannot be combined with `--resume`"
+    );
+    let mft_output_overrides = plan.mft_output_overrides(drive_letters)?;
+    let mut drive_infos =
         resolve_drive_infos_in_dir_for_letters(sync_dir, drive_letters.iter().copied())?;
-    execute_sync(drive_infos.clone(), &if_exists, cancel).await?;
+    for info in &mut drive_infos {
+        if let Some(mft_output_path) = mft_output_overrides.get(&info.drive_letter) {
+            if let Some(parent) = mft_output_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            info.mft_output_path.clone_from(mft_output_path);
+        } else {
+            info.mft_output_path = plan.default_mft_output_path(sync_dir, info.drive_letter);
+        }
+    }
+    let summary = execute_sync(drive_infos.clone(), plan, cancel).await?;
+    // Publish every drive that succeeded before reporting failures: its base index and
+    // manifest entry are already new, so its overlay and checkpoint must follow.
+    publish_synced_drives(
+        sync_dir,
+        plan,
+        &drive_infos,
+        &mft_output_overrides,
+        &snapshot_cursors,
+        &summary,
+    )?;
+    let summary = summary.finish(plan.keep_going)?;
+
+    Ok(MachineCacheSyncResult::from_summary(
+        &summary,
+        live_drives,
+        skipped_drives,
+    ))
+}
 
-    for info in drive_infos {
+/// Reset the overlay and write a fresh checkpoint for every drive in `summary` that
+/// succeeded, after dropping its snapshot in the other format.
+///
+/// Only drives with a fresh snapshot are touched; a failed drive keeps its old snapshot and
+/// checkpoint so the next incremental run does not treat it as current.
+fn publish_synced_drives(
+    sync_dir: &std::path::Path,
+    plan: &SyncPlan,
+    drive_infos: &[DriveSyncInfo],
+    mft_output_overrides: &HashMap<char, PathBuf>,
+    snapshot_cursors: &FxHashMap<char, JournalCursor>,
+    summary: &SyncSummary,
+) -> eyre::Result<()> {
+    let succeeded = summary.succeeded().collect::<BTreeSet<_>>();
+    let synced_drive_infos = drive_infos
+        .iter()
+        .filter(|info| succeeded.contains(&info.drive_letter))
+        .collect::<Vec<_>>();
+
+    // Drop the snapshot in the other format so `published_drive_paths` resolves the fresh one.
+    for info in &synced_drive_infos {
+        if mft_output_overrides.contains_key(&info.drive_letter) {
+            continue;
+        }
+        let stale_extension = if plan.compress {
+            MFT_CACHE_FILE_EXTENSION
+        } else {
+            COMPRESSED_MFT_CACHE_FILE_EXTENSION
+        };
+        let stale_path = sync_dir.join(format!("{}{stale_extension}", info.drive_letter));
+        if stale_path.is_file() {
+            std::fs::remove_file(&stale_path)
+                .wrap_err_with(|| format!("Failed removing stale {}", stale_path.display()))?;
+        }
+    }
+
+    for info in synced_drive_infos {
         let paths = published_drive_paths(sync_dir, info.drive_letter);
         crate::search_index::search_index_bytes::SearchIndexBytesMut::from_rows(
             crate::search_index::format::SearchIndexHeader::new(info.drive_letter, 0, 0),
@@ -2137,11 +2254,7 @@ async fn sync_machine_cache_async(
         save_checkpoint(&paths.checkpoint_path, &checkpoint)?;
     }
 
-    Ok(MachineCacheSyncResult {
-        synced_drives: drive_letters.to_vec(),
-        live_drives,
-        skipped_drives,
-    })
+    Ok(())
 }
 
 fn collect_supported_drives_for_machine_sync(drive_letters: &[char]) -> SupportedDriveSyncOutcome {
@@ -2175,14 +2288,22 @@ mod tests {
     use super::DriveWorkerRuntimeMode;
     use super::DriveWorkerState;
     use super::DriveWorkerStatusSnapshot;
+    use super::MachineCacheSyncResult;
+    use super::publish_synced_drives;
     use super::warm_next_drive_worker;
     use crate::cancellation::CancellationToken;
+    use crate::machine::config::load_checkpoint;
     use crate::machine::config::published_drive_paths;
+    use crate::machine::usn::JournalCursor;
     use crate::query::QueryPlan;
     use crate::search_index::format::SearchIndexHeader;
     use crate::search_index::format::SearchIndexPathRow;
     use crate::search_index::search_index_bytes::SearchIndexBytesMut;
+    use crate::sync::SyncPlan;
+    use crate::sync::SyncSummary;
+    use crate::sync::resolve_drive_infos_in_dir_for_letters;
     use rustc_hash::FxHashMap;
+    use std::collections::HashMap;
     use std::sync::Mutex as StdMutex;
     use std::time::Duration;
     use std::time::Instant;
@@ -2222,6 +2343,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn failed_drives_are_reported_separately_from_synced_drives() {
+        let mut summary = SyncSummary::default();
+        summary.record('C', &Ok(()));
+        summary.record(
+            'D',
+            &Err(eyre::eyre!("Failed reading MFT data for drive D")),
+        );
+
+        let result = MachineCacheSyncResult::from_summary(
+            &summary,
+            vec!['C', 'D'],
+            vec![('E', String::from("no USN journal"))],
+        );
+
+        assert_eq!(result.synced_drives, vec!['C']);
+        assert_eq!(result.live_drives, vec!['C']);
+        assert_eq!(
+            result.failed_drives,
+            vec![('D', String::from("Failed reading MFT data for drive D"))]
+        );
+        assert_eq!(
+            result.skipped_drives,
+            vec![('E', String::from("no USN journal"))]
+        );
+    }
+
+    #[test]
+    fn failed_drives_do_not_hold_back_publishing_the_synced_ones() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let sync_dir = temp_dir.path();
+        let drive_infos = resolve_drive_infos_in_dir_for_letters(sync_dir, ['C', 'D'])?;
+        for drive in ['C', 'D'] {
+            std::fs::write(sync_dir.join(format!("{drive}.mft.zst")), b"stale")?;
+        }
+        let mut snapshot_cursors = FxHashMap::default();
+        snapshot_cursors.insert(
+            'C',
+            JournalCursor {
+                journal_id: 7,
+                first_usn: 0,
+                next_usn: 42,
+                lowest_valid_usn: 0,
+                max_usn: u64::MAX,
+            },
+        );
+        let mut summary = SyncSummary::default();
+        summary.record('C', &Ok(()));
+        summary.record(
+            'D',
+            &Err(eyre::eyre!("Failed reading MFT data for drive D")),
+        );
+        let plan = SyncPlan::default();
+
+        publish_synced_drives(
+            sync_dir,
+            &plan,
+            &drive_infos,
+            &HashMap::new(),
+            &snapshot_cursors,
+            &summary,
+        )?;
+
+        let c = published_drive_paths(sync_dir, 'C');
+        let d = published_drive_paths(sync_dir, 'D');
+        assert!(!sync_dir.join("C.mft.zst").exists());
+        assert!(sync_dir.join("D.mft.zst").is_file());
+        assert!(c.overlay_index_path.is_file());
+        assert_eq!(
+            load_checkpoint(&c.checkpoint_path)?.and_then(|checkpoint| checkpoint.snapshot_usn),
+            Some(42)
+        );
+        assert!(!d.overlay_index_path.exists());
+        assert!(!d.checkpoint_path.exists());
+        assert!(summary.finish(plan.keep_going).is_err());
+        Ok(())
+    }
+
     #[test]
     fn cancelled_drive_query_does_not_mark_drive_snapshot_only() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir()?;
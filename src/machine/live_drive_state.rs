@@ -652,12 +652,10 @@ impl LiveDriveGraph {
                     .and_modify(|(namespace, current_name)| {
                         if namespace_rank(name_ref.namespace) < namespace_rank(*namespace) {
                             *namespace = name_ref.namespace;
-                            *current_name = decode_utf16_lossy(name_ref.name_utf16);
+                            *current_name = name_ref.name_lossy();
                         }
                     })
-                    .or_insert_with(|| {
-                        (name_ref.namespace, decode_utf16_lossy(name_ref.name_utf16))
-                    });
+                    .or_insert_with(|| (name_ref.namespace, name_ref.name_lossy()));
             }
 
             let mut links = best_by_parent
@@ -960,10 +958,6 @@ fn join_windows_path(parent: &str, child: &str) -> Pathlike {
     }
 }
 
-fn decode_utf16_lossy(units: &[u16]) -> String {
-    String::from_utf16_lossy(units)
-}
-
 fn namespace_rank(namespace: u8) -> u8 {
     match namespace {
         1 => 0,
@@ -997,6 +991,7 @@ mod tests {
     use crate::search_index::format::SearchIndexPathRow;
     use crate::search_index::search_index_bytes::SearchIndexBytesMut;
     use crate::sync::IfExistsOutputBehaviour;
+    use crate::sync::SyncPlan;
     use eyre::ContextCompat;
     use rustc_hash::FxHashMap;
     use std::path::Path;
@@ -1629,7 +1624,10 @@ mod tests {
         sync_machine_cache(
             cache_dir.path(),
             &[drive_letter],
-            IfExistsOutputBehaviour::Overwrite,
+            &SyncPlan {
+                if_exists: IfExistsOutputBehaviour::Overwrite,
+                ..SyncPlan::default()
+            },
             &cancel,
         )?;
 
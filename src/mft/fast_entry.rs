@@ -11,6 +11,7 @@ use tracing::debug_span;
 use tracing::instrument;
 
 pub const ATTR_TYPE_FILE_NAME: u32 = 0x30;
+pub const ATTR_TYPE_REPARSE_POINT: u32 = 0xC0;
 const ATTRIBUTE_TYPE_END: u32 = 0xFFFF_FFFF;
 
 #[derive(Clone, Copy, Debug)]
@@ -18,7 +19,25 @@ pub struct FileNameRef<'a> {
     pub entry_id: u32,
     pub parent_ref: u64, // raw 64-bit reference (contains sequence)
     pub namespace: u8,
-    pub name_utf16: &'a [u16],
+    /// UTF-16LE name bytes borrowed from the record; not necessarily 2-byte aligned.
+    pub name_bytes: &'a [u8],
+}
+
+impl FileNameRef<'_> {
+    /// The name's UTF-16 code units, decoded from the unaligned little-endian bytes.
+    pub fn name_units(&self) -> impl Iterator<Item = u16> + '_ {
+        self.name_bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+    }
+
+    /// Decode the name, replacing unpaired surrogates with U+FFFD.
+    #[must_use]
+    pub fn name_lossy(&self) -> String {
+        char::decode_utf16(self.name_units())
+            .map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
 }
 
 /// Collection of `FILE_NAME` attributes extracted from MFT data.
@@ -34,6 +53,8 @@ pub struct FileNameCollection<'a> {
     pub per_entry_indices: Vec<Vec<usize>>,
     /// Per-entry deleted state derived from MFT record flags (true when not in-use)
     pub per_entry_deleted: Vec<bool>,
+    /// Per-entry reparse tag from `$REPARSE_POINT` (0xC0), `None` for ordinary entries
+    pub per_entry_reparse_tag: Vec<Option<u32>>,
 }
 
 impl<'a> FileNameCollection<'a> {
@@ -95,6 +116,16 @@ impl<'a> FileNameCollection<'a> {
             .copied()
             .unwrap_or(false)
     }
+
+    /// Returns the reparse tag when the entry carries a `$REPARSE_POINT` attribute
+    /// (mount points, junctions, symlinks, cloud placeholders, ...).
+    #[must_use]
+    pub fn reparse_tag(&self, entry_id: MftRecordIndex) -> Option<u32> {
+        self.per_entry_reparse_tag
+            .get(entry_id.get())
+            .copied()
+            .flatten()
+    }
 }
 
 #[inline]
@@ -196,24 +227,11 @@ pub fn for_each_filename<'a, F: FnMut(FileNameRef<'a>)>(
                     let name_utf16_off = value_abs + 0x42;
                     let name_bytes_end = name_utf16_off + name_len * 2;
                     if name_bytes_end <= entry_bytes.len() {
-                        #[expect(
-                            clippy::cast_ptr_alignment,
-                            reason = "NTFS data is expected to be properly aligned for u16"
-                        )]
-                        // SAFETY: `entry_bytes` contains validated NTFS FILE_NAME data with a proper size, and the bytes are expected to align for u16 reads.
-                        let raw: &[u16] = unsafe {
-                            std::slice::from_raw_parts(
-                                entry_bytes[name_utf16_off..name_bytes_end]
-                                    .as_ptr()
-                                    .cast::<u16>(),
-                                name_len,
-                            )
-                        };
                         f(FileNameRef {
                             entry_id,
                             parent_ref,
                             namespace,
-                            name_utf16: raw,
+                            name_bytes: &entry_bytes[name_utf16_off..name_bytes_end],
                         });
                         count += 1;
                     }
@@ -225,6 +243,40 @@ pub fn for_each_filename<'a, F: FnMut(FileNameRef<'a>)>(
     count
 }
 
+/// Find the `$REPARSE_POINT` (0xC0) attribute in an entry and return its reparse tag.
+///
+/// The tag is the first `u32` of the attribute value. A non-resident `$REPARSE_POINT`
+/// keeps its value in clusters outside the record, so it is reported with tag `0`.
+#[must_use]
+pub fn reparse_point_tag(entry_bytes: &[u8]) -> Option<u32> {
+    if entry_bytes.len() < 0x18 || &entry_bytes[0..4] != b"FILE" {
+        return None;
+    }
+    let mut offset = read_u16(entry_bytes, 0x14)? as usize;
+    if offset == 0 {
+        return None;
+    }
+    while offset + 16 <= entry_bytes.len() {
+        let attr_type = read_u32(entry_bytes, offset)?;
+        if attr_type == ATTRIBUTE_TYPE_END {
+            return None;
+        }
+        let attr_len = read_u32(entry_bytes, offset + 4)? as usize;
+        if attr_len == 0 || offset + attr_len > entry_bytes.len() {
+            return None;
+        }
+        if attr_type == ATTR_TYPE_REPARSE_POINT {
+            if entry_bytes.get(offset + 8).copied().unwrap_or(0) != 0 {
+                return Some(0);
+            }
+            let value_off = read_u16(entry_bytes, offset + 20)? as usize;
+            return read_u32(entry_bytes, offset + value_off);
+        }
+        offset += attr_len;
+    }
+    None
+}
+
 /// Parallel collection of all `FILE_NAME` attributes from MFT data.
 ///
 /// This function processes MFT entries in parallel to extract all `FILE_NAME` attributes efficiently.
@@ -250,7 +302,7 @@ pub fn for_each_filename<'a, F: FnMut(FileNameRef<'a>)>(
 /// Panics if the MFT entry count exceeds `u32::MAX`.
 #[instrument(level = "debug", skip_all)]
 pub fn collect_filenames<'a>(mft: &'a MftFile) -> FileNameCollection<'a> {
-    type PerThreadData<'a> = Vec<(Vec<FileNameRef<'a>>, Vec<(u32, usize)>)>;
+    type PerThreadData<'a> = Vec<(Vec<FileNameRef<'a>>, Vec<(u32, usize)>, Option<u32>)>;
 
     let (full, entry_size, entry_count, per_entry_deleted) = {
         let _span = debug_span!("prepare_collection_inputs").entered();
@@ -287,26 +339,31 @@ pub fn collect_filenames<'a>(mft: &'a MftFile) -> FileNameCollection<'a> {
                         pairs.push((fref.entry_id, global_index));
                     },
                 );
-                (list, pairs)
+                (list, pairs, reparse_point_tag(record_bytes))
             })
             .collect()
     };
 
     let mut file_names = {
         let _span = debug_span!("flatten_thread_results").entered();
-        let total = per_thread.iter().map(|(v, _)| v.len()).sum();
+        let total = per_thread.iter().map(|(v, _, _)| v.len()).sum();
         let mut file_names = Vec::with_capacity(total);
-        for (v, _) in &per_thread {
+        for (v, _, _) in &per_thread {
             file_names.extend_from_slice(v);
         }
         file_names
     };
 
+    let per_entry_reparse_tag = {
+        let _span = debug_span!("collect_reparse_tags").entered();
+        per_thread.iter().map(|(_, _, tag)| *tag).collect()
+    };
+
     let per_entry = {
         let _span = debug_span!("build_per_entry_index").entered();
         let mut per_entry: Vec<Vec<usize>> = vec![Vec::new(); entry_count];
         let mut base = 0usize;
-        for (v, pairs) in per_thread {
+        for (v, pairs, _) in per_thread {
             for (entry_id, local_idx) in pairs {
                 let global_idx = base + local_idx;
                 if let Some(vec) = per_entry.get_mut(entry_id as usize) {
@@ -325,6 +382,41 @@ pub fn collect_filenames<'a>(mft: &'a MftFile) -> FileNameCollection<'a> {
             all_filenames: std::mem::take(&mut file_names),
             per_entry_indices: per_entry,
             per_entry_deleted,
+            per_entry_reparse_tag,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ATTR_TYPE_REPARSE_POINT;
+    use super::reparse_point_tag;
+
+    const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+
+    fn record_with_attribute(attr_type: u32, content: &[u8]) -> Vec<u8> {
+        let attr_len = (0x18 + content.len()).next_multiple_of(8);
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x14..0x16].copy_from_slice(&0x38u16.to_le_bytes());
+        let attribute = &mut record[0x38..0x38 + attr_len];
+        attribute[0..4].copy_from_slice(&attr_type.to_le_bytes());
+        attribute[4..8].copy_from_slice(&u32::try_from(attr_len).unwrap().to_le_bytes());
+        attribute[0x10..0x14].copy_from_slice(&u32::try_from(content.len()).unwrap().to_le_bytes());
+        attribute[0x14..0x16].copy_from_slice(&0x18u16.to_le_bytes());
+        attribute[0x18..0x18 + content.len()].copy_from_slice(content);
+        record[0x38 + attr_len..0x3C + attr_len].copy_from_slice(&u32::MAX.to_le_bytes());
+        record
+    }
+
+    #[test]
+    fn detects_resident_reparse_point_attribute() {
+        let mut reparse_data = vec![0u8; 16];
+        reparse_data[0..4].copy_from_slice(&IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+        let record = record_with_attribute(ATTR_TYPE_REPARSE_POINT, &reparse_data);
+        assert_eq!(reparse_point_tag(&record), Some(IO_REPARSE_TAG_MOUNT_POINT));
+
+        let ordinary = record_with_attribute(0x10, &[0u8; 0x48]);
+        assert_eq!(reparse_point_tag(&ordinary), None);
+    }
+}
@@ -167,12 +167,32 @@ pub fn apply_fixup_in_place(entry: &mut [u8]) -> FixupState {
     }
 }
 
+/// Number of entries fixed up between progress callbacks in [`apply_fixups_parallel_with_progress`].
+const FIXUP_PROGRESS_BLOCK_ENTRIES: usize = 16 * 1024;
+
 /// Apply fixups to all entries in the buffer using parallelism when the `rayon` feature is enabled.
 /// Also logs basic telemetry: detected entry size, entry count, elapsed time and throughput, and outcome stats.
-#[instrument(level = "debug", skip_all)]
 // mfti[impl fixup.parallel-buffer-processing]
 pub fn apply_fixups_parallel(buf: &mut [u8], entry_size: usize) -> FixupStats {
+    apply_fixups_parallel_with_progress(buf, entry_size, None)
+}
+
+/// Like [`apply_fixups_parallel`], additionally calling `progress(entries_processed, entry_count)`
+/// every 16Ki entries and once the last entry is done.
+///
+/// Entries are still fixed up in parallel one at a time; only the callback is batched.
+/// Callbacks can arrive out of order across threads, so callers should treat the reported
+/// count as monotonic only by taking the maximum seen. The entry that completes the buffer
+/// reports `entry_count`.
+#[instrument(level = "debug", skip_all)]
+pub fn apply_fixups_parallel_with_progress(
+    buf: &mut [u8],
+    entry_size: usize,
+    progress: Option<&(dyn Fn(usize, usize) + Sync)>,
+) -> FixupStats {
     use rayon::prelude::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
     {
         let _span = debug_span!("validate_entry_alignment").entered();
         if entry_size == 0 || !buf.len().is_multiple_of(entry_size) {
@@ -200,15 +220,19 @@ pub fn apply_fixups_parallel(buf: &mut [u8], entry_size: usize) -> FixupStats {
 
     let stats = {
         let _span = debug_span!("parallel_apply_fixups").entered();
+        let processed = AtomicUsize::new(0);
         buf.par_chunks_mut(entry_size)
-            .enumerate()
-            .map(|(_entry_index, entry)| {
+            .map(|entry| {
                 #[cfg(feature = "extended_observability_per_record")]
                 let _span = debug_span!("apply_fixup_to_entry").entered();
-                if entry.len() < entry_size {
-                    return FixupState::Invalid;
+                let state = apply_fixup_in_place(entry);
+                if let Some(progress) = progress {
+                    let done = processed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if done.is_multiple_of(FIXUP_PROGRESS_BLOCK_ENTRIES) || done == entry_count {
+                        progress(done, entry_count);
+                    }
                 }
-                apply_fixup_in_place(entry)
+                state
             })
             .fold(FixupStats::default, |mut acc, state| {
                 #[cfg(feature = "extended_observability_per_record")]
@@ -252,6 +276,77 @@ pub fn apply_fixups_parallel(buf: &mut [u8], entry_size: usize) -> FixupStats {
     stats
 }
 
+/// An entry whose update sequence array did not match its sector tails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidFixupEntry {
+    /// Zero-based position of the entry within the buffer.
+    pub entry_index: usize,
+    /// Record number stored in the entry header at 0x2C.
+    pub record_number: u32,
+    /// First four bytes of the entry, normally `FILE`.
+    pub signature: [u8; 4],
+}
+
+/// Apply fixups to all entries in the buffer and return the entries that failed validation.
+///
+/// Returns an empty list when `entry_size` is smaller than a record header or does not evenly
+/// divide the buffer.
+#[instrument(level = "debug", skip_all)]
+pub fn collect_invalid_fixups(buf: &mut [u8], entry_size: usize) -> Vec<InvalidFixupEntry> {
+    use rayon::prelude::*;
+    let Some(entries) = validated_entries_mut(buf, entry_size) else {
+        return Vec::new();
+    };
+    entries
+        .enumerate()
+        .filter_map(|(entry_index, entry)| validate_entry(entry_index, entry))
+        .collect()
+}
+
+/// Split `buf` into entries for validation, or `None` when `entry_size` is smaller than a
+/// record header or does not evenly divide the buffer.
+fn validated_entries_mut(
+    buf: &mut [u8],
+    entry_size: usize,
+) -> Option<rayon::slice::ChunksMut<'_, u8>> {
+    use rayon::prelude::*;
+    if entry_size < 0x30 || !buf.len().is_multiple_of(entry_size) {
+        debug!(
+            "Invalid/unaligned entry size: entry_size={} buf_len={}",
+            entry_size,
+            buf.len()
+        );
+        return None;
+    }
+    Some(buf.par_chunks_mut(entry_size))
+}
+
+/// Apply fixups to one entry and describe it if it failed validation.
+///
+/// The shared validator behind [`collect_invalid_fixups`] and [`find_first_invalid_fixup`].
+fn validate_entry(entry_index: usize, entry: &mut [u8]) -> Option<InvalidFixupEntry> {
+    let signature = [entry[0], entry[1], entry[2], entry[3]];
+    let record_number = u32::from_le_bytes([entry[0x2C], entry[0x2D], entry[0x2E], entry[0x2F]]);
+    (apply_fixup_in_place(entry) == FixupState::Invalid).then_some(InvalidFixupEntry {
+        entry_index,
+        record_number,
+        signature,
+    })
+}
+
+/// Apply fixups like [`collect_invalid_fixups`] but stop at the first entry that fails
+/// validation, returning it.
+///
+/// Entries after the first invalid one are not guaranteed to have been fixed up.
+#[instrument(level = "debug", skip_all)]
+pub fn find_first_invalid_fixup(buf: &mut [u8], entry_size: usize) -> Option<InvalidFixupEntry> {
+    use rayon::prelude::*;
+    // `find_map_first` stops scheduling entries past the earliest match once one is found.
+    validated_entries_mut(buf, entry_size)?
+        .enumerate()
+        .find_map_first(|(entry_index, entry)| validate_entry(entry_index, entry))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,4 +359,107 @@ mod tests {
         entry[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
         assert_eq!(detect_entry_size(&entry), Some(1024));
     }
+
+    fn mk_entry(record_number: u32) -> Vec<u8> {
+        // 1 KiB entry: USA at 0x30 with update sequence 0x0001 and two sector slots.
+        let mut entry = vec![0u8; 1024];
+        entry[0..4].copy_from_slice(b"FILE");
+        entry[4..6].copy_from_slice(&0x30u16.to_le_bytes());
+        entry[6..8].copy_from_slice(&3u16.to_le_bytes());
+        entry[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        entry[0x2C..0x30].copy_from_slice(&record_number.to_le_bytes());
+        entry[0x30..0x32].copy_from_slice(&1u16.to_le_bytes());
+        entry[0x32..0x34].copy_from_slice(&[0xAA, 0xBB]);
+        entry[0x34..0x36].copy_from_slice(&[0xCC, 0xDD]);
+        entry[510..512].copy_from_slice(&1u16.to_le_bytes());
+        entry[1022..1024].copy_from_slice(&1u16.to_le_bytes());
+        entry
+    }
+
+    #[test]
+    fn collect_invalid_fixups_reports_corrupted_entry() {
+        let mut buf = mk_entry(0);
+        let mut corrupted = mk_entry(1);
+        corrupted[1022..1024].copy_from_slice(&[0x12, 0x34]);
+        buf.extend_from_slice(&corrupted);
+        buf.extend_from_slice(&mk_entry(2));
+
+        let invalid = collect_invalid_fixups(&mut buf, 1024);
+
+        assert_eq!(
+            invalid,
+            vec![InvalidFixupEntry {
+                entry_index: 1,
+                record_number: 1,
+                signature: *b"FILE",
+            }]
+        );
+        assert_eq!(&buf[510..512], &[0xAA, 0xBB]);
+        assert_eq!(&buf[1022..1024], &[0xCC, 0xDD]);
+    }
+
+    #[test]
+    fn find_first_invalid_fixup_reports_only_the_earliest_corrupted_entry() {
+        let mut buf = mk_entry(0);
+        for record_number in 1..=3 {
+            let mut entry = mk_entry(record_number);
+            if record_number != 2 {
+                entry[1022..1024].copy_from_slice(&[0x12, 0x34]);
+            }
+            buf.extend_from_slice(&entry);
+        }
+
+        let first = find_first_invalid_fixup(&mut buf, 1024);
+
+        assert_eq!(
+            first,
+            Some(InvalidFixupEntry {
+                entry_index: 1,
+                record_number: 1,
+                signature: *b"FILE",
+            })
+        );
+    }
+
+    #[test]
+    fn progress_reaches_entry_count() {
+        let entry_count = FIXUP_PROGRESS_BLOCK_ENTRIES * 2 + 3;
+        let mut buf = Vec::with_capacity(entry_count * 1024);
+        for record_number in 0..entry_count {
+            buf.extend_from_slice(&mk_entry(u32::try_from(record_number).unwrap()));
+        }
+        let max_reported = std::sync::atomic::AtomicUsize::new(0);
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let progress = |done: usize, total: usize| {
+            assert_eq!(total, entry_count);
+            max_reported.fetch_max(done, std::sync::atomic::Ordering::Relaxed);
+            calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        };
+
+        let stats = apply_fixups_parallel_with_progress(&mut buf, 1024, Some(&progress));
+
+        assert_eq!(stats.applied, u64::try_from(entry_count).unwrap());
+        assert_eq!(max_reported.into_inner(), entry_count);
+        assert_eq!(calls.into_inner(), 3);
+    }
+
+    #[test]
+    fn invalid_fixups_report_record_numbers_from_the_header() {
+        let mut buf = mk_entry(7);
+        let mut corrupted = mk_entry(42);
+        corrupted[510..512].copy_from_slice(&[0x12, 0x34]);
+        buf.extend_from_slice(&corrupted);
+
+        let invalid = collect_invalid_fixups(&mut buf, 1024);
+
+        assert_eq!(
+            invalid,
+            vec![InvalidFixupEntry {
+                entry_index: 1,
+                record_number: 42,
+                signature: *b"FILE",
+            }]
+        );
+        assert_eq!(&buf[510..512], &[0xAA, 0xBB]);
+    }
 }
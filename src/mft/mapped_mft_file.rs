@@ -0,0 +1,199 @@
+use crate::machine::config::is_compressed_mft_path;
+use crate::mft::fast_fixup::apply_fixup_in_place;
+use crate::mft::fast_fixup::detect_entry_size;
+use crate::mft::mft_record::MftRecord;
+use crate::mft::mft_record_size::MftRecordSize;
+use bytes::Bytes;
+use eyre::Context;
+use eyre::bail;
+use humansize::BINARY;
+use memmap2::Mmap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::path::Path;
+use teamy_uom_extensions::HumanInformationExt;
+use thousands::Separable;
+use tracing::debug;
+use tracing::instrument;
+use uom::si::information::byte;
+use uom::si::usize::Information;
+
+/// A cached `.mft` mapped read-only, for scans that should not copy the whole file.
+///
+/// [`crate::mft::mft_file::MftFile::from_path`] reads the file onto the heap and fixes up every
+/// record before the first one is visited. Here nothing is copied up front: each record is
+/// copied out of the mapping and fixed up only when [`Self::record_at`] or [`Self::records`]
+/// reaches it. The mapping and the file on disk are never written.
+///
+/// The file must not be truncated or rewritten by another process while it is mapped; doing so
+/// is undefined behaviour on most platforms and typically surfaces as an access violation.
+pub struct MappedMftFile {
+    mmap: Mmap,
+    record_size: MftRecordSize,
+}
+
+impl Debug for MappedMftFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedMftFile")
+            .field("size", &self.size().format_human(BINARY))
+            .field("entry_size", &self.record_size.format_human(BINARY))
+            .field("entry_count", &self.record_count().separate_with_commas())
+            .finish()
+    }
+}
+
+impl MappedMftFile {
+    /// Map an uncompressed cached MFT read-only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is compressed, the file cannot be opened or mapped, or its
+    /// length is not a whole number of records.
+    #[instrument(level = "debug")]
+    pub fn open(mft_file_path: &Path) -> eyre::Result<Self> {
+        if is_compressed_mft_path(mft_file_path) {
+            bail!(
+                "Cannot memory-map compressed MFT {}; use MftFile::from_path instead",
+                mft_file_path.display()
+            );
+        }
+        let file = File::open(mft_file_path)
+            .wrap_err_with(|| format!("Failed to open {}", mft_file_path.display()))?;
+        // SAFETY: the mapping is only ever read. Concurrent truncation by another process
+        // remains the caller's responsibility, as documented on the type.
+        let mmap = unsafe { Mmap::map(&file) }
+            .wrap_err_with(|| format!("Failed memory-mapping {}", mft_file_path.display()))?;
+
+        let Some(entry_size) = detect_entry_size(&mmap) else {
+            bail!("Cannot detect entry size for {}", mft_file_path.display());
+        };
+        let record_size = MftRecordSize::new(Information::new::<byte>(entry_size as usize))?;
+        if !mmap.len().is_multiple_of(entry_size as usize) {
+            bail!(
+                "{} length ({}) is not a multiple of entry size ({})",
+                mft_file_path.display(),
+                mmap.len(),
+                entry_size
+            );
+        }
+
+        let rtn = Self { mmap, record_size };
+        debug!(
+            "Mapped {} with entry size {} bytes and {} entries",
+            rtn.size().format_human(BINARY),
+            entry_size.separate_with_commas(),
+            rtn.record_count().separate_with_commas()
+        );
+        Ok(rtn)
+    }
+
+    #[must_use]
+    pub fn size(&self) -> Information {
+        Information::new::<byte>(self.mmap.len())
+    }
+
+    #[must_use]
+    pub fn record_size(&self) -> MftRecordSize {
+        self.record_size
+    }
+
+    #[must_use]
+    pub fn record_count(&self) -> usize {
+        self.mmap.len() / self.record_size.get::<byte>()
+    }
+
+    /// The mapped bytes as stored on disk, before any fixups.
+    #[must_use]
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Copy the record at `index` out of the mapping and apply its fixups to that copy.
+    ///
+    /// As in the bulk fixup pass of [`crate::mft::mft_file::MftFile`], a record whose fixups
+    /// do not validate is returned as read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is not below [`Self::record_count`].
+    pub fn record_at(&self, index: u64) -> eyre::Result<MftRecord> {
+        let record_count = self.record_count();
+        let Some(index) = usize::try_from(index)
+            .ok()
+            .filter(|index| *index < record_count)
+        else {
+            bail!("Record {index} is out of range; this MFT has {record_count} records");
+        };
+        let record_size = self.record_size.get::<byte>();
+        let start = index * record_size;
+        Ok(MftRecord::from_bytes_unchecked(Self::fixed_up(
+            &self.mmap[start..start + record_size],
+        )))
+    }
+
+    /// Iterate over the records, fixing each one up as it is reached.
+    ///
+    /// Each item is an error when the record does not carry a `FILE` signature, matching
+    /// [`crate::mft::mft_file::MftFile::records`].
+    pub fn records(&self) -> impl Iterator<Item = eyre::Result<MftRecord>> + '_ {
+        self.mmap
+            .chunks_exact(self.record_size.get::<byte>())
+            .map(|record| MftRecord::from_bytes(Self::fixed_up(record)))
+    }
+
+    fn fixed_up(record: &[u8]) -> Bytes {
+        let mut record = record.to_vec();
+        let _state = apply_fixup_in_place(&mut record);
+        Bytes::from(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MappedMftFile;
+    use crate::cancellation::CancellationToken;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::mft_record::MftRecord;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+
+    fn record_bytes(
+        records: impl Iterator<Item = eyre::Result<MftRecord>>,
+    ) -> Vec<Option<Vec<u8>>> {
+        records
+            .map(|record| record.ok().map(|record| record.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn mapped_records_match_the_copied_file() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        let docs = builder.directory(SYNTHETIC_ROOT_RECORD, "docs");
+        let notes = builder.file(docs, "notes.txt");
+        let raw = builder.build();
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("C.mft");
+        std::fs::write(&path, &raw)?;
+
+        let copied = MftFile::from_path(&path, &CancellationToken::new())?;
+        let mapped = MappedMftFile::open(&path)?;
+
+        assert_eq!(mapped.record_count(), copied.record_count());
+        assert_eq!(mapped.raw_bytes(), raw.as_slice());
+        assert_eq!(
+            record_bytes(mapped.records()),
+            record_bytes(copied.records())
+        );
+        assert_eq!(
+            mapped.record_at(notes)?.to_vec(),
+            copied.record_at(notes)?.to_vec()
+        );
+        assert!(mapped.record_at(copied.record_count() as u64).is_err());
+        assert_eq!(
+            std::fs::read(&path)?,
+            raw,
+            "on-disk file must stay untouched"
+        );
+        Ok(())
+    }
+}
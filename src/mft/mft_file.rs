@@ -1,5 +1,7 @@
 use crate::cancellation::CancellationToken;
+use crate::machine::config::is_compressed_mft_path;
 use crate::mft::fast_fixup::apply_fixups_parallel;
+use crate::mft::mft_record::MftRecord;
 use crate::mft::mft_record_iter::MftRecordIter;
 use crate::mft::mft_record_size::MftRecordSize;
 use bytes::Bytes;
@@ -7,6 +9,7 @@ use bytes::BytesMut;
 use eyre::Context;
 use eyre::bail;
 use humansize::BINARY;
+use rayon::prelude::*;
 use std::fmt::Debug;
 use std::io::Read;
 use std::ops::Deref;
@@ -42,7 +45,10 @@ impl MftFile {
     // mftf[impl cached-stream.record-size-field]
     // mftf[impl cached-stream.fixed-record-size]
     // mftf[impl cached-stream.fixups-applied-before-iteration]
-    fn validate_and_apply_fixups(raw: &mut [u8]) -> eyre::Result<()> {
+    //
+    // Returns whether the fixup pass ran. It is skipped only when `assume_fixed` is set; the
+    // pass is idempotent, so records that are already fixed up are left unchanged.
+    fn validate_and_apply_fixups(raw: &mut [u8], assume_fixed: bool) -> eyre::Result<bool> {
         {
             let _span = debug_span!("validate_minimum_header_size", raw_len = raw.len()).entered();
             if raw.len() < 0x20 {
@@ -77,13 +83,18 @@ impl MftFile {
             }
         }
 
+        if assume_fixed {
+            debug!("Skipping fixups because the caller asserted they are already applied");
+            return Ok(false);
+        }
+
         {
             let _span =
                 debug_span!("apply_fixups_parallel", entry_size_bytes = entry_size_bytes).entered();
             let _stats = apply_fixups_parallel(raw, entry_size_bytes);
         }
 
-        Ok(())
+        Ok(true)
     }
 
     pub fn size(&self) -> Information {
@@ -115,13 +126,60 @@ impl MftFile {
         self.bytes.len() / entry_size_bytes
     }
 
+    /// Total bytes reserved for records: `record_count() * record_size()`.
+    #[must_use]
+    pub fn allocated_bytes(&self) -> Information {
+        Information::new::<byte>(self.record_count() * self.record_size().get::<byte>())
+    }
+
+    /// Sum of the used-size field (0x18) across in-use `FILE` records.
+    ///
+    /// Records with another signature (e.g. `BAAD` or never-initialized zeros) are skipped.
+    #[must_use]
+    pub fn used_bytes(&self) -> Information {
+        let used = self
+            .bytes
+            .par_chunks_exact(self.record_size().get::<byte>())
+            .map(|record| MftRecord::from_bytes_unchecked(self.bytes.slice_ref(record)))
+            .filter(|record| record.get_signature() == b"FILE" && record.is_in_use())
+            .map(|record| record.get_used_size() as usize)
+            .sum();
+        Information::new::<byte>(used)
+    }
+
     /// Load an MFT file from the given path, checking `cancel` between read chunks.
     ///
+    /// Paths ending in `.zst` are transparently zstd-decompressed into memory.
+    ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be opened, read, parsed, or if cancellation is requested.
     #[instrument(level = "debug")]
     pub fn from_path(mft_file_path: &Path, cancel: &CancellationToken) -> eyre::Result<Self> {
+        Self::load_path(mft_file_path, cancel, false)
+    }
+
+    /// Load an MFT file like [`Self::from_path`] without applying fixups.
+    ///
+    /// Use this only for cached MFTs known to have been saved after fixups were applied;
+    /// otherwise sector tails keep their update sequence numbers and records parse wrongly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, read, parsed, or if cancellation is requested.
+    #[instrument(level = "debug")]
+    pub fn from_path_assume_fixed(
+        mft_file_path: &Path,
+        cancel: &CancellationToken,
+    ) -> eyre::Result<Self> {
+        Self::load_path(mft_file_path, cancel, true)
+    }
+
+    fn load_path(
+        mft_file_path: &Path,
+        cancel: &CancellationToken,
+        assume_fixed: bool,
+    ) -> eyre::Result<Self> {
         let file = {
             let _span = debug_span!("open_file", path = %mft_file_path.display()).entered();
             std::fs::File::open(mft_file_path)
@@ -143,7 +201,8 @@ impl MftFile {
                 .wrap_err("File size too large for usize")?,
             )
         };
-        if mft_file_size < Information::new::<byte>(1024) {
+        let compressed = is_compressed_mft_path(mft_file_path);
+        if !compressed && mft_file_size < Information::new::<byte>(1024) {
             bail!("MFT file too small: {}", mft_file_path.display());
         }
 
@@ -157,11 +216,18 @@ impl MftFile {
             let _span = debug_span!(
                 "read_all_bytes",
                 path = %mft_file_path.display(),
-                file_size_bytes = mft_file_size.get::<byte>()
+                file_size_bytes = mft_file_size.get::<byte>(),
+                compressed,
             )
             .entered();
             let mut buf = Vec::with_capacity(mft_file_size.get::<byte>());
-            let mut reader = std::io::BufReader::new(&file);
+            let mut reader: Box<dyn Read> = if compressed {
+                Box::new(zstd::stream::read::Decoder::new(&file).wrap_err_with(|| {
+                    format!("Failed to start decompressing {}", mft_file_path.display())
+                })?)
+            } else {
+                Box::new(std::io::BufReader::new(&file))
+            };
             let mut chunk = vec![0u8; 1024 * 1024];
             loop {
                 if cancel.is_cancelled() {
@@ -175,12 +241,19 @@ impl MftFile {
                 }
                 buf.extend_from_slice(&chunk[..read]);
             }
-            BytesMut::from(Bytes::from(buf))
+            if compressed && buf.len() < 1024 {
+                bail!("Decompressed MFT too small: {}", mft_file_path.display());
+            }
+            buf
         };
 
         let rtn = {
             let _span = debug_span!("construct_from_bytes").entered();
-            MftFile::from_bytes(bytes)?
+            let mut raw = bytes;
+            Self::validate_and_apply_fixups(&mut raw, assume_fixed)?;
+            MftFile {
+                bytes: Bytes::from(raw),
+            }
         };
 
         // Log summary
@@ -202,7 +275,7 @@ impl MftFile {
     /// Returns an error if the bytes are invalid or fixups fail.
     #[instrument(level = "debug", skip_all)]
     pub fn from_bytes(mut raw: BytesMut) -> eyre::Result<Self> {
-        Self::validate_and_apply_fixups(raw.as_mut())?;
+        Self::validate_and_apply_fixups(raw.as_mut(), false)?;
 
         let bytes = {
             let _span = debug_span!("freeze_bytes").entered();
@@ -222,12 +295,43 @@ impl MftFile {
     /// Returns an error if the bytes are invalid or fixups fail.
     #[instrument(level = "debug", skip_all)]
     pub fn from_vec(mut raw: Vec<u8>) -> eyre::Result<Self> {
-        Self::validate_and_apply_fixups(&mut raw)?;
+        Self::validate_and_apply_fixups(&mut raw, false)?;
         Ok(MftFile {
             bytes: Bytes::from(raw),
         })
     }
 
+    /// The fixed-up record at `index` as a zero-copy view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is not below [`Self::record_count`].
+    pub fn record_at(&self, index: u64) -> eyre::Result<MftRecord> {
+        let record_count = self.record_count();
+        let Some(index) = usize::try_from(index)
+            .ok()
+            .filter(|index| *index < record_count)
+        else {
+            bail!("Record {index} is out of range; this MFT has {record_count} records");
+        };
+        let record_size = self.record_size().get::<byte>();
+        let start = index * record_size;
+        Ok(MftRecord::from_bytes_unchecked(
+            self.bytes.slice(start..start + record_size),
+        ))
+    }
+
+    /// Iterate over the records of this MFT file as validated zero-copy views.
+    ///
+    /// Each item is an error when the slice does not carry a `FILE` signature
+    /// (e.g. `BAAD` or never-initialized records), so callers can skip or report them.
+    pub fn records(&self) -> impl Iterator<Item = eyre::Result<MftRecord>> + '_ {
+        let record_size = self.record_size().get::<byte>();
+        self.bytes
+            .chunks_exact(record_size)
+            .map(|record| MftRecord::from_bytes(self.bytes.slice_ref(record)))
+    }
+
     /// Iterate over fixed-size records contained in this MFT file.
     ///
     /// The logical MFT stream starts directly with record 0 (`FILE`), so there is
@@ -244,3 +348,136 @@ impl MftFile {
         MftRecordIter::new(self.bytes.clone(), self.record_size())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MftFile;
+    use crate::cancellation::CancellationToken;
+    use uom::si::information::byte;
+
+    #[test]
+    fn compressed_mft_round_trips_to_identical_bytes() -> eyre::Result<()> {
+        let mut raw = vec![0u8; 4 * 1024];
+        for record in raw.chunks_exact_mut(1024) {
+            record[..4].copy_from_slice(b"FILE");
+            record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        }
+        let dir = tempfile::tempdir()?;
+        let plain_path = dir.path().join("C.mft");
+        let compressed_path = dir.path().join("C.mft.zst");
+        std::fs::write(&plain_path, &raw)?;
+        std::fs::write(
+            &compressed_path,
+            zstd::stream::encode_all(raw.as_slice(), 0)?,
+        )?;
+        assert!(std::fs::metadata(&compressed_path)?.len() < raw.len() as u64);
+
+        let cancel = CancellationToken::new();
+        let plain = MftFile::from_path(&plain_path, &cancel)?;
+        let decompressed = MftFile::from_path(&compressed_path, &cancel)?;
+        assert_eq!(&decompressed[..], &plain[..]);
+        assert_eq!(decompressed.record_count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn used_and_allocated_bytes_cover_in_use_file_records() -> eyre::Result<()> {
+        let mut raw = vec![0u8; 2 * 1024];
+        for (record, used_size) in raw.chunks_exact_mut(1024).zip([416u32, 352]) {
+            record[..4].copy_from_slice(b"FILE");
+            record[0x16..0x18].copy_from_slice(&1u16.to_le_bytes());
+            record[0x18..0x1C].copy_from_slice(&used_size.to_le_bytes());
+            record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        }
+        let mut with_bad_record = raw.clone();
+        with_bad_record.extend_from_slice(&raw[1024..]);
+        with_bad_record[2048..2052].copy_from_slice(b"BAAD");
+
+        let mft = MftFile::from_vec(with_bad_record)?;
+        assert_eq!(mft.allocated_bytes().get::<byte>(), 3 * 1024);
+        assert_eq!(mft.used_bytes().get::<byte>(), 416 + 352);
+        Ok(())
+    }
+
+    #[test]
+    fn records_reads_record_numbers_and_rejects_bad_signatures() -> eyre::Result<()> {
+        let mut raw = vec![0u8; 3 * 1024];
+        for (record_number, record) in (0u32..).zip(raw.chunks_exact_mut(1024)) {
+            record[..4].copy_from_slice(b"FILE");
+            record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+            record[0x2C..0x30].copy_from_slice(&record_number.to_le_bytes());
+        }
+        raw[1024..1028].copy_from_slice(b"BAAD");
+
+        let mft = MftFile::from_vec(raw)?;
+        let records = mft.records().collect::<Vec<_>>();
+        assert_eq!(records.len(), 3);
+        assert!(records[1].is_err());
+        let record_numbers = records
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(|record| *record.get_record_number())
+            .collect::<Vec<_>>();
+        assert_eq!(record_numbers, vec![0, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn fixup_pass_runs_unless_the_caller_assumes_fixed() -> eyre::Result<()> {
+        let mut raw = vec![0u8; 80 * 1024];
+        for record in raw.chunks_exact_mut(1024) {
+            record[..4].copy_from_slice(b"FILE");
+            record[0x04..0x06].copy_from_slice(&0x30u16.to_le_bytes());
+            record[0x06..0x08].copy_from_slice(&3u16.to_le_bytes());
+            record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+            record[0x30..0x32].copy_from_slice(&[0xAB, 0xCD]);
+            record[0x32..0x34].copy_from_slice(&[0x11, 0x22]);
+            record[0x34..0x36].copy_from_slice(&[0x33, 0x44]);
+            record[510..512].copy_from_slice(&[0xAB, 0xCD]);
+            record[1022..1024].copy_from_slice(&[0xAB, 0xCD]);
+        }
+
+        let mut needs_fixing = raw.clone();
+        assert!(MftFile::validate_and_apply_fixups(
+            &mut needs_fixing,
+            false
+        )?);
+        assert_eq!(&needs_fixing[510..512], &[0x11, 0x22]);
+
+        let mut already_fixed = needs_fixing.clone();
+        assert!(MftFile::validate_and_apply_fixups(
+            &mut already_fixed,
+            false
+        )?);
+        assert_eq!(already_fixed, needs_fixing);
+
+        // Late records still get fixed when every leading record already is.
+        let mut partly_fixed = needs_fixing.clone();
+        partly_fixed[79 * 1024..].copy_from_slice(&raw[79 * 1024..]);
+        MftFile::validate_and_apply_fixups(&mut partly_fixed, false)?;
+        assert_eq!(partly_fixed, needs_fixing);
+
+        let mut assumed = raw.clone();
+        assert!(!MftFile::validate_and_apply_fixups(&mut assumed, true)?);
+        assert_eq!(assumed, raw);
+        Ok(())
+    }
+
+    #[test]
+    fn record_at_returns_the_matching_slice() -> eyre::Result<()> {
+        let mut raw = vec![0u8; 3 * 1024];
+        for (record_number, record) in (0u32..).zip(raw.chunks_exact_mut(1024)) {
+            record[..4].copy_from_slice(b"FILE");
+            record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+            record[0x2C..0x30].copy_from_slice(&record_number.to_le_bytes());
+            record[0x100] = u8::try_from(record_number).unwrap() + 0xA0;
+        }
+
+        let mft = MftFile::from_vec(raw.clone())?;
+        let record = mft.record_at(2)?;
+        assert_eq!(*record.get_record_number(), 2);
+        assert_eq!(&record[..], &raw[2048..3072]);
+        assert!(mft.record_at(3).is_err());
+        Ok(())
+    }
+}
@@ -11,12 +11,7 @@ pub struct MftLocationOnDisk {
 }
 impl From<&NtfsBootSector> for MftLocationOnDisk {
     fn from(value: &NtfsBootSector) -> Self {
-        let ntfs_cluster_size = Information::new::<byte>(value.bytes_per_cluster());
-        Self {
-            offset: usize::try_from(value.mft_cluster_number())
-                .expect("cluster number fits in usize")
-                * ntfs_cluster_size,
-        }
+        Self::from_cluster(value.mft_cluster_number(), value.bytes_per_cluster())
     }
 }
 impl Deref for MftLocationOnDisk {
@@ -26,6 +21,19 @@ impl Deref for MftLocationOnDisk {
     }
 }
 impl MftLocationOnDisk {
+    /// Locate a record table starting at `cluster_number`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cluster_number` cannot be converted to `usize`.
+    #[must_use]
+    pub fn from_cluster(cluster_number: u64, bytes_per_cluster: usize) -> Self {
+        Self {
+            offset: usize::try_from(cluster_number).expect("cluster number fits in usize")
+                * Information::new::<byte>(bytes_per_cluster),
+        }
+    }
+
     /// Compute the on-disk byte location of a given MFT record number.
     /// `bytes_per_record` must be provided explicitly (do not assume cluster size).
     ///
@@ -1,4 +1,8 @@
+use crate::machine::config::is_compressed_mft_path;
+use crate::mft::fast_fixup::FixupState;
+use crate::mft::fast_fixup::apply_fixup_in_place;
 use crate::mft::mft_file::MftFile;
+use crate::mft::mft_location::MftLocationOnDisk;
 use crate::mft::mft_record::MftRecord;
 use crate::mft::mft_record_attribute_run_list::MftRecordAttributeRunListOwned;
 use crate::mft::mft_record_location::MftRecordLocationOnDisk;
@@ -6,34 +10,66 @@ use crate::mft::mft_record_number::MftRecordNumber;
 use crate::mft::mft_record_size::MftRecordSize;
 use crate::ntfs::ntfs_boot_sector::NtfsBootSector;
 use crate::ntfs::ntfs_drive_handle::NtfsDriveHandle;
+use crate::read::logical_read_plan::LogicalFileSegmentKind;
 use crate::read::logical_read_plan::LogicalReadPlan;
+use crate::read::physical_read_plan::PhysicalReadPlan;
 use crate::read::physical_read_results::PhysicalReadResults;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
+use crate::sync::SyncTimings;
 use crate::windows_utils::handle::get_read_only_drive_handle;
+use crate::windows_utils::storage::HandleReadExt;
 use crate::windows_utils::string::EasyPCWSTR;
+use bytes::Bytes;
 use eyre::WrapErr;
 use humansize::BINARY;
 use teamy_uom_extensions::HumanInformationExt;
+use tracing::debug;
 use tracing::info;
 use tracing::info_span;
 use tracing::instrument;
+use tracing::warn;
 use uom::si::information::byte;
-use uom::si::information::mebibyte;
 use uom::si::usize::Information;
 
 #[derive(Debug)]
 pub struct PhysicalMftReadResult {
     pub logical_read_plan: LogicalReadPlan,
     pub physical_read_results: PhysicalReadResults,
+    pub timings: SyncTimings,
 }
 
 impl PhysicalMftReadResult {
     /// # Errors
     ///
-    /// Returns an error if writing to the specified path fails.
+    /// Returns an error if the logical plan has gaps or overlaps, the read results do not cover
+    /// it, or writing to the specified path fails.
+    ///
+    /// Paths ending in `.zst` are written zstd-compressed.
     #[instrument(skip_all)]
     pub fn write_to_path(&self, output_path: impl AsRef<std::path::Path>) -> eyre::Result<()> {
+        self.logical_read_plan.validate()?;
+        if let Err(gaps) = self.physical_read_results.verify(&self.logical_read_plan) {
+            eyre::bail!(
+                "Physical read results are missing {} logical range(s): {gaps:?}",
+                gaps.len()
+            );
+        }
+        debug!(
+            logical_size = %self.logical_read_plan.total_logical_size().format_human(BINARY),
+            "Physical read results fully cover the logical plan"
+        );
+        let output_path = output_path.as_ref();
+        if is_compressed_mft_path(output_path) {
+            let bytes = self.physical_read_results.to_vec(&self.logical_read_plan)?;
+            let file = std::fs::File::create(output_path)
+                .wrap_err_with(|| format!("Failed to create {}", output_path.display()))?;
+            zstd::stream::copy_encode(bytes.as_slice(), file, 0)
+                .wrap_err_with(|| format!("Failed to compress into {}", output_path.display()))?;
+            return Ok(());
+        }
         self.physical_read_results
-            .write_to_path(&self.logical_read_plan, output_path)
+            .write_to_path(&self.logical_read_plan, output_path)?;
+        Ok(())
     }
 
     /// Reconstruct the logical `$MFT` stream in memory and apply fixups.
@@ -54,59 +90,207 @@ impl PhysicalMftReadResult {
 
 /// Read the complete MFT using IOCP overlapped reads.
 /// `drive_letter`: 'C', 'D', ...
-/// `output_path`: file path to write final MFT blob
 ///
 /// # Errors
 ///
 /// Returns an error if the drive cannot be accessed or MFT cannot be read.
 #[instrument]
 pub fn read_physical_mft(drive_letter: char) -> eyre::Result<PhysicalMftReadResult> {
+    read_physical_mft_with_tuning(drive_letter, &PhysicalReadTuning::default())
+}
+
+/// Read the complete MFT like [`read_physical_mft`] with a custom chunk size and queue depth.
+///
+/// # Errors
+///
+/// Returns an error if the drive cannot be accessed or MFT cannot be read.
+#[instrument]
+pub fn read_physical_mft_with_tuning(
+    drive_letter: char,
+    tuning: &PhysicalReadTuning,
+) -> eyre::Result<PhysicalMftReadResult> {
+    read_physical_stream(drive_letter, MftRecordNumber::DOLLAR_MFT, None, tuning)
+}
+
+/// Read a non-resident `$DATA` stream of an MFT record using IOCP overlapped reads.
+///
+/// `stream_name` selects a named stream; `None` selects the unnamed default stream.
+/// The record is located through the `$MFT`'s own data runs, so it may live in any extent.
+///
+/// # Errors
+///
+/// Returns an error if the drive cannot be accessed, the record cannot be read,
+/// or the selected stream has no non-resident runs.
+#[instrument]
+pub fn read_physical_stream(
+    drive_letter: char,
+    record_number: MftRecordNumber,
+    stream_name: Option<&str>,
+    tuning: &PhysicalReadTuning,
+) -> eyre::Result<PhysicalMftReadResult> {
     let drive_letter = drive_letter.to_ascii_uppercase();
     let volume_path = format!(r"\\.\{drive_letter}:");
     let volume_path = volume_path
         .easy_pcwstr()
         .wrap_err("Failed to convert volume path to PCWSTR")?;
 
-    // Open blocking handle for boot sector & MFT record parsing
-    let drive_handle: NtfsDriveHandle = {
-        let _span = info_span!("open_ntfs_drive_handle", drive = %drive_letter).entered();
-        get_read_only_drive_handle(drive_letter)
-            .wrap_err("Failed to open handle to drive")?
-            .try_into()
-            .wrap_err("Failed to convert drive handle to NtfsDriveHandle")?
-    };
+    let mut timings = SyncTimings::default();
+    let logical_read_plan = plan_physical_stream_timed(
+        drive_letter,
+        record_number,
+        stream_name,
+        tuning.max_entries,
+        &mut timings,
+    )?;
 
-    let boot_sector = {
-        let _span = info_span!("read_ntfs_boot_sector", drive = %drive_letter).entered();
-        NtfsBootSector::try_from_handle(&drive_handle)?
+    let plan = {
+        let _span = info_span!(
+            "build_physical_mft_read_plan",
+            drive = %drive_letter,
+            logical_segments = logical_read_plan.segments.len(),
+            chunk_size_bytes = tuning.chunk_size.get::<byte>(),
+        )
+        .entered();
+        build_physical_read_plan(&logical_read_plan, tuning.chunk_size)
     };
-    let mft_record_size = MftRecordSize::new(boot_sector.file_record_size())?;
-    let dollar_mft_record = {
+    let physical_read_results: PhysicalReadResults = {
         let _span = info_span!(
-            "read_dollar_mft_record",
+            "execute_physical_mft_read_plan",
             drive = %drive_letter,
-            record_size_bytes = mft_record_size.get::<byte>(),
+            physical_requests = plan.len(),
+            total_physical_bytes = plan.total_size().get::<byte>(),
         )
         .entered();
-        MftRecord::try_from_handle(
-            &drive_handle,
-            MftRecordLocationOnDisk::from_record_number(
-                &boot_sector.mft_location(),
-                MftRecordNumber::DOLLAR_MFT,
-                boot_sector.file_record_size(),
-            ),
-            mft_record_size,
+        SyncTimings::measure(&mut timings.physical_read, || {
+            plan.read_with_queue_depth(&volume_path, tuning.queue_depth)
+        })?
+    };
+
+    info!(
+        "Completed read of record {record_number} from drive {drive_letter} - read {} physical segments totalling {}",
+        physical_read_results.entries.len(),
+        physical_read_results
+            .entries
+            .iter()
+            .map(|e| e.request.length)
+            .sum::<Information>()
+            .format_human(BINARY),
+    );
+    Ok(PhysicalMftReadResult {
+        logical_read_plan,
+        physical_read_results,
+        timings,
+    })
+}
+
+/// Derive the physical reads that execute `logical_read_plan`: 512-byte aligned, merged where
+/// contiguous, and split into requests of at most `chunk_size`
+/// (see [`PhysicalReadTuning::default`] for the 1 MiB default).
+#[must_use]
+pub fn build_physical_read_plan(
+    logical_read_plan: &LogicalReadPlan,
+    chunk_size: Information,
+) -> PhysicalReadPlan {
+    let mut physical_read_plan = logical_read_plan.as_physical_read_plan();
+    physical_read_plan.align_512().merge_contiguous_reads();
+    physical_read_plan.chunked(chunk_size)
+}
+
+/// Build the sparse-aware logical read plan for a non-resident `$DATA` stream of an MFT record
+/// without reading the stream contents.
+///
+/// With `max_entries`, the plan covers only the first `max_entries` file records' worth of
+/// logical bytes, which is enough for smoke tests that do not need the whole `$MFT`.
+///
+/// # Errors
+///
+/// Returns an error if the drive cannot be accessed, the record cannot be read,
+/// or the selected stream has no non-resident runs.
+#[instrument]
+pub fn plan_physical_stream(
+    drive_letter: char,
+    record_number: MftRecordNumber,
+    stream_name: Option<&str>,
+    max_entries: Option<usize>,
+) -> eyre::Result<LogicalReadPlan> {
+    plan_physical_stream_timed(
+        drive_letter,
+        record_number,
+        stream_name,
+        max_entries,
+        &mut SyncTimings::default(),
+    )
+}
+
+fn plan_physical_stream_timed(
+    drive_letter: char,
+    record_number: MftRecordNumber,
+    stream_name: Option<&str>,
+    max_entries: Option<usize>,
+    timings: &mut SyncTimings,
+) -> eyre::Result<LogicalReadPlan> {
+    let drive_letter = drive_letter.to_ascii_uppercase();
+    let (drive_handle, boot_sector) = SyncTimings::measure(
+        &mut timings.boot_sector_read,
+        || -> eyre::Result<(NtfsDriveHandle, NtfsBootSector)> {
+            // Open blocking handle for boot sector & MFT record parsing
+            let drive_handle: NtfsDriveHandle = {
+                let _span = info_span!("open_ntfs_drive_handle", drive = %drive_letter).entered();
+                get_read_only_drive_handle(drive_letter)
+                    .wrap_err("Failed to open handle to drive")?
+                    .try_into()
+                    .wrap_err("Failed to convert drive handle to NtfsDriveHandle")?
+            };
+
+            let boot_sector = {
+                let _span = info_span!("read_ntfs_boot_sector", drive = %drive_letter).entered();
+                NtfsBootSector::try_from_handle(&drive_handle)?
+            };
+            Ok((drive_handle, boot_sector))
+        },
+    )?;
+    SyncTimings::measure(&mut timings.record_parse, || {
+        plan_from_boot_sector(
+            drive_letter,
+            record_number,
+            stream_name,
+            max_entries,
+            drive_handle,
+            &boot_sector,
         )
-        .wrap_err("Failed reading record")?
+    })
+}
+
+fn plan_from_boot_sector(
+    drive_letter: char,
+    record_number: MftRecordNumber,
+    stream_name: Option<&str>,
+    max_entries: Option<usize>,
+    drive_handle: impl HandleReadExt,
+    boot_sector: &NtfsBootSector,
+) -> eyre::Result<LogicalReadPlan> {
+    let mft_record = {
+        let _span = info_span!(
+            "read_stream_mft_record",
+            drive = %drive_letter,
+            record_number = %record_number,
+            record_size_bytes = boot_sector.file_record_size().get::<byte>(),
+        )
+        .entered();
+        read_record_with_mirror_fallback(&drive_handle, boot_sector, record_number, drive_letter)
+            .wrap_err("Failed reading record")?
     };
 
-    // Gather all non-resident $DATA runlists (could be multiple segments if attribute list used).
+    // Gather all non-resident $DATA runlists for the stream (could be multiple segments if attribute list used).
     let decoded_runs = {
-        let _span = info_span!("decode_dollar_mft_runlists", drive = %drive_letter).entered();
-        MftRecordAttributeRunListOwned::from_mft_record(&dollar_mft_record)
+        let _span = info_span!("decode_stream_runlists", drive = %drive_letter).entered();
+        MftRecordAttributeRunListOwned::from_mft_record_stream(&mft_record, stream_name)
     };
     if decoded_runs.is_empty() {
-        eyre::bail!("No non-resident $DATA runs found in $MFT record");
+        eyre::bail!(
+            "No non-resident $DATA runs found for stream {:?} in record {record_number}",
+            stream_name.unwrap_or_default()
+        );
     }
     drop(drive_handle);
 
@@ -125,45 +309,129 @@ pub fn read_physical_mft(drive_letter: char) -> eyre::Result<PhysicalMftReadResu
         eyre::bail!("Logical plan empty (no runs)");
     }
 
-    // Derive physical read plan, merge, chunk and execute with 1 MiB (binary) chunk size (1,048,576 = 1024*1024) for sector alignment
-    let chunk_size = Information::new::<mebibyte>(1);
-    let plan = {
-        let _span = info_span!(
-            "build_physical_mft_read_plan",
-            drive = %drive_letter,
-            logical_segments = logical_read_plan.segments.len(),
-            chunk_size_bytes = chunk_size.get::<byte>(),
-        )
-        .entered();
-        let mut physical_read_plan = logical_read_plan.as_physical_read_plan();
-        physical_read_plan.align_512().merge_contiguous_reads();
-        physical_read_plan.chunked(chunk_size)
+    let Some(max_entries) = max_entries else {
+        return Ok(logical_read_plan);
     };
-    let physical_read_results: PhysicalReadResults = {
-        let _span = info_span!(
-            "execute_physical_mft_read_plan",
-            drive = %drive_letter,
-            physical_requests = plan.len(),
-            total_physical_bytes = plan.total_size().get::<byte>(),
-        )
-        .entered();
-        plan.read(&volume_path)?
+    let max_logical_size = boot_sector.file_record_size() * max_entries;
+    debug!(
+        drive = %drive_letter,
+        max_entries,
+        full_logical_size = %logical_read_plan.total_logical_size().format_human(BINARY),
+        "Truncating logical read plan to the first {max_entries} records"
+    );
+    Ok(logical_read_plan.truncated(max_logical_size))
+}
+
+/// Read `record_number` of the `$MFT`, apply its fixups and check its header.
+///
+/// Record 0 is read where the boot sector places the `$MFT`; every other record is located
+/// by mapping its logical offset through record 0's data runs, so records past the first
+/// extent of a fragmented `$MFT` come from the right clusters.
+///
+/// `$MFTMirr` holds copies of the first four `$MFT` records, so when one of those cannot be
+/// read or has invalid fixups the mirror copy is used instead.
+fn read_record_with_mirror_fallback(
+    drive_handle: &impl HandleReadExt,
+    boot_sector: &NtfsBootSector,
+    record_number: MftRecordNumber,
+    drive_letter: char,
+) -> eyre::Result<MftRecord> {
+    let record_size = boot_sector.file_record_size();
+    let mft_record_size = MftRecordSize::new(record_size)?;
+    let read_at = |mft_location: &MftLocationOnDisk| -> eyre::Result<MftRecord> {
+        let record = MftRecord::try_from_handle(
+            drive_handle,
+            MftRecordLocationOnDisk::from_record_number(mft_location, record_number, record_size),
+            mft_record_size,
+        )?;
+        verified_record(record.to_vec(), record_number)
     };
 
-    info!(
-        "Completed MFT read from drive {drive_letter} - read {} physical segments totalling {}",
-        physical_read_results.entries.len(),
-        physical_read_results
-            .entries
-            .iter()
-            .map(|e| e.request.length)
-            .sum::<Information>()
-            .format_human(BINARY),
-    );
-    Ok(PhysicalMftReadResult {
-        logical_read_plan,
-        physical_read_results,
-    })
+    let primary = if record_number == MftRecordNumber::DOLLAR_MFT {
+        read_at(&boot_sector.mft_location())
+    } else {
+        read_record_through_mft_runs(drive_handle, boot_sector, record_number, drive_letter)
+    };
+    match primary {
+        Ok(record) => Ok(record),
+        Err(primary_error) if record_number <= MftRecordNumber::DOLLAR_VOLUME => {
+            warn!(
+                drive = %drive_letter,
+                record_number = %record_number,
+                error = %primary_error,
+                "Primary $MFT record is unreadable; falling back to the $MFTMirr copy"
+            );
+            read_at(&boot_sector.mft_mirror_location()).wrap_err_with(|| {
+                format!("$MFTMirr copy is also unreadable (primary record: {primary_error})")
+            })
+        }
+        Err(primary_error) => Err(primary_error),
+    }
+}
+
+/// Read `record_number` from wherever the `$MFT`'s unnamed `$DATA` runs place it, following
+/// a record that straddles two runs across both.
+fn read_record_through_mft_runs(
+    drive_handle: &impl HandleReadExt,
+    boot_sector: &NtfsBootSector,
+    record_number: MftRecordNumber,
+    drive_letter: char,
+) -> eyre::Result<MftRecord> {
+    let dollar_mft = read_record_with_mirror_fallback(
+        drive_handle,
+        boot_sector,
+        MftRecordNumber::DOLLAR_MFT,
+        drive_letter,
+    )?;
+    let mft_plan = MftRecordAttributeRunListOwned::from_mft_record(&dollar_mft)
+        .into_logical_read_plan(Information::new::<byte>(boot_sector.bytes_per_cluster()));
+    let record_size = boot_sector.file_record_size().get::<byte>();
+    let record_start = usize::try_from(*record_number)? * record_size;
+
+    let mut bytes = vec![0u8; record_size];
+    let mut filled = 0;
+    while filled < record_size {
+        let position = record_start + filled;
+        let Some((segment_start, segment_end, physical_offset)) =
+            mft_plan.segments.iter().find_map(|segment| {
+                let start = segment.logical_offset.get::<byte>();
+                let end = start + segment.length.get::<byte>();
+                match segment.kind {
+                    LogicalFileSegmentKind::Physical { physical_offset }
+                        if (start..end).contains(&position) =>
+                    {
+                        Some((start, end, physical_offset.get::<byte>()))
+                    }
+                    _ => None,
+                }
+            })
+        else {
+            eyre::bail!("Record {record_number} lies outside the allocated $MFT data runs");
+        };
+        let length = (record_size - filled).min(segment_end - position);
+        drive_handle.try_read_exact(
+            i64::try_from(physical_offset + (position - segment_start))?,
+            &mut bytes[filled..filled + length],
+        )?;
+        filled += length;
+    }
+    verified_record(bytes, record_number)
+}
+
+/// Apply fixups to a freshly read record and check that it is a `FILE` record carrying
+/// `record_number` in its header.
+fn verified_record(mut bytes: Vec<u8>, record_number: MftRecordNumber) -> eyre::Result<MftRecord> {
+    if apply_fixup_in_place(&mut bytes) == FixupState::Invalid {
+        eyre::bail!("Record {record_number} has invalid update sequence fixups");
+    }
+    let record = MftRecord::from_bytes(Bytes::from(bytes))?;
+    if record.get_record_number() != record_number {
+        eyre::bail!(
+            "Expected record {record_number} but its header says {}",
+            record.get_record_number()
+        );
+    }
+    Ok(record)
 }
 
 #[cfg(test)]
@@ -179,4 +447,210 @@ mod test {
             Information::new::<mebibyte>(1).get::<byte>()
         );
     }
+
+    #[test]
+    fn custom_chunk_size_covers_the_same_bytes() -> eyre::Result<()> {
+        use super::build_physical_read_plan;
+        use crate::read::logical_read_plan::LogicalFileSegment;
+        use crate::read::logical_read_plan::LogicalFileSegmentKind;
+        use crate::read::logical_read_plan::LogicalReadPlan;
+        use crate::read::physical_read_tuning::PhysicalReadTuning;
+
+        let logical_read_plan = LogicalReadPlan {
+            segments: [(0, 3000, 8192), (3072, 1024, 65536)]
+                .into_iter()
+                .map(|(logical, length, physical)| LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(logical),
+                    length: Information::new::<byte>(length),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(physical),
+                    },
+                })
+                .collect(),
+        };
+        let tuning = PhysicalReadTuning::new(Some(1024), Some(4))?;
+        assert_eq!(tuning.queue_depth, Some(4));
+
+        let default_plan =
+            build_physical_read_plan(&logical_read_plan, PhysicalReadTuning::default().chunk_size);
+        let custom_plan = build_physical_read_plan(&logical_read_plan, tuning.chunk_size);
+
+        assert_eq!(default_plan.len(), 2);
+        assert_eq!(custom_plan.len(), 4);
+        assert_eq!(custom_plan.total_size(), default_plan.total_size());
+        assert!(
+            custom_plan
+                .into_iter()
+                .all(|request| request.length <= tuning.chunk_size)
+        );
+        assert!(PhysicalReadTuning::new(Some(0), None).is_err());
+        assert!(PhysicalReadTuning::new(None, Some(0)).is_err());
+        Ok(())
+    }
+
+    struct MemoryVolume(Vec<u8>);
+
+    impl crate::windows_utils::storage::HandleReadExt for MemoryVolume {
+        fn try_read_exact(&self, offset: i64, buf: &mut [u8]) -> eyre::Result<()> {
+            let start = usize::try_from(offset)?;
+            let source = self
+                .0
+                .get(start..start + buf.len())
+                .ok_or_else(|| eyre::eyre!("read past end of volume"))?;
+            buf.copy_from_slice(source);
+            Ok(())
+        }
+    }
+
+    /// A 1 KiB `$MFT` record whose unnamed `$DATA` runs cover 4 clusters at LCN 16.
+    fn dollar_mft_record() -> Vec<u8> {
+        dollar_mft_record_with_runs(&[0x11, 0x04, 0x10])
+    }
+
+    /// A 1 KiB `$MFT` record whose unnamed `$DATA` attribute holds the encoded `runs`.
+    fn dollar_mft_record_with_runs(runs: &[u8]) -> Vec<u8> {
+        use crate::mft::mft_record_attribute::MftRecordAttribute;
+
+        let mut attribute = vec![0u8; 0x48];
+        attribute[0..4].copy_from_slice(&MftRecordAttribute::TYPE_DOLLAR_DATA.to_le_bytes());
+        attribute[4..8].copy_from_slice(&0x48u32.to_le_bytes());
+        attribute[8] = 1;
+        attribute[10..12].copy_from_slice(&0x40u16.to_le_bytes());
+        attribute[0x20..0x22].copy_from_slice(&0x40u16.to_le_bytes());
+        attribute[0x40..0x40 + runs.len()].copy_from_slice(runs);
+
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x14..0x16].copy_from_slice(&0x38u16.to_le_bytes());
+        record[0x38..0x38 + attribute.len()].copy_from_slice(&attribute);
+        let end = 0x38 + attribute.len();
+        record[end..end + 4].copy_from_slice(&MftRecordAttribute::TYPE_END.to_le_bytes());
+        record[0x18..0x1C].copy_from_slice(&u32::try_from(end + 8).unwrap().to_le_bytes());
+        record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        record
+    }
+
+    /// 512-byte clusters and 1 KiB records, with `$MFT` at cluster 8 and `$MFTMirr` at 16.
+    fn boot_sector() -> crate::ntfs::ntfs_boot_sector::NtfsBootSector {
+        let mut boot_sector = crate::ntfs::ntfs_boot_sector::NtfsBootSector { data: [0u8; 512] };
+        boot_sector.data[0x0b..0x0d].copy_from_slice(&512u16.to_le_bytes());
+        boot_sector.data[0x0d] = 1;
+        boot_sector.data[0x30..0x38].copy_from_slice(&8u64.to_le_bytes());
+        boot_sector.data[0x38..0x40].copy_from_slice(&16u64.to_le_bytes());
+        boot_sector.data[0x40] = (-10i8).to_le_bytes()[0];
+        boot_sector
+    }
+
+    #[test]
+    fn corrupt_record_zero_falls_back_to_mft_mirror() -> eyre::Result<()> {
+        use super::plan_from_boot_sector;
+        use crate::mft::mft_record_number::MftRecordNumber;
+
+        // $MFT at cluster 8 is left zeroed, so record 0 comes from $MFTMirr at cluster 16.
+        let boot_sector = boot_sector();
+        let mut volume = vec![0u8; 32 * 512];
+        volume[16 * 512..16 * 512 + 1024].copy_from_slice(&dollar_mft_record());
+
+        let plan = plan_from_boot_sector(
+            'C',
+            MftRecordNumber::DOLLAR_MFT,
+            None,
+            None,
+            MemoryVolume(volume.clone()),
+            &boot_sector,
+        )?;
+        assert_eq!(plan.total_logical_size().get::<byte>(), 4 * 512);
+
+        // Records beyond the mirrored four have no fallback.
+        assert!(
+            plan_from_boot_sector(
+                'C',
+                MftRecordNumber::MFT_ROOT,
+                None,
+                None,
+                MemoryVolume(volume),
+                &boot_sector,
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+
+    fn numbered_record(record_number: u32) -> Vec<u8> {
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        record[0x2C..0x30].copy_from_slice(&record_number.to_le_bytes());
+        record
+    }
+
+    #[test]
+    fn records_past_the_first_extent_follow_the_mft_data_runs() -> eyre::Result<()> {
+        use super::read_record_with_mirror_fallback;
+        use crate::mft::mft_record_number::MftRecordNumber;
+
+        // Records 0-2 live in 6 clusters at LCN 8 and records 3-5 in 6 clusters at LCN 40.
+        let boot_sector = boot_sector();
+        let mut volume = vec![0u8; 48 * 512];
+        volume[8 * 512..8 * 512 + 1024].copy_from_slice(&dollar_mft_record_with_runs(&[
+            0x11, 0x06, 0x08, 0x11, 0x06, 0x20,
+        ]));
+        // Where a contiguous `$MFT` would put record 5: a different record.
+        volume[8 * 512 + 5 * 1024..8 * 512 + 6 * 1024].copy_from_slice(&numbered_record(99));
+        volume[40 * 512 + 2 * 1024..40 * 512 + 3 * 1024].copy_from_slice(&numbered_record(5));
+
+        let record = read_record_with_mirror_fallback(
+            &MemoryVolume(volume.clone()),
+            &boot_sector,
+            MftRecordNumber::MFT_ROOT,
+            'C',
+        )?;
+        assert_eq!(record.get_record_number(), MftRecordNumber::MFT_ROOT);
+
+        // A record whose header names a different record is rejected.
+        volume[40 * 512 + 2 * 1024..40 * 512 + 3 * 1024].copy_from_slice(&numbered_record(6));
+        let error = read_record_with_mirror_fallback(
+            &MemoryVolume(volume),
+            &boot_sector,
+            MftRecordNumber::MFT_ROOT,
+            'C',
+        )
+        .expect_err("a mismatched record number must not be returned");
+        assert!(error.to_string().contains("header says 6"));
+        Ok(())
+    }
+
+    #[test]
+    fn max_entries_truncates_the_plan_to_whole_records() -> eyre::Result<()> {
+        use super::plan_from_boot_sector;
+        use crate::mft::mft_record_number::MftRecordNumber;
+
+        // $MFT at cluster 8 with 4 clusters (2 records) of data.
+        let boot_sector = boot_sector();
+        let mut volume = vec![0u8; 32 * 512];
+        volume[8 * 512..8 * 512 + 1024].copy_from_slice(&dollar_mft_record());
+
+        let entry_size = boot_sector.file_record_size();
+        let plan = plan_from_boot_sector(
+            'C',
+            MftRecordNumber::DOLLAR_MFT,
+            None,
+            Some(1),
+            MemoryVolume(volume.clone()),
+            &boot_sector,
+        )?;
+        assert_eq!(plan.total_logical_size(), entry_size);
+        plan.validate()?;
+
+        let untruncated = plan_from_boot_sector(
+            'C',
+            MftRecordNumber::DOLLAR_MFT,
+            None,
+            Some(100),
+            MemoryVolume(volume),
+            &boot_sector,
+        )?;
+        assert_eq!(untruncated.total_logical_size(), entry_size * 2);
+        Ok(())
+    }
 }
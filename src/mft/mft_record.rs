@@ -1,4 +1,10 @@
+use crate::mft::fast_entry::ATTR_TYPE_FILE_NAME;
+use crate::mft::mft_record_attribute::MftRecordAttribute;
 use crate::mft::mft_record_attribute_iter::MftRecordAttributeIter;
+use crate::mft::mft_record_attribute_x10_standard_information::ATTR_TYPE_STANDARD_INFORMATION;
+use crate::mft::mft_record_attribute_x10_standard_information::StdInfo;
+use crate::mft::mft_record_attribute_x30_file_name::FileNameInfo;
+use crate::mft::mft_record_attribute_x80_data_attribute::DataSizes;
 use crate::mft::mft_record_flags::MftRecordFlags;
 use crate::mft::mft_record_location::MftRecordLocationOnDisk;
 use crate::mft::mft_record_number::MftRecordNumber;
@@ -35,6 +41,29 @@ impl std::fmt::Debug for MftRecord {
 }
 
 impl MftRecord {
+    /// Construct a record from an in-memory slice holding exactly one record with fixups applied.
+    /// Validates the "FILE" signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slice is shorter than a record header or the signature is not `FILE`.
+    pub fn from_bytes(bytes: Bytes) -> eyre::Result<Self> {
+        if bytes.len() < Self::OFFSET_FOR_RECORD_NUMBER + 4 {
+            bail!(
+                "MFT record too small: {} bytes, need at least {}",
+                bytes.len(),
+                Self::OFFSET_FOR_RECORD_NUMBER + 4
+            );
+        }
+        if &bytes[0..4] != b"FILE" {
+            bail!(
+                "Invalid MFT record signature: expected 'FILE', got {:?}",
+                String::from_utf8_lossy(&bytes[0..4])
+            );
+        }
+        Ok(Self { data: bytes })
+    }
+
     /// Construct a record without validating the signature.
     ///
     /// Use this when the caller already ensured the slice is a single MFT record
@@ -236,4 +265,119 @@ impl MftRecord {
     pub fn iter_attributes(&self) -> MftRecordAttributeIter<'_> {
         MftRecordAttributeIter::new(self)
     }
+
+    /// Parse the first resident `$FILE_NAME` (0x30) attribute, if any.
+    #[must_use]
+    pub fn first_file_name(&self) -> Option<FileNameInfo> {
+        self.iter_attributes()
+            .filter(|attribute| attribute.get_attr_type() == ATTR_TYPE_FILE_NAME)
+            .find_map(|attribute| {
+                attribute
+                    .get_resident_content()
+                    .and_then(FileNameInfo::parse)
+            })
+    }
+
+    /// Parse the resident `$STANDARD_INFORMATION` (0x10) attribute, if present.
+    #[must_use]
+    pub fn standard_info(&self) -> Option<StdInfo> {
+        self.iter_attributes()
+            .filter(|attribute| attribute.get_attr_type() == ATTR_TYPE_STANDARD_INFORMATION)
+            .find_map(|attribute| attribute.get_resident_content().and_then(StdInfo::parse))
+    }
+
+    /// Sizes of the unnamed `$DATA` stream, if this record holds its first extent.
+    ///
+    /// Prefer [`DataSizes::logical`] over the `$FILE_NAME` real size, which NTFS only refreshes
+    /// when the name is updated and which does not distinguish compressed streams.
+    #[must_use]
+    pub fn data_sizes(&self) -> Option<DataSizes> {
+        self.iter_attributes()
+            .filter(|attribute| {
+                attribute.get_attr_type() == MftRecordAttribute::TYPE_DOLLAR_DATA
+                    && attribute.get_name_len() == 0
+            })
+            .filter(|attribute| {
+                attribute
+                    .get_non_resident_header()
+                    .is_none_or(|header| header.starting_vcn() == 0)
+            })
+            .find_map(|attribute| attribute.as_x80().and_then(|data| data.sizes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MftRecord;
+    use bytes::Bytes;
+
+    fn resident_attribute(attr_type: u32, content: &[u8]) -> Vec<u8> {
+        let len = (0x18 + content.len()).next_multiple_of(8);
+        let mut attribute = vec![0u8; len];
+        attribute[0..4].copy_from_slice(&attr_type.to_le_bytes());
+        attribute[4..8].copy_from_slice(&u32::try_from(len).unwrap().to_le_bytes());
+        attribute[0x10..0x14].copy_from_slice(&u32::try_from(content.len()).unwrap().to_le_bytes());
+        attribute[0x14..0x16].copy_from_slice(&0x18u16.to_le_bytes());
+        attribute[0x18..0x18 + content.len()].copy_from_slice(content);
+        attribute
+    }
+
+    #[test]
+    fn parses_standard_information_and_file_name() -> eyre::Result<()> {
+        let mut standard_information = vec![0u8; 0x48];
+        for (index, timestamp) in [11u64, 22, 33, 44].into_iter().enumerate() {
+            standard_information[index * 8..index * 8 + 8]
+                .copy_from_slice(&timestamp.to_le_bytes());
+        }
+        standard_information[0x20..0x24].copy_from_slice(&0x20u32.to_le_bytes());
+
+        let name = "notes.txt".encode_utf16().collect::<Vec<_>>();
+        let mut file_name = vec![0u8; 0x42 + name.len() * 2];
+        file_name[0..8].copy_from_slice(&((3u64 << 48) | 5).to_le_bytes());
+        file_name[0x08..0x10].copy_from_slice(&55u64.to_le_bytes());
+        file_name[0x30..0x38].copy_from_slice(&1234u64.to_le_bytes());
+        file_name[0x38..0x3C].copy_from_slice(&0x20u32.to_le_bytes());
+        file_name[0x40] = u8::try_from(name.len()).unwrap();
+        file_name[0x41] = 1;
+        for (index, unit) in name.iter().enumerate() {
+            file_name[0x42 + index * 2..0x44 + index * 2].copy_from_slice(&unit.to_le_bytes());
+        }
+
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x14..0x16].copy_from_slice(&0x38u16.to_le_bytes());
+        let mut offset = 0x38;
+        for attribute in [
+            resident_attribute(0x10, &standard_information),
+            resident_attribute(0x30, &file_name),
+        ] {
+            record[offset..offset + attribute.len()].copy_from_slice(&attribute);
+            offset += attribute.len();
+        }
+        record[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        record[0x18..0x1C].copy_from_slice(&u32::try_from(offset + 8).unwrap().to_le_bytes());
+        record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+
+        let record = MftRecord::from_bytes(Bytes::from(record))?;
+        let standard_info = record.standard_info().expect("standard information");
+        assert_eq!(
+            (
+                standard_info.created,
+                standard_info.modified,
+                standard_info.mft_modified,
+                standard_info.accessed
+            ),
+            (11, 22, 33, 44)
+        );
+        assert_eq!(standard_info.file_attributes, 0x20);
+
+        let file_name = record.first_file_name().expect("file name");
+        assert_eq!(file_name.name, "notes.txt");
+        assert_eq!(file_name.parent_ref & 0xFFFF_FFFF_FFFF, 5);
+        assert_eq!(file_name.namespace, 1);
+        assert_eq!(file_name.created, 55);
+        assert_eq!(file_name.real_size, 1234);
+        assert_eq!(file_name.flags, 0x20);
+        Ok(())
+    }
 }
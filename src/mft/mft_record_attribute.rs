@@ -69,6 +69,26 @@ impl<'a> MftRecordAttribute<'a> {
         u16::from_le_bytes(self.mft_record_attribute_data[10..12].try_into().unwrap())
     }
 
+    /// Decode the attribute name (e.g. the stream name of a named `$DATA` attribute).
+    ///
+    /// Returns `None` for unnamed attributes or when the name lies outside the attribute slice.
+    #[must_use]
+    pub fn get_name(&self) -> Option<String> {
+        let name_len = self.get_name_len() as usize;
+        if name_len == 0 {
+            return None;
+        }
+        let name_offset = self.get_name_offset() as usize;
+        let raw = self
+            .mft_record_attribute_data
+            .get(name_offset..name_offset + name_len * 2)?;
+        let units: Vec<u16> = raw
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        Some(String::from_utf16_lossy(&units))
+    }
+
     /// # Panics
     ///
     /// Panics if the attribute data is too short.
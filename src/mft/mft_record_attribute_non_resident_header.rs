@@ -69,6 +69,15 @@ impl<'a> MftRecordAttributeNonResidentHeader<'a> {
     pub fn initialized_size(&self) -> u64 {
         u64::from_le_bytes(self[0x38..0x40].try_into().unwrap())
     }
+    /// Bytes actually stored on disk. Only compressed and sparse attributes carry this field;
+    /// for other attributes these bytes belong to the run list. `None` when out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn compressed_size(&self) -> Option<u64> {
+        self.get(0x40..0x48)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+    }
     /// # Errors
     ///
     /// Returns an error if the runlist offset is out of bounds.
@@ -129,10 +129,22 @@ impl MftRecordAttributeRunListOwned {
     /// Extract the data runs from the unnamed x80 attribute
     #[instrument(skip_all)]
     pub fn from_mft_record(dollar_mft_record: &MftRecord) -> Self {
+        Self::from_mft_record_stream(dollar_mft_record, None)
+    }
+
+    /// Extract the data runs from every x80 attribute whose name matches `stream_name`.
+    ///
+    /// `None` selects the unnamed (default) stream. Multiple matching attribute
+    /// instances are concatenated in record order, which is how an extended stream
+    /// is split across attribute extents.
+    #[instrument(skip_all)]
+    pub fn from_mft_record_stream(mft_record: &MftRecord, stream_name: Option<&str>) -> Self {
         let mut rtn = Self::default();
-        for attr in dollar_mft_record.iter_attributes() {
+        for attr in mft_record.iter_attributes() {
             if let Some(x80) = attr.as_x80() {
-                // todo: ensure that the attribute has no name
+                if x80.get_name().as_deref() != stream_name {
+                    continue;
+                }
                 if let Ok(runlist) = x80.get_data_run_list() {
                     for run_res in &runlist {
                         match run_res {
@@ -198,3 +210,128 @@ impl DerefMut for MftRecordAttributeRunListOwned {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mft::mft_record_attribute::MftRecordAttribute;
+    use bytes::Bytes;
+    use uom::si::information::byte;
+
+    fn non_resident_data_attribute(name: &str, runlist: &[u8]) -> Vec<u8> {
+        let name_utf16: Vec<u16> = name.encode_utf16().collect();
+        let name_offset = 0x40usize;
+        let runlist_offset = (name_offset + name_utf16.len() * 2).next_multiple_of(8);
+        // Trailing zero byte terminates the run list.
+        let length = (runlist_offset + runlist.len() + 1).next_multiple_of(8);
+        let mut attribute = vec![0u8; length];
+        attribute[0..4].copy_from_slice(&MftRecordAttribute::TYPE_DOLLAR_DATA.to_le_bytes());
+        attribute[4..8].copy_from_slice(&u32::try_from(length).unwrap().to_le_bytes());
+        attribute[8] = 1;
+        attribute[9] = u8::try_from(name_utf16.len()).unwrap();
+        attribute[10..12].copy_from_slice(&u16::try_from(name_offset).unwrap().to_le_bytes());
+        attribute[0x20..0x22]
+            .copy_from_slice(&u16::try_from(runlist_offset).unwrap().to_le_bytes());
+        for (i, unit) in name_utf16.iter().enumerate() {
+            attribute[name_offset + i * 2..name_offset + i * 2 + 2]
+                .copy_from_slice(&unit.to_le_bytes());
+        }
+        attribute[runlist_offset..runlist_offset + runlist.len()].copy_from_slice(runlist);
+        attribute
+    }
+
+    fn record_with_attributes(attributes: &[Vec<u8>]) -> MftRecord {
+        let mut record = vec![0u8; 1024];
+        record[0..4].copy_from_slice(b"FILE");
+        record[0x14..0x16].copy_from_slice(&0x38u16.to_le_bytes());
+        let mut position = 0x38;
+        for attribute in attributes {
+            record[position..position + attribute.len()].copy_from_slice(attribute);
+            position += attribute.len();
+        }
+        record[position..position + 4].copy_from_slice(&MftRecordAttribute::TYPE_END.to_le_bytes());
+        let used_size = u32::try_from(position + 8).unwrap();
+        record[0x18..0x1C].copy_from_slice(&used_size.to_le_bytes());
+        record[0x1C..0x20].copy_from_slice(&1024u32.to_le_bytes());
+        MftRecord::from_bytes_unchecked(Bytes::from(record))
+    }
+
+    #[test]
+    fn selects_runs_for_requested_stream() {
+        // 4 clusters at LCN 16 for the default stream, 2 clusters at LCN 64 for "alt".
+        let record = record_with_attributes(&[
+            non_resident_data_attribute("", &[0x11, 0x04, 0x10]),
+            non_resident_data_attribute("alt", &[0x11, 0x02, 0x40]),
+        ]);
+
+        let unnamed = MftRecordAttributeRunListOwned::from_mft_record(&record);
+        assert_eq!(
+            *unnamed,
+            vec![MftRecordAttributeRunListEntry {
+                length_clusters: 4,
+                local_cluster_network_start_entry_index: Some(16),
+            }]
+        );
+
+        let named = MftRecordAttributeRunListOwned::from_mft_record_stream(&record, Some("alt"));
+        assert_eq!(
+            *named,
+            vec![MftRecordAttributeRunListEntry {
+                length_clusters: 2,
+                local_cluster_network_start_entry_index: Some(64),
+            }]
+        );
+
+        let plan = named.into_logical_read_plan(Information::new::<byte>(4096));
+        let segment = plan.segments.first().unwrap();
+        assert_eq!(segment.length.get::<byte>(), 2 * 4096);
+        assert_eq!(
+            segment.kind,
+            LogicalFileSegmentKind::Physical {
+                physical_offset: Information::new::<byte>(64 * 4096)
+            }
+        );
+
+        assert!(
+            MftRecordAttributeRunListOwned::from_mft_record_stream(&record, Some("missing"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn sparse_hole_in_middle_contributes_logical_size() {
+        // 4 clusters at LCN 16, a 2 cluster hole, then 3 clusters at LCN 16 + 8.
+        let runlist = [0x11, 0x04, 0x10, 0x01, 0x02, 0x11, 0x03, 0x08, 0x00];
+        let decoded = MftRecordAttributeRunList::new(&runlist)
+            .decode_all()
+            .unwrap();
+        assert_eq!(
+            decoded,
+            vec![
+                MftRecordAttributeRunListEntry {
+                    length_clusters: 4,
+                    local_cluster_network_start_entry_index: Some(16),
+                },
+                MftRecordAttributeRunListEntry {
+                    length_clusters: 2,
+                    local_cluster_network_start_entry_index: None,
+                },
+                MftRecordAttributeRunListEntry {
+                    length_clusters: 3,
+                    local_cluster_network_start_entry_index: Some(24),
+                },
+            ]
+        );
+
+        let mut owned = MftRecordAttributeRunListOwned::default();
+        owned.extend(decoded);
+        let cluster_size = Information::new::<byte>(4096);
+        let plan = owned.into_logical_read_plan(cluster_size);
+        assert_eq!(plan.segments.len(), 3);
+        assert_eq!(plan.total_logical_size(), 9 * cluster_size);
+        assert_eq!(plan.physical_segments().count(), 2);
+        let hole = plan.segments.iter().nth(1).unwrap();
+        assert_eq!(hole.kind, LogicalFileSegmentKind::Sparse);
+        assert_eq!(hole.logical_offset, 4 * cluster_size);
+    }
+}
@@ -0,0 +1,43 @@
+/// Attribute type of `$STANDARD_INFORMATION`.
+pub const ATTR_TYPE_STANDARD_INFORMATION: u32 = 0x10;
+
+/// Owned copy of a resident `$STANDARD_INFORMATION` (0x10) attribute.
+///
+/// Timestamps are raw NTFS `FILETIME` values (100ns ticks since 1601-01-01 UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StdInfo {
+    pub created: u64,
+    pub modified: u64,
+    pub mft_modified: u64,
+    pub accessed: u64,
+    /// `FILE_ATTRIBUTE_*` flags
+    pub file_attributes: u32,
+}
+
+impl StdInfo {
+    /// Length of the fields common to every `$STANDARD_INFORMATION` version.
+    const MIN_CONTENT_LEN: usize = 0x24;
+
+    /// Parse the resident content of a `$STANDARD_INFORMATION` attribute.
+    ///
+    /// Returns `None` if the content is too short.
+    #[must_use]
+    pub fn parse(content: &[u8]) -> Option<Self> {
+        if content.len() < Self::MIN_CONTENT_LEN {
+            return None;
+        }
+        let read_u64 = |off: usize| {
+            content
+                .get(off..off + 8)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+        };
+        Some(Self {
+            created: read_u64(0x00)?,
+            modified: read_u64(0x08)?,
+            mft_modified: read_u64(0x10)?,
+            accessed: read_u64(0x18)?,
+            file_attributes: u32::from_le_bytes(content[0x20..0x24].try_into().ok()?),
+        })
+    }
+}
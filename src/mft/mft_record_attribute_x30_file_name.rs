@@ -0,0 +1,60 @@
+/// Owned copy of a resident `$FILE_NAME` (0x30) attribute.
+///
+/// Timestamps are raw NTFS `FILETIME` values (100ns ticks since 1601-01-01 UTC) and are only
+/// refreshed by NTFS when the name changes, so prefer [`super::mft_record_attribute_x10_standard_information::StdInfo`]
+/// for current times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileNameInfo {
+    /// Raw 64-bit reference to the parent directory (entry number and sequence)
+    pub parent_ref: u64,
+    pub created: u64,
+    pub modified: u64,
+    pub mft_modified: u64,
+    pub accessed: u64,
+    pub allocated_size: u64,
+    pub real_size: u64,
+    /// `FILE_ATTRIBUTE_*` flags
+    pub flags: u32,
+    /// 0 = POSIX, 1 = Win32, 2 = DOS, 3 = Win32 and DOS
+    pub namespace: u8,
+    pub name: String,
+}
+
+impl FileNameInfo {
+    /// Offset of the UTF-16 name inside the attribute content.
+    const NAME_OFFSET: usize = 0x42;
+
+    /// Parse the resident content of a `$FILE_NAME` attribute.
+    ///
+    /// Returns `None` if the content is too short for its fixed fields or its name.
+    #[must_use]
+    pub fn parse(content: &[u8]) -> Option<Self> {
+        if content.len() < Self::NAME_OFFSET {
+            return None;
+        }
+        let name_len = content[0x40] as usize;
+        let name_units = content
+            .get(Self::NAME_OFFSET..Self::NAME_OFFSET + name_len * 2)?
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect::<Vec<_>>();
+        let read_u64 = |off: usize| {
+            content
+                .get(off..off + 8)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+        };
+        Some(Self {
+            parent_ref: read_u64(0x00)?,
+            created: read_u64(0x08)?,
+            modified: read_u64(0x10)?,
+            mft_modified: read_u64(0x18)?,
+            accessed: read_u64(0x20)?,
+            allocated_size: read_u64(0x28)?,
+            real_size: read_u64(0x30)?,
+            flags: u32::from_le_bytes(content[0x38..0x3C].try_into().ok()?),
+            namespace: content[0x41],
+            name: String::from_utf16_lossy(&name_units),
+        })
+    }
+}
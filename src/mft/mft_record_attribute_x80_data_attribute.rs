@@ -6,6 +6,23 @@ use eyre::Result;
 use eyre::bail;
 use std::ops::Deref;
 
+/// Attribute header flag set on NTFS-compressed `$DATA`.
+pub const ATTRIBUTE_FLAG_COMPRESSED: u16 = 0x0001;
+/// Attribute header flag set on sparse `$DATA`.
+pub const ATTRIBUTE_FLAG_SPARSE: u16 = 0x8000;
+
+/// Logical and on-disk sizes of a `$DATA` stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataSizes {
+    /// Length of the stream as seen by readers, i.e. uncompressed.
+    pub logical: u64,
+    /// Bytes the stream occupies on disk: the compressed size of compressed or sparse
+    /// streams, the allocated size of other non-resident streams, and the value length of
+    /// resident ones.
+    pub on_disk: u64,
+    pub is_compressed: bool,
+}
+
 /// Wrapper specific to a type 0x80 ($DATA) attribute.
 /// Exposes helpers for resident / non-resident variants.
 #[derive(Clone, Copy, Debug)]
@@ -34,6 +51,37 @@ impl<'a> MftRecordX80DollarDataAttribute<'a> {
             .wrap_err("No data run list for resident $DATA attribute")?;
         Ok(rl)
     }
+
+    #[must_use]
+    pub fn is_compressed(&self) -> bool {
+        self.get_flags() & ATTRIBUTE_FLAG_COMPRESSED != 0
+    }
+
+    /// Sizes of this stream, reading the compressed size from the extended header when the
+    /// attribute is compressed or sparse. `None` if a non-resident header is truncated.
+    #[must_use]
+    pub fn sizes(&self) -> Option<DataSizes> {
+        if let Some(content) = self.get_resident_content() {
+            let length = content.len() as u64;
+            return Some(DataSizes {
+                logical: length,
+                on_disk: length,
+                is_compressed: false,
+            });
+        }
+        let header = self.get_non_resident_header()?;
+        let on_disk = if self.get_flags() & (ATTRIBUTE_FLAG_COMPRESSED | ATTRIBUTE_FLAG_SPARSE) != 0
+        {
+            header.compressed_size()?
+        } else {
+            header.allocated_size()
+        };
+        Some(DataSizes {
+            logical: header.real_size(),
+            on_disk,
+            is_compressed: self.is_compressed(),
+        })
+    }
 }
 impl<'a> Deref for MftRecordX80DollarDataAttribute<'a> {
     type Target = MftRecordAttribute<'a>;
@@ -45,3 +93,45 @@ impl<'a> Deref for MftRecordX80DollarDataAttribute<'a> {
 
 /// Backwards compatibility alias (temporary) for code still using `DataRunEntry`.
 pub type DataRunEntry = MftRecordAttributeRunListEntry;
+
+#[cfg(test)]
+mod tests {
+    use super::DataSizes;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::testing::SYNTHETIC_CLUSTER_SIZE;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+
+    #[test]
+    fn compressed_data_reports_logical_and_on_disk_sizes() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        let plain = builder.file(SYNTHETIC_ROOT_RECORD, "plain.bin");
+        builder.set_non_resident_data(plain, &[(8, Some(0x100))], 30_000);
+        let compressed = builder.file(SYNTHETIC_ROOT_RECORD, "compressed.bin");
+        builder.set_compressed_data(
+            compressed,
+            &[(4, Some(0x200)), (12, None)],
+            16 * SYNTHETIC_CLUSTER_SIZE,
+            4 * SYNTHETIC_CLUSTER_SIZE,
+        );
+        let mft = MftFile::from_vec(builder.build())?;
+
+        assert_eq!(
+            mft.record_at(plain)?.data_sizes(),
+            Some(DataSizes {
+                logical: 30_000,
+                on_disk: 8 * SYNTHETIC_CLUSTER_SIZE,
+                is_compressed: false,
+            })
+        );
+        assert_eq!(
+            mft.record_at(compressed)?.data_sizes(),
+            Some(DataSizes {
+                logical: 16 * SYNTHETIC_CLUSTER_SIZE,
+                on_disk: 4 * SYNTHETIC_CLUSTER_SIZE,
+                is_compressed: true,
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use crate::cancellation::CancellationToken;
+use crate::mft::mft_physical_read::build_physical_read_plan;
+use crate::mft::mft_physical_read::plan_physical_stream;
+use crate::mft::mft_record_number::MftRecordNumber;
+use crate::read::logical_read_plan::LogicalFileSegment;
+use crate::read::logical_read_plan::LogicalFileSegmentKind;
+use crate::read::logical_read_plan::LogicalReadPlan;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
+use crate::read::physical_reader::PhysicalReader;
+use crate::windows_utils::string::EasyPCWSTR;
+use eyre::Context;
+use eyre::bail;
+use facet::Facet;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use tracing::debug;
+use tracing::info;
+use tracing::info_span;
+use tracing::instrument;
+use uom::si::information::byte;
+use uom::si::information::mebibyte;
+use uom::si::usize::Information;
+
+/// Suffix appended to the output path while a resumable read is in progress.
+pub const PARTIAL_FILE_SUFFIX: &str = ".partial";
+/// Suffix appended to the partial path for the sidecar recording progress.
+pub const PARTIAL_SIDECAR_SUFFIX: &str = ".json";
+
+/// Largest logical span read and flushed to the partial file before progress is recorded.
+const RESUMABLE_BATCH_MIB: usize = 64;
+
+/// Progress sidecar persisted next to a `*.mft.partial` file.
+///
+/// Stores the logical read plan the partial was laid out for together with the logical
+/// ranges already written, so a later run can read only what is missing.
+#[derive(Facet, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PartialMftSidecar {
+    pub segments: Vec<PartialMftSegment>,
+    pub written: Vec<PartialMftRange>,
+}
+
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialMftSegment {
+    pub logical_offset: u64,
+    pub length: u64,
+    /// `None` for sparse segments
+    pub physical_offset: Option<u64>,
+}
+
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialMftRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl PartialMftSidecar {
+    #[must_use]
+    pub fn for_plan(plan: &LogicalReadPlan) -> Self {
+        Self {
+            segments: plan
+                .segments
+                .iter()
+                .map(|segment| PartialMftSegment {
+                    logical_offset: segment.logical_offset.get::<byte>() as u64,
+                    length: segment.length.get::<byte>() as u64,
+                    physical_offset: match segment.kind {
+                        LogicalFileSegmentKind::Physical { physical_offset } => {
+                            Some(physical_offset.get::<byte>() as u64)
+                        }
+                        LogicalFileSegmentKind::Sparse => None,
+                    },
+                })
+                .collect(),
+            written: Vec::new(),
+        }
+    }
+
+    /// Whether this sidecar was recorded for the same logical layout as `plan`.
+    #[must_use]
+    pub fn matches_plan(&self, plan: &LogicalReadPlan) -> bool {
+        self.segments == Self::for_plan(plan).segments
+    }
+
+    /// Logical ranges already written to the partial file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a recorded offset does not fit in `usize`.
+    #[must_use]
+    pub fn written_ranges(&self) -> Vec<Range<Information>> {
+        self.written
+            .iter()
+            .map(|range| {
+                Information::new::<byte>(usize::try_from(range.start).expect("offset fits usize"))
+                    ..Information::new::<byte>(
+                        usize::try_from(range.end).expect("offset fits usize"),
+                    )
+            })
+            .collect()
+    }
+}
+
+/// Path of the in-progress partial file for `output_path` (e.g. `C.mft.partial`).
+#[must_use]
+pub fn partial_path_for(output_path: &Path) -> PathBuf {
+    let mut partial = output_path.as_os_str().to_owned();
+    partial.push(PARTIAL_FILE_SUFFIX);
+    PathBuf::from(partial)
+}
+
+/// Path of the progress sidecar for `output_path` (e.g. `C.mft.partial.json`).
+#[must_use]
+pub fn partial_sidecar_path_for(output_path: &Path) -> PathBuf {
+    let mut sidecar = partial_path_for(output_path).into_os_string();
+    sidecar.push(PARTIAL_SIDECAR_SUFFIX);
+    PathBuf::from(sidecar)
+}
+
+/// Read the `$MFT` of `drive_letter` into `output_path`, resuming a previous interrupted run.
+///
+/// Bytes are written in batches to `<output_path>.partial` and each finished batch is
+/// recorded in `<output_path>.partial.json`. With `resume`, when both files exist and the
+/// sidecar matches the current logical layout, only the missing ranges are read; otherwise any
+/// earlier partial is discarded and the read starts over. The partial file is renamed to
+/// `output_path` once every range has been written.
+///
+/// `on_batch(bytes_done, bytes_total)` is called once before reading and again after each
+/// batch is persisted, counting only physical (non-sparse) bytes.
+///
+/// # Errors
+///
+/// Returns an error if the drive cannot be read, the logical read plan has gaps or overlaps,
+/// the partial files cannot be written, or cancellation is requested between batches.
+#[instrument(skip(cancel, on_batch))]
+pub fn read_physical_mft_resumable(
+    drive_letter: char,
+    output_path: &Path,
+    tuning: &PhysicalReadTuning,
+    resume: bool,
+    cancel: &CancellationToken,
+    on_batch: &mut dyn FnMut(u64, u64),
+) -> eyre::Result<()> {
+    let drive_letter = drive_letter.to_ascii_uppercase();
+    let volume_path = format!(r"\\.\{drive_letter}:");
+    let volume_path = volume_path
+        .easy_pcwstr()
+        .wrap_err("Failed to convert volume path to PCWSTR")?;
+    let partial_path = partial_path_for(output_path);
+    let sidecar_path = partial_sidecar_path_for(output_path);
+
+    let logical_read_plan = plan_physical_stream(
+        drive_letter,
+        MftRecordNumber::DOLLAR_MFT,
+        None,
+        tuning.max_entries,
+    )?;
+    // A gap or overlap would leave stale bytes in the partial file or write some twice.
+    logical_read_plan.validate()?;
+
+    let existing = if resume && partial_path.is_file() && sidecar_path.is_file() {
+        let sidecar =
+            facet_json::from_str::<PartialMftSidecar>(&fs::read_to_string(&sidecar_path)?)
+                .map_err(|error| {
+                    eyre::eyre!("Failed parsing {}: {error}", sidecar_path.display())
+                })?;
+        if sidecar.matches_plan(&logical_read_plan) {
+            Some(sidecar)
+        } else {
+            info!(
+                drive = %drive_letter,
+                "MFT layout changed since the partial read was started; restarting"
+            );
+            None
+        }
+    } else {
+        None
+    };
+    let mut sidecar = if let Some(sidecar) = existing {
+        info!(
+            drive = %drive_letter,
+            written_ranges = sidecar.written.len(),
+            "Resuming partial MFT read from {}",
+            partial_path.display()
+        );
+        sidecar
+    } else {
+        let file = fs::File::create(&partial_path)
+            .wrap_err_with(|| format!("Failed to create {}", partial_path.display()))?;
+        file.set_len(logical_read_plan.total_logical_size().get::<byte>() as u64)?;
+        let sidecar = PartialMftSidecar::for_plan(&logical_read_plan);
+        fs::write(&sidecar_path, facet_json::to_vec_pretty(&sidecar)?)?;
+        sidecar
+    };
+
+    let remaining = logical_read_plan.without_logical_ranges(&sidecar.written_ranges());
+    let bytes_total = logical_read_plan
+        .physical_segments()
+        .map(|segment| segment.length.get::<byte>() as u64)
+        .sum::<u64>();
+    let mut bytes_done = sidecar
+        .written
+        .iter()
+        .map(|range| range.end - range.start)
+        .sum::<u64>();
+    on_batch(bytes_done, bytes_total);
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .open(&partial_path)
+        .wrap_err_with(|| format!("Failed to open {}", partial_path.display()))?;
+    let batch_size = Information::new::<mebibyte>(RESUMABLE_BATCH_MIB);
+    // Every batch reads the same volume, so open its handle and completion port once.
+    let mut reader = PhysicalReader::open(&volume_path, tuning.queue_depth)?;
+    for segment in remaining.physical_segments() {
+        let LogicalFileSegmentKind::Physical { physical_offset } = segment.kind else {
+            unreachable!("physical_segments only yields physical segments");
+        };
+        let segment_end = segment.logical_offset + segment.length;
+        let mut batch_start = segment.logical_offset;
+        while batch_start < segment_end {
+            if cancel.is_cancelled() {
+                bail!(
+                    "Cancelled resumable MFT read for drive {drive_letter}; rerun with --resume to continue"
+                );
+            }
+            let batch_end = (batch_start + batch_size).min(segment_end);
+            let batch_plan = LogicalReadPlan {
+                segments: [LogicalFileSegment {
+                    logical_offset: batch_start,
+                    length: batch_end - batch_start,
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: physical_offset + (batch_start - segment.logical_offset),
+                    },
+                }]
+                .into_iter()
+                .collect(),
+            };
+            let _span = info_span!(
+                "read_resumable_mft_batch",
+                drive = %drive_letter,
+                logical_offset = batch_start.get::<byte>(),
+                length = (batch_end - batch_start).get::<byte>(),
+            )
+            .entered();
+            let results =
+                reader.read_plan(build_physical_read_plan(&batch_plan, tuning.chunk_size))?;
+            results.write(&batch_plan, &mut file)?;
+            file.sync_data()?;
+
+            sidecar.written.push(PartialMftRange {
+                start: batch_start.get::<byte>() as u64,
+                end: batch_end.get::<byte>() as u64,
+            });
+            fs::write(&sidecar_path, facet_json::to_vec_pretty(&sidecar)?)?;
+            bytes_done += (batch_end - batch_start).get::<byte>() as u64;
+            on_batch(bytes_done, bytes_total);
+            debug!(
+                drive = %drive_letter,
+                written_ranges = sidecar.written.len(),
+                "Recorded resumable MFT batch"
+            );
+            batch_start = batch_end;
+        }
+    }
+    drop(file);
+
+    fs::rename(&partial_path, output_path).wrap_err_with(|| {
+        format!(
+            "Failed to move {} to {}",
+            partial_path.display(),
+            output_path.display()
+        )
+    })?;
+    fs::remove_file(&sidecar_path)
+        .wrap_err_with(|| format!("Failed to remove {}", sidecar_path.display()))?;
+    info!(
+        drive = %drive_letter,
+        "Completed resumable MFT read into {}",
+        output_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bytes(value: usize) -> Information {
+        Information::new::<byte>(value)
+    }
+
+    #[test]
+    fn resuming_with_half_written_reads_only_the_remainder() {
+        let plan = LogicalReadPlan {
+            segments: [
+                LogicalFileSegment {
+                    logical_offset: bytes(0),
+                    length: bytes(4096),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: bytes(1 << 20),
+                    },
+                },
+                LogicalFileSegment {
+                    logical_offset: bytes(4096),
+                    length: bytes(4096),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: bytes(8 << 20),
+                    },
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let mut sidecar = PartialMftSidecar::for_plan(&plan);
+        sidecar.written.push(PartialMftRange {
+            start: 0,
+            end: 4096,
+        });
+
+        let roundtripped = facet_json::from_str::<PartialMftSidecar>(
+            &String::from_utf8(facet_json::to_vec_pretty(&sidecar).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(roundtripped, sidecar);
+        assert!(roundtripped.matches_plan(&plan));
+
+        let remaining = plan.without_logical_ranges(&roundtripped.written_ranges());
+        let physical = remaining.as_physical_read_plan();
+        assert_eq!(physical.total_size(), bytes(4096));
+        assert_eq!(physical.into_iter().next().unwrap().offset, bytes(8 << 20));
+    }
+
+    #[test]
+    fn partial_paths_extend_the_output_path() {
+        let output = Path::new(r"C:\cache\C.mft");
+        assert_eq!(
+            partial_path_for(output),
+            PathBuf::from(r"C:\cache\C.mft.partial")
+        );
+        assert_eq!(
+            partial_sidecar_path_for(output),
+            PathBuf::from(r"C:\cache\C.mft.partial.json")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod fast_entry;
 pub mod fast_fixup;
+pub mod mapped_mft_file;
 pub mod mft_convert_to_path_collection;
 pub mod mft_file;
 pub mod mft_location;
@@ -9,6 +10,8 @@ pub mod mft_record_attribute;
 pub mod mft_record_attribute_iter;
 pub mod mft_record_attribute_non_resident_header;
 pub mod mft_record_attribute_run_list;
+pub mod mft_record_attribute_x10_standard_information;
+pub mod mft_record_attribute_x30_file_name;
 pub mod mft_record_attribute_x80_data_attribute;
 pub mod mft_record_flags;
 pub mod mft_record_index;
@@ -17,5 +20,8 @@ pub mod mft_record_location;
 pub mod mft_record_number;
 pub mod mft_record_reference;
 pub mod mft_record_size;
+pub mod mft_resumable_read;
 pub mod mft_sequence_number;
 pub mod path_resolve;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
@@ -4,10 +4,12 @@
 use crate::mft::fast_entry::FileNameCollection;
 use crate::mft::mft_record_index::MftRecordIndex;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use tracing::debug_span;
 use tracing::instrument;
+use tracing::warn;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ResolvedPath {
@@ -15,6 +17,8 @@ pub struct ResolvedPath {
     pub root_prefix: String,
     pub components: Vec<String>,
     pub component_deleted: Vec<bool>,
+    /// Per-component reparse tag; `Some` where the path crosses a mount point, junction, or link
+    pub component_reparse_tag: Vec<Option<u32>>,
 }
 
 impl ResolvedPath {
@@ -30,33 +34,109 @@ impl ResolvedPath {
             .filter(|is_deleted| **is_deleted)
             .count()
     }
+
+    /// True when any component of this path is a reparse point, so the path may not
+    /// reflect the directory that is actually reached through it.
+    #[must_use]
+    pub fn crosses_reparse_point(&self) -> bool {
+        self.component_reparse_tag.iter().any(Option::is_some)
+    }
 }
 
-/// Decode UTF-16 little endian slice to String (lossy ASCII fast-path optional later).
-fn decode_name(units: &[u16]) -> Cow<'_, str> {
+/// Decode a UTF-16 little endian byte slice to a String, replacing unpaired surrogates.
+///
+/// The bytes come straight from the record and may be unaligned, so code units are
+/// assembled with `u16::from_le_bytes` rather than reinterpreting the slice.
+/// The returned flag is `true` when the name had unpaired surrogates or already
+/// contained U+FFFD, either of which can indicate a corrupted or obfuscated name.
+fn decode_name(bytes: &[u8]) -> (Cow<'_, str>, bool) {
     use std::char::decode_utf16;
-    // ASCII fast path: if all code units are < 0x80 build directly
-    if units.iter().all(|&u| u < 0x80) {
-        let mut s = String::with_capacity(units.len());
-        #[expect(clippy::cast_possible_truncation, reason = "checked u < 0x80")]
-        for &u in units {
-            s.push(u as u8 as char);
+    // ASCII fast path: every code unit is a low byte below 0x80 followed by a zero high byte
+    if bytes
+        .chunks_exact(2)
+        .all(|pair| pair[0] < 0x80 && pair[1] == 0)
+    {
+        let mut s = String::with_capacity(bytes.len() / 2);
+        for pair in bytes.chunks_exact(2) {
+            s.push(char::from(pair[0]));
         }
-        return Cow::Owned(s);
+        return (Cow::Owned(s), false);
     }
-    let iter = decode_utf16(units.iter().copied());
-    let mut s = String::with_capacity(units.len());
+    let iter = decode_utf16(
+        bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+    );
+    let mut s = String::with_capacity(bytes.len() / 2);
+    let mut invalid = false;
     for r in iter {
-        s.push(r.unwrap_or('\u{FFFD}'));
+        let c = r.unwrap_or('\u{FFFD}');
+        invalid |= c == '\u{FFFD}';
+        s.push(c);
+    }
+    (Cow::Owned(s), invalid)
+}
+
+/// Entries whose `FILE_NAME` could not be decoded cleanly during path resolution.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NameDecodeStats {
+    /// Entry ids, ascending, with at least one name containing unpaired surrogates or U+FFFD
+    pub invalid_name_entries: Vec<usize>,
+}
+
+impl NameDecodeStats {
+    #[must_use]
+    pub fn invalid_name_count(&self) -> usize {
+        self.invalid_name_entries.len()
     }
-    Cow::Owned(s)
+}
+
+/// Synthetic root directory under which orphaned entries are listed.
+pub const ORPHAN_DIR_NAME: &str = "$Orphan";
+
+/// An entry with a `FILE_NAME` whose parent is out of range or never resolves to the root,
+/// typically a deleted file whose directory record has since been reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanEntry {
+    pub entry_id: usize,
+    /// Parent entry number recorded in the highest-precedence `FILE_NAME`
+    pub parent: usize,
+    pub name: String,
+    pub is_deleted: bool,
+}
+
+impl OrphanEntry {
+    /// The synthetic `<root>\$Orphan\<name>` path this entry is listed under.
+    #[must_use]
+    pub fn path(&self, root_prefix: &Path) -> PathBuf {
+        root_prefix.join(ORPHAN_DIR_NAME).join(&self.name)
+    }
+}
+
+/// Whether `path` (`C:\...` or `\...`) lies under the synthetic [`ORPHAN_DIR_NAME`] root.
+#[must_use]
+pub fn is_orphan_path(path: &str) -> bool {
+    let path = match path.as_bytes() {
+        [letter, b':', ..] if letter.is_ascii_alphabetic() => &path[2..],
+        _ => path,
+    };
+    path.strip_prefix('\\')
+        .and_then(|path| path.strip_prefix(ORPHAN_DIR_NAME))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('\\'))
 }
 
 /// A mapping from MFT entry ID to zero/one/many resolved paths.
 /// Because an entry can have multiple x30 attributes, one entry may have more than one full path associated with it.
+/// Entries with names that could not be rooted are kept separately as [`OrphanEntry`]s.
 #[derive(Debug, Default, Clone)]
-pub struct MftEntryPathCollection(pub Vec<Vec<ResolvedPath>>);
+pub struct MftEntryPathCollection(pub Vec<Vec<ResolvedPath>>, Vec<OrphanEntry>);
 impl MftEntryPathCollection {
+    /// Entries that have a name but no resolved path, in entry order.
+    #[must_use]
+    pub fn orphan_entries(&self) -> &[OrphanEntry] {
+        &self.1
+    }
+
     #[must_use]
     pub fn entry_count(&self) -> usize {
         self.0.len()
@@ -69,6 +149,44 @@ impl MftEntryPathCollection {
     pub fn paths_for(&self, entry_id: usize) -> &[ResolvedPath] {
         self.0.get(entry_id).map_or(&[], |v| &**v)
     }
+
+    /// The highest-precedence path for an entry: the one with the fewest deleted
+    /// components, ties broken by path order.
+    #[must_use]
+    pub fn primary_path(&self, entry_id: usize) -> Option<&Path> {
+        self.paths_for(entry_id)
+            .iter()
+            .min_by_key(|path| (path.deleted_segment_count(), &path.path))
+            .map(|path| path.path.as_path())
+    }
+
+    /// Whether the entry itself is a reparse point (mount point, junction, symlink, ...).
+    #[must_use]
+    pub fn is_reparse_point(&self, entry_id: usize) -> bool {
+        self.reparse_tag(entry_id).is_some()
+    }
+
+    /// The entry's reparse tag, taken from the last component of any of its resolved paths.
+    #[must_use]
+    pub fn reparse_tag(&self, entry_id: usize) -> Option<u32> {
+        self.paths_for(entry_id)
+            .first()
+            .and_then(|path| path.component_reparse_tag.last().copied().flatten())
+    }
+
+    /// Build an inverse lookup from every resolved path to its entry id.
+    ///
+    /// Every hardlink of an entry gets its own key, so several paths may map to the same entry.
+    #[must_use]
+    pub fn build_path_index(&self) -> HashMap<PathBuf, usize> {
+        let mut rtn = HashMap::with_capacity(self.total_paths());
+        for (entry_id, paths) in self.0.iter().enumerate() {
+            for path in paths {
+                rtn.insert(path.path.clone(), entry_id);
+            }
+        }
+        rtn
+    }
 }
 
 #[inline]
@@ -127,13 +245,28 @@ fn dfs(i: usize, per_entry: &Vec<Vec<BestName>>, depth: &mut [i32], mark: &mut [
 ///
 /// Currently this function always returns `Ok`, but the fallible signature allows future
 /// extensions that might fail during decoding or validation.
-#[expect(clippy::too_many_lines, reason = "complex path resolution logic")]
 #[instrument(level = "debug", skip(file_names))]
 // mftf[impl path-resolution.parent-chain-absolute-paths]
 pub fn resolve_paths_all_parallel(
     file_names: &FileNameCollection<'_>,
     root_prefix: &Path,
 ) -> eyre::Result<MftEntryPathCollection> {
+    resolve_paths_all_parallel_with_stats(file_names, root_prefix).map(|(paths, _stats)| paths)
+}
+
+/// Resolve all paths like [`resolve_paths_all_parallel`], also reporting which entries had
+/// names that needed replacement characters.
+///
+/// # Errors
+///
+/// Currently this function always returns `Ok`, but the fallible signature allows future
+/// extensions that might fail during decoding or validation.
+#[expect(clippy::too_many_lines, reason = "complex path resolution logic")]
+#[instrument(level = "debug", skip(file_names))]
+pub fn resolve_paths_all_parallel_with_stats(
+    file_names: &FileNameCollection<'_>,
+    root_prefix: &Path,
+) -> eyre::Result<(MftEntryPathCollection, NameDecodeStats)> {
     use rayon::prelude::*;
     let entry_count = {
         let _span = debug_span!("get_entry_count").entered();
@@ -143,7 +276,8 @@ pub fn resolve_paths_all_parallel(
     // Build raw selections with namespace precedence (same logic as sequential version) then decode.
     let raw = {
         let _span = debug_span!("build_raw_parent_name_selection").entered();
-        let mut raw: Vec<Vec<(usize, u8, &'_ [u16])>> = Vec::with_capacity(entry_count);
+        let mut raw: Vec<Vec<(usize, u8, &'_ [u8])>> = Vec::with_capacity(entry_count);
+        let mut out_of_range: Vec<(usize, usize, u8, &'_ [u8])> = Vec::new();
         for _ in 0..entry_count {
             raw.push(Vec::new());
         }
@@ -154,38 +288,53 @@ pub fn resolve_paths_all_parallel(
             for fref in file_names.filenames_for_entry(entry_id as u32) {
                 let parent = (fref.parent_ref & 0xFFFF_FFFF_FFFF) as usize;
                 if parent >= entry_count {
+                    out_of_range.push((entry_id, parent, fref.namespace, fref.name_bytes));
                     continue;
                 }
                 if let Some((_, ns, name_units)) = list.iter_mut().find(|(p, _, _)| *p == parent) {
                     if ns_rank(fref.namespace) < ns_rank(*ns) {
                         *ns = fref.namespace;
-                        *name_units = fref.name_utf16;
+                        *name_units = fref.name_bytes;
                     }
                 } else {
-                    list.push((parent, fref.namespace, fref.name_utf16));
+                    list.push((parent, fref.namespace, fref.name_bytes));
                 }
             }
         }
-        raw
+        (raw, out_of_range)
     };
+    let (raw, out_of_range) = raw;
 
+    let mut stats = NameDecodeStats::default();
     let per_entry = {
         let _span = debug_span!("decode_raw_names_to_best_names").entered();
         let mut per_entry: Vec<Vec<BestName>> = Vec::with_capacity(entry_count);
-        for raw_list in &raw {
+        for (entry_id, raw_list) in raw.iter().enumerate() {
             #[cfg(feature = "extended_observability_per_record")]
             let _span = debug_span!("decode_entry_names").entered();
             let mut v: Vec<BestName> = Vec::with_capacity(raw_list.len());
+            let mut entry_invalid = false;
             for (parent, _ns, name_units) in raw_list {
+                let (name, invalid) = decode_name(name_units);
+                entry_invalid |= invalid;
                 v.push(BestName {
                     parent: *parent,
-                    name: decode_name(name_units).into_owned(),
+                    name: name.into_owned(),
                 });
             }
+            if entry_invalid {
+                stats.invalid_name_entries.push(entry_id);
+            }
             per_entry.push(v);
         }
         per_entry
     };
+    if stats.invalid_name_count() > 0 {
+        warn!(
+            invalid_name_count = stats.invalid_name_count(),
+            "Some file names contained invalid UTF-16 and were decoded with replacement characters"
+        );
+    }
 
     // Compute depth (minimum parent depth + 1) so parents always processed before children.
     let layers = {
@@ -222,6 +371,7 @@ pub fn resolve_paths_all_parallel(
                 root_prefix: root_prefix_display,
                 components: Vec::new(),
                 component_deleted: Vec::new(),
+                component_reparse_tag: Vec::new(),
             });
         }
         results
@@ -259,11 +409,15 @@ pub fn resolve_paths_all_parallel(
                                 components.push(bn.name.clone());
                                 let mut component_deleted = parent_path.component_deleted.clone();
                                 component_deleted.push(file_names.is_entry_deleted(entry_id));
+                                let mut component_reparse_tag =
+                                    parent_path.component_reparse_tag.clone();
+                                component_reparse_tag.push(file_names.reparse_tag(entry_id));
                                 acc.push(ResolvedPath {
                                     path: p,
                                     root_prefix: parent_path.root_prefix.clone(),
                                     components,
                                     component_deleted,
+                                    component_reparse_tag,
                                 });
                             }
                         }
@@ -302,5 +456,258 @@ pub fn resolve_paths_all_parallel(
         }
     }
 
-    Ok(MftEntryPathCollection(results))
+    let orphans = {
+        let _span = debug_span!("collect_orphan_entries").entered();
+        collect_orphans(file_names, &raw, &out_of_range, &results)
+    };
+
+    Ok((MftEntryPathCollection(results, orphans), stats))
+}
+
+/// Every entry with a name but no resolved path, named by its highest-precedence `FILE_NAME`.
+fn collect_orphans(
+    file_names: &FileNameCollection<'_>,
+    raw: &[Vec<(usize, u8, &[u8])>],
+    out_of_range: &[(usize, usize, u8, &[u8])],
+    results: &[Vec<ResolvedPath>],
+) -> Vec<OrphanEntry> {
+    let mut best = vec![None::<(usize, u8, &[u8])>; results.len()];
+    let candidates = raw
+        .iter()
+        .enumerate()
+        .flat_map(|(entry_id, list)| list.iter().map(move |name| (entry_id, *name)))
+        .chain(
+            out_of_range
+                .iter()
+                .map(|&(entry_id, parent, ns, name)| (entry_id, (parent, ns, name))),
+        );
+    for (entry_id, (parent, ns, name)) in candidates {
+        if !results[entry_id].is_empty() || parent == entry_id {
+            continue;
+        }
+        let slot = &mut best[entry_id];
+        if slot.is_none_or(|(_, best_ns, _)| ns_rank(ns) < ns_rank(best_ns)) {
+            *slot = Some((parent, ns, name));
+        }
+    }
+    best.into_iter()
+        .enumerate()
+        .filter_map(|(entry_id, best)| {
+            let (parent, _ns, name) = best?;
+            Some(OrphanEntry {
+                entry_id,
+                parent,
+                name: decode_name(name).0.into_owned(),
+                is_deleted: file_names.is_entry_deleted(MftRecordIndex::new(entry_id)),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MftEntryPathCollection;
+    use super::OrphanEntry;
+    use super::ResolvedPath;
+    use super::decode_name;
+    use super::is_orphan_path;
+    use super::resolve_paths_all_parallel_with_stats;
+    use crate::mft::fast_entry::FileNameCollection;
+    use crate::mft::fast_entry::FileNameRef;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    fn resolved(path: &str, component_deleted: Vec<bool>) -> ResolvedPath {
+        ResolvedPath {
+            path: PathBuf::from(path),
+            root_prefix: String::from(r"C:\"),
+            components: Vec::new(),
+            component_reparse_tag: vec![None; component_deleted.len()],
+            component_deleted,
+        }
+    }
+
+    fn collection() -> MftEntryPathCollection {
+        MftEntryPathCollection(
+            vec![
+                vec![resolved(r"C:\", vec![])],
+                vec![],
+                vec![
+                    resolved(r"C:\old\report.txt", vec![true, false]),
+                    resolved(r"C:\docs\report.txt", vec![false, false]),
+                ],
+            ],
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn decode_name_handles_surrogate_pairs_and_unaligned_bytes() {
+        let name = "résumé-\u{1F600}.txt";
+        let mut bytes = vec![0_u8];
+        bytes.extend(name.encode_utf16().flat_map(u16::to_le_bytes));
+
+        // Start one byte in so the UTF-16 data is deliberately misaligned.
+        assert_eq!(decode_name(&bytes[1..]), (name.into(), false));
+        assert_eq!(decode_name(&[b'a', 0, b'b', 0]), ("ab".into(), false));
+        assert_eq!(
+            decode_name(&0xD800_u16.to_le_bytes()),
+            ("\u{FFFD}".into(), true)
+        );
+    }
+
+    #[test]
+    fn invalid_utf16_names_are_reported_in_stats() -> eyre::Result<()> {
+        // Entry 5 is the root; entry 6 has an unpaired high surrogate before "a".
+        let unpaired = [0x00, 0xD8, b'a', 0x00];
+        let file_names = FileNameCollection {
+            all_filenames: vec![
+                FileNameRef {
+                    entry_id: 5,
+                    parent_ref: 5,
+                    namespace: 1,
+                    name_bytes: &[b'.', 0],
+                },
+                FileNameRef {
+                    entry_id: 6,
+                    parent_ref: 5,
+                    namespace: 1,
+                    name_bytes: &unpaired,
+                },
+            ],
+            per_entry_indices: vec![vec![], vec![], vec![], vec![], vec![], vec![0], vec![1]],
+            per_entry_deleted: vec![false; 7],
+            per_entry_reparse_tag: vec![None; 7],
+        };
+
+        let (paths, stats) = resolve_paths_all_parallel_with_stats(&file_names, Path::new(r"C:\"))?;
+
+        assert_eq!(stats.invalid_name_entries, vec![6]);
+        assert_eq!(stats.invalid_name_count(), 1);
+        assert_eq!(paths.paths_for(6)[0].components, vec!["\u{FFFD}a"]);
+        Ok(())
+    }
+
+    #[test]
+    fn entries_with_invalid_parents_surface_as_orphans() -> eyre::Result<()> {
+        // Entry 6 names a parent past the end of the MFT; entry 7 hangs off entry 6.
+        // Entry 8 resolves normally under the root.
+        let file_names = FileNameCollection {
+            all_filenames: vec![
+                FileNameRef {
+                    entry_id: 5,
+                    parent_ref: 5,
+                    namespace: 1,
+                    name_bytes: &[b'.', 0],
+                },
+                FileNameRef {
+                    entry_id: 6,
+                    parent_ref: (3 << 48) | 4242,
+                    namespace: 1,
+                    name_bytes: &[b'o', 0, b'l', 0, b'd', 0],
+                },
+                FileNameRef {
+                    entry_id: 7,
+                    parent_ref: 6,
+                    namespace: 1,
+                    name_bytes: &[b'x', 0],
+                },
+                FileNameRef {
+                    entry_id: 8,
+                    parent_ref: 5,
+                    namespace: 1,
+                    name_bytes: &[b'y', 0],
+                },
+            ],
+            per_entry_indices: vec![
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![0],
+                vec![1],
+                vec![2],
+                vec![3],
+            ],
+            per_entry_deleted: vec![false, false, false, false, false, false, true, true, false],
+            per_entry_reparse_tag: vec![None; 9],
+        };
+
+        let (paths, _stats) =
+            resolve_paths_all_parallel_with_stats(&file_names, Path::new(r"C:\"))?;
+
+        assert!(paths.paths_for(6).is_empty());
+        assert_eq!(paths.primary_path(8), Some(Path::new(r"C:\y")));
+        assert_eq!(
+            paths.orphan_entries(),
+            [
+                OrphanEntry {
+                    entry_id: 6,
+                    parent: 4242,
+                    name: "old".to_owned(),
+                    is_deleted: true,
+                },
+                OrphanEntry {
+                    entry_id: 7,
+                    parent: 6,
+                    name: "x".to_owned(),
+                    is_deleted: true,
+                },
+            ]
+        );
+        let orphan_path = paths.orphan_entries()[0].path(Path::new(r"C:\"));
+        assert!(is_orphan_path(&orphan_path.to_string_lossy()));
+        assert!(is_orphan_path(r"\$Orphan\x"));
+        assert!(!is_orphan_path(r"C:\$OrphanNot\x"));
+        assert!(!is_orphan_path(r"C:\docs\$Orphan\x"));
+        Ok(())
+    }
+
+    #[test]
+    fn primary_path_prefers_fewest_deleted_components() {
+        let collection = collection();
+        assert_eq!(
+            collection.primary_path(2),
+            Some(Path::new(r"C:\docs\report.txt"))
+        );
+        assert_eq!(collection.primary_path(0), Some(Path::new(r"C:\")));
+        assert_eq!(collection.primary_path(1), None);
+        assert_eq!(collection.primary_path(99), None);
+
+        let hardlinks = MftEntryPathCollection(
+            vec![vec![
+                resolved(r"C:\z\shared.txt", vec![false, false]),
+                resolved(r"C:\a\shared.txt", vec![false, false]),
+            ]],
+            Vec::new(),
+        );
+        assert_eq!(
+            hardlinks.primary_path(0),
+            Some(Path::new(r"C:\a\shared.txt"))
+        );
+    }
+
+    #[test]
+    fn path_index_maps_every_hardlink_to_its_entry() {
+        let index = collection().build_path_index();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index[Path::new(r"C:\docs\report.txt")], 2);
+        assert_eq!(index[Path::new(r"C:\old\report.txt")], 2);
+        assert_eq!(index[Path::new(r"C:\")], 0);
+    }
+
+    #[test]
+    fn reparse_point_is_read_from_the_last_component() {
+        let mut mounted = resolved(r"C:\mnt\data", vec![false, false]);
+        mounted.component_reparse_tag = vec![None, Some(0xA000_0003)];
+        let mut inside = resolved(r"C:\mnt\data\file.txt", vec![false, false, false]);
+        inside.component_reparse_tag = vec![None, Some(0xA000_0003), None];
+        let collection = MftEntryPathCollection(vec![vec![mounted], vec![inside]], Vec::new());
+
+        assert!(collection.is_reparse_point(0));
+        assert_eq!(collection.reparse_tag(0), Some(0xA000_0003));
+        assert!(!collection.is_reparse_point(1));
+        assert!(collection.paths_for(1)[0].crosses_reparse_point());
+    }
 }
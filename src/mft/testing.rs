@@ -0,0 +1,496 @@
+//! Synthetic MFT generation for tests and benchmarks.
+//!
+//! Builds raw `FILE` records in memory, with update sequence arrays protected the way
+//! NTFS writes them, so the fixup, filename and path-resolution passes can be exercised
+//! without reading a real volume. Every in-use record carries `$STANDARD_INFORMATION`
+//! and `$FILE_NAME` attributes, plus a non-resident `$DATA` attribute when one is set.
+
+/// Size of every generated record; matches the common NTFS default.
+pub const SYNTHETIC_RECORD_SIZE: usize = 1024;
+/// Record number of the root directory.
+pub const SYNTHETIC_ROOT_RECORD: u64 = 5;
+/// First record number handed out by [`SyntheticMftBuilder`]; NTFS reserves the ones below it.
+pub const SYNTHETIC_FIRST_USER_RECORD: u64 = RESERVED_RECORD_COUNT as u64;
+/// Cluster size assumed for allocated sizes and `$DATA` runs.
+pub const SYNTHETIC_CLUSTER_SIZE: u64 = 4096;
+/// `FILETIME` stamped on every generated record (2024-01-01 00:00:00 UTC).
+pub const SYNTHETIC_TIMESTAMP: u64 = 133_485_408_000_000_000;
+
+const RESERVED_RECORD_COUNT: usize = 16;
+
+const SECTOR_SIZE: usize = 512;
+const UPDATE_SEQUENCE_OFFSET: usize = 0x30;
+const UPDATE_SEQUENCE_VALUE: u16 = 0x0001;
+const FIRST_ATTRIBUTE_OFFSET: usize = 0x38;
+const RECORD_FLAG_IN_USE: u16 = 0x0001;
+const RECORD_FLAG_DIRECTORY: u16 = 0x0002;
+const ATTRIBUTE_TYPE_STANDARD_INFORMATION: u32 = 0x10;
+const ATTRIBUTE_TYPE_FILE_NAME: u32 = 0x30;
+const ATTRIBUTE_TYPE_DATA: u32 = 0x80;
+const ATTRIBUTE_TYPE_END: u32 = 0xFFFF_FFFF;
+const RESIDENT_HEADER_LEN: usize = 0x18;
+const NON_RESIDENT_HEADER_LEN: usize = 0x40;
+const COMPRESSED_NON_RESIDENT_HEADER_LEN: usize = 0x48;
+const ATTRIBUTE_FLAG_COMPRESSED: u16 = 0x0001;
+const STANDARD_INFORMATION_LEN: usize = 0x48;
+const FILE_NAME_NAME_OFFSET: usize = 0x42;
+const FILE_NAME_NAMESPACE_WIN32_AND_DOS: u8 = 3;
+const FILE_NAME_FLAG_DIRECTORY: u32 = 0x1000_0000;
+const RESERVED_NAMES: [&str; 12] = [
+    "$MFT", "$MFTMirr", "$LogFile", "$Volume", "$AttrDef", ".", "$Bitmap", "$Boot", "$BadClus",
+    "$Secure", "$UpCase", "$Extend",
+];
+
+#[derive(Debug, Clone)]
+struct SyntheticRecord {
+    parent: u64,
+    name: String,
+    is_directory: bool,
+    in_use: bool,
+    size: u64,
+    file_attributes: u32,
+    data: Option<SyntheticData>,
+}
+
+/// Unnamed non-resident `$DATA`: `(length_clusters, lcn)` runs, `None` marking a sparse run.
+#[derive(Debug, Clone)]
+struct SyntheticData {
+    runs: Vec<(u64, Option<u64>)>,
+    real_size: u64,
+    compressed_size: Option<u64>,
+}
+
+/// Builds an in-memory MFT from a list of directories and files.
+///
+/// Records 0 through 15 are the NTFS system files, with record 5 as the root directory.
+/// Entries added through the builder are numbered from [`SYNTHETIC_FIRST_USER_RECORD`].
+/// [`Self::build`] returns the raw bytes before fixups, as they would be read from disk.
+#[derive(Debug, Clone)]
+pub struct SyntheticMftBuilder {
+    records: Vec<Option<SyntheticRecord>>,
+}
+
+impl Default for SyntheticMftBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyntheticMftBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        let mut records: Vec<Option<SyntheticRecord>> = RESERVED_NAMES
+            .iter()
+            .map(|name| {
+                Some(SyntheticRecord {
+                    parent: SYNTHETIC_ROOT_RECORD,
+                    name: (*name).to_owned(),
+                    is_directory: *name == "." || *name == "$Extend",
+                    in_use: true,
+                    size: 0,
+                    file_attributes: 0,
+                    data: None,
+                })
+            })
+            .collect();
+        records.resize(RESERVED_RECORD_COUNT, None);
+        Self { records }
+    }
+
+    /// Add a directory under `parent` and return its record number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is longer than 255 UTF-16 code units.
+    pub fn directory(&mut self, parent: u64, name: &str) -> u64 {
+        self.push(parent, name, true)
+    }
+
+    /// Add an empty file under `parent` and return its record number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is longer than 255 UTF-16 code units.
+    pub fn file(&mut self, parent: u64, name: &str) -> u64 {
+        self.push(parent, name, false)
+    }
+
+    /// Add a file of `size` bytes under `parent` and return its record number.
+    ///
+    /// The size is recorded in the `$FILE_NAME` attribute; no `$DATA` attribute is emitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is longer than 255 UTF-16 code units.
+    pub fn file_with_size(&mut self, parent: u64, name: &str, size: u64) -> u64 {
+        let record_number = self.push(parent, name, false);
+        if let Some(record) = self.record_mut(record_number) {
+            record.size = size;
+        }
+        record_number
+    }
+
+    /// Set the `FILE_ATTRIBUTE_*` flags stored in `$STANDARD_INFORMATION` and `$FILE_NAME`.
+    pub fn set_file_attributes(&mut self, record_number: u64, file_attributes: u32) -> &mut Self {
+        if let Some(record) = self.record_mut(record_number) {
+            record.file_attributes = file_attributes;
+        }
+        self
+    }
+
+    /// Give the record a non-resident unnamed `$DATA` attribute of `real_size` bytes, stored in
+    /// `(length_clusters, lcn)` runs where a `None` LCN is a sparse run.
+    pub fn set_non_resident_data(
+        &mut self,
+        record_number: u64,
+        runs: &[(u64, Option<u64>)],
+        real_size: u64,
+    ) -> &mut Self {
+        if let Some(record) = self.record_mut(record_number) {
+            record.data = Some(SyntheticData {
+                runs: runs.to_vec(),
+                real_size,
+                compressed_size: None,
+            });
+        }
+        self
+    }
+
+    /// Like [`Self::set_non_resident_data`], but flagged NTFS-compressed with
+    /// `compressed_size` bytes on disk.
+    pub fn set_compressed_data(
+        &mut self,
+        record_number: u64,
+        runs: &[(u64, Option<u64>)],
+        real_size: u64,
+        compressed_size: u64,
+    ) -> &mut Self {
+        if let Some(record) = self.record_mut(record_number) {
+            record.data = Some(SyntheticData {
+                runs: runs.to_vec(),
+                real_size,
+                compressed_size: Some(compressed_size),
+            });
+        }
+        self
+    }
+
+    /// Clear the in-use flag so the record reads back as deleted.
+    pub fn mark_deleted(&mut self, record_number: u64) -> &mut Self {
+        if let Some(record) = self.record_mut(record_number) {
+            record.in_use = false;
+        }
+        self
+    }
+
+    /// Number of records, including the reserved system records.
+    #[must_use]
+    pub fn record_count(&self) -> usize {
+        self.records.len()
+    }
+
+    fn record_mut(&mut self, record_number: u64) -> Option<&mut SyntheticRecord> {
+        usize::try_from(record_number)
+            .ok()
+            .and_then(|index| self.records.get_mut(index))
+            .and_then(Option::as_mut)
+    }
+
+    fn push(&mut self, parent: u64, name: &str, is_directory: bool) -> u64 {
+        assert!(
+            name.encode_utf16().count() <= usize::from(u8::MAX),
+            "FILE_NAME holds at most 255 UTF-16 code units, got {name:?}"
+        );
+        self.records.push(Some(SyntheticRecord {
+            parent,
+            name: name.to_owned(),
+            is_directory,
+            in_use: true,
+            size: 0,
+            file_attributes: 0,
+            data: None,
+        }));
+        (self.records.len() - 1) as u64
+    }
+
+    /// Encode every record, protecting each sector with the update sequence value.
+    #[must_use]
+    pub fn build(&self) -> Vec<u8> {
+        let mut bytes = vec![0_u8; self.records.len() * SYNTHETIC_RECORD_SIZE];
+        for (record_number, (record, slot)) in self
+            .records
+            .iter()
+            .zip(bytes.chunks_exact_mut(SYNTHETIC_RECORD_SIZE))
+            .enumerate()
+        {
+            write_record(slot, record_number as u64, record.as_ref());
+        }
+        bytes
+    }
+}
+
+/// A flat MFT with `file_count` files spread over directories of `files_per_directory`
+/// entries each, all directories sitting directly under the root.
+#[must_use]
+pub fn synthetic_mft(file_count: usize, files_per_directory: usize) -> Vec<u8> {
+    let mut builder = SyntheticMftBuilder::new();
+    let files_per_directory = files_per_directory.max(1);
+    let mut directory = SYNTHETIC_ROOT_RECORD;
+    for index in 0..file_count {
+        if index % files_per_directory == 0 {
+            directory = builder.directory(
+                SYNTHETIC_ROOT_RECORD,
+                &format!("dir{:06}", index / files_per_directory),
+            );
+        }
+        builder.file(directory, &format!("file{index:08}.txt"));
+    }
+    builder.build()
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "record layout values fit their on-disk field widths"
+)]
+fn write_record(slot: &mut [u8], record_number: u64, record: Option<&SyntheticRecord>) {
+    slot[0..4].copy_from_slice(b"FILE");
+    slot[0x04..0x06].copy_from_slice(&(UPDATE_SEQUENCE_OFFSET as u16).to_le_bytes());
+    let update_sequence_count = SYNTHETIC_RECORD_SIZE / SECTOR_SIZE + 1;
+    slot[0x06..0x08].copy_from_slice(&(update_sequence_count as u16).to_le_bytes());
+    slot[0x10..0x12].copy_from_slice(&1_u16.to_le_bytes());
+    slot[0x14..0x16].copy_from_slice(&(FIRST_ATTRIBUTE_OFFSET as u16).to_le_bytes());
+    slot[0x1C..0x20].copy_from_slice(&(SYNTHETIC_RECORD_SIZE as u32).to_le_bytes());
+    slot[0x2C..0x30].copy_from_slice(&(record_number as u32).to_le_bytes());
+
+    let mut offset = FIRST_ATTRIBUTE_OFFSET;
+    let mut flags = 0;
+    if let Some(record) = record {
+        if record.in_use {
+            flags |= RECORD_FLAG_IN_USE;
+        }
+        if record.is_directory {
+            flags |= RECORD_FLAG_DIRECTORY;
+        }
+        slot[0x12..0x14].copy_from_slice(&1_u16.to_le_bytes());
+
+        let mut standard_information = [0_u8; STANDARD_INFORMATION_LEN];
+        for timestamp in standard_information[..0x20].chunks_exact_mut(8) {
+            timestamp.copy_from_slice(&SYNTHETIC_TIMESTAMP.to_le_bytes());
+        }
+        standard_information[0x20..0x24].copy_from_slice(&record.file_attributes.to_le_bytes());
+        offset += write_resident_attribute(
+            &mut slot[offset..],
+            ATTRIBUTE_TYPE_STANDARD_INFORMATION,
+            0,
+            &standard_information,
+        );
+
+        let name: Vec<u16> = record.name.encode_utf16().collect();
+        let mut file_name = vec![0_u8; FILE_NAME_NAME_OFFSET + name.len() * 2];
+        let parent_reference = record.parent | (1_u64 << 48);
+        file_name[0x00..0x08].copy_from_slice(&parent_reference.to_le_bytes());
+        for timestamp in file_name[0x08..0x28].chunks_exact_mut(8) {
+            timestamp.copy_from_slice(&SYNTHETIC_TIMESTAMP.to_le_bytes());
+        }
+        let allocated_size = record.size.next_multiple_of(SYNTHETIC_CLUSTER_SIZE);
+        file_name[0x28..0x30].copy_from_slice(&allocated_size.to_le_bytes());
+        file_name[0x30..0x38].copy_from_slice(&record.size.to_le_bytes());
+        let mut file_name_flags = record.file_attributes;
+        if record.is_directory {
+            file_name_flags |= FILE_NAME_FLAG_DIRECTORY;
+        }
+        file_name[0x38..0x3C].copy_from_slice(&file_name_flags.to_le_bytes());
+        file_name[0x40] = name.len() as u8;
+        file_name[0x41] = FILE_NAME_NAMESPACE_WIN32_AND_DOS;
+        for (unit, chunk) in name
+            .iter()
+            .zip(file_name[FILE_NAME_NAME_OFFSET..].chunks_exact_mut(2))
+        {
+            chunk.copy_from_slice(&unit.to_le_bytes());
+        }
+        offset +=
+            write_resident_attribute(&mut slot[offset..], ATTRIBUTE_TYPE_FILE_NAME, 1, &file_name);
+
+        let mut next_attribute_id = 2_u16;
+        if let Some(data) = &record.data {
+            offset +=
+                write_non_resident_data_attribute(&mut slot[offset..], next_attribute_id, data);
+            next_attribute_id += 1;
+        }
+        slot[0x28..0x2A].copy_from_slice(&next_attribute_id.to_le_bytes());
+    }
+    slot[offset..offset + 4].copy_from_slice(&ATTRIBUTE_TYPE_END.to_le_bytes());
+    slot[0x16..0x18].copy_from_slice(&flags.to_le_bytes());
+    slot[0x18..0x1C].copy_from_slice(&((offset + 8) as u32).to_le_bytes());
+
+    let usn = UPDATE_SEQUENCE_VALUE.to_le_bytes();
+    slot[UPDATE_SEQUENCE_OFFSET..UPDATE_SEQUENCE_OFFSET + 2].copy_from_slice(&usn);
+    for sector in 0..update_sequence_count - 1 {
+        let tail = (sector + 1) * SECTOR_SIZE - 2;
+        let saved = UPDATE_SEQUENCE_OFFSET + 2 + sector * 2;
+        slot.copy_within(tail..tail + 2, saved);
+        slot[tail..tail + 2].copy_from_slice(&usn);
+    }
+}
+
+/// Write a resident attribute header followed by `value`, returning the attribute length.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "record layout values fit their on-disk field widths"
+)]
+fn write_resident_attribute(
+    attribute: &mut [u8],
+    attribute_type: u32,
+    attribute_id: u16,
+    value: &[u8],
+) -> usize {
+    let attribute_len = (RESIDENT_HEADER_LEN + value.len()).next_multiple_of(8);
+    attribute[0x00..0x04].copy_from_slice(&attribute_type.to_le_bytes());
+    attribute[0x04..0x08].copy_from_slice(&(attribute_len as u32).to_le_bytes());
+    attribute[0x0E..0x10].copy_from_slice(&attribute_id.to_le_bytes());
+    attribute[0x10..0x14].copy_from_slice(&(value.len() as u32).to_le_bytes());
+    attribute[0x14..0x16].copy_from_slice(&(RESIDENT_HEADER_LEN as u16).to_le_bytes());
+    if attribute_type == ATTRIBUTE_TYPE_FILE_NAME {
+        attribute[0x16] = 1;
+    }
+    attribute[RESIDENT_HEADER_LEN..RESIDENT_HEADER_LEN + value.len()].copy_from_slice(value);
+    attribute_len
+}
+
+/// Write a non-resident unnamed `$DATA` attribute, returning the attribute length.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "record layout values fit their on-disk field widths"
+)]
+fn write_non_resident_data_attribute(
+    attribute: &mut [u8],
+    attribute_id: u16,
+    data: &SyntheticData,
+) -> usize {
+    let run_list = encode_run_list(&data.runs);
+    let header_len = if data.compressed_size.is_some() {
+        COMPRESSED_NON_RESIDENT_HEADER_LEN
+    } else {
+        NON_RESIDENT_HEADER_LEN
+    };
+    let attribute_len = (header_len + run_list.len()).next_multiple_of(8);
+    let cluster_count = data.runs.iter().map(|(length, _)| length).sum::<u64>();
+    attribute[0x00..0x04].copy_from_slice(&ATTRIBUTE_TYPE_DATA.to_le_bytes());
+    attribute[0x04..0x08].copy_from_slice(&(attribute_len as u32).to_le_bytes());
+    attribute[0x08] = 1;
+    attribute[0x0A..0x0C].copy_from_slice(&(header_len as u16).to_le_bytes());
+    attribute[0x0E..0x10].copy_from_slice(&attribute_id.to_le_bytes());
+    attribute[0x18..0x20].copy_from_slice(&cluster_count.saturating_sub(1).to_le_bytes());
+    attribute[0x20..0x22].copy_from_slice(&(header_len as u16).to_le_bytes());
+    attribute[0x28..0x30].copy_from_slice(&(cluster_count * SYNTHETIC_CLUSTER_SIZE).to_le_bytes());
+    attribute[0x30..0x38].copy_from_slice(&data.real_size.to_le_bytes());
+    attribute[0x38..0x40].copy_from_slice(&data.real_size.to_le_bytes());
+    if let Some(compressed_size) = data.compressed_size {
+        attribute[0x0C..0x0E].copy_from_slice(&ATTRIBUTE_FLAG_COMPRESSED.to_le_bytes());
+        // Compression units of 2^4 clusters, as NTFS uses.
+        attribute[0x22..0x24].copy_from_slice(&4_u16.to_le_bytes());
+        attribute[0x40..0x48].copy_from_slice(&compressed_size.to_le_bytes());
+    }
+    attribute[header_len..header_len + run_list.len()].copy_from_slice(&run_list);
+    attribute_len
+}
+
+/// Encode `(length_clusters, lcn)` runs as an NTFS run list; a `None` LCN is a sparse run.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    reason = "run lengths and LCNs are small enough for their encoded widths"
+)]
+fn encode_run_list(runs: &[(u64, Option<u64>)]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    let mut previous_lcn = 0_i64;
+    for &(length_clusters, lcn) in runs {
+        let length_size = (u64::BITS - length_clusters.leading_zeros())
+            .div_ceil(8)
+            .max(1) as usize;
+        let delta = lcn.map(|lcn| lcn as i64 - previous_lcn);
+        let offset_size = delta.map_or(0, |delta| {
+            (1..8)
+                .find(|size| {
+                    let limit = 1_i64 << (size * 8 - 1);
+                    (-limit..limit).contains(&delta)
+                })
+                .unwrap_or(8)
+        });
+        encoded.push(((offset_size << 4) | length_size) as u8);
+        encoded.extend_from_slice(&length_clusters.to_le_bytes()[..length_size]);
+        if let Some(delta) = delta {
+            encoded.extend_from_slice(&delta.to_le_bytes()[..offset_size]);
+            previous_lcn += delta;
+        }
+    }
+    encoded.push(0);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SYNTHETIC_RECORD_SIZE;
+    use super::SYNTHETIC_ROOT_RECORD;
+    use super::SYNTHETIC_TIMESTAMP;
+    use super::SyntheticMftBuilder;
+    use super::synthetic_mft;
+    use crate::mft::fast_entry::collect_filenames;
+    use crate::mft::mft_file::MftFile;
+    use crate::mft::path_resolve::resolve_paths_all_parallel;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    #[test]
+    fn synthetic_mft_parses_every_generated_name() -> eyre::Result<()> {
+        let bytes = synthetic_mft(100, 10);
+        assert_eq!(bytes.len(), (16 + 10 + 100) * SYNTHETIC_RECORD_SIZE);
+
+        let mft = MftFile::from_vec(bytes)?;
+        let file_names = collect_filenames(&mft);
+        assert_eq!(file_names.x30_count(), 12 + 10 + 100);
+        Ok(())
+    }
+
+    #[test]
+    fn built_tree_resolves_to_expected_paths() -> eyre::Result<()> {
+        let mut builder = SyntheticMftBuilder::new();
+        let docs = builder.directory(SYNTHETIC_ROOT_RECORD, "docs");
+        let notes = builder.directory(docs, "notes");
+        let readme = builder.file_with_size(docs, "readme.md", 1234);
+        let todo = builder.file(notes, "todo.txt");
+        let hidden = builder.file(SYNTHETIC_ROOT_RECORD, "hidden.sys");
+        builder.set_file_attributes(hidden, 0x2).mark_deleted(todo);
+
+        let mft = MftFile::from_vec(builder.build())?;
+        let file_names = collect_filenames(&mft);
+        let paths = resolve_paths_all_parallel(&file_names, Path::new("C:\\"))?;
+        let path_of = |record: u64| {
+            usize::try_from(record)
+                .ok()
+                .and_then(|record| paths.primary_path(record))
+                .map(Path::to_path_buf)
+        };
+        assert_eq!(path_of(readme), Some(PathBuf::from("C:\\docs\\readme.md")));
+        assert_eq!(
+            path_of(todo),
+            Some(PathBuf::from("C:\\docs\\notes\\todo.txt"))
+        );
+        assert_eq!(path_of(hidden), Some(PathBuf::from("C:\\hidden.sys")));
+
+        let readme_record = mft.record_at(readme)?;
+        assert_eq!(
+            readme_record.first_file_name().map(|name| name.real_size),
+            Some(1234)
+        );
+        let hidden_info = mft.record_at(hidden)?.standard_info();
+        assert_eq!(hidden_info.map(|info| info.file_attributes), Some(0x2));
+        assert_eq!(
+            hidden_info.map(|info| info.modified),
+            Some(SYNTHETIC_TIMESTAMP)
+        );
+        assert!(mft.record_at(todo)?.is_deleted());
+        assert!(!readme_record.is_deleted());
+        Ok(())
+    }
+}
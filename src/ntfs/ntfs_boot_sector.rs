@@ -1,6 +1,7 @@
 use crate::mft::mft_location::MftLocationOnDisk;
 use crate::ntfs::ntfs_drive_handle::NtfsDriveHandle;
 use crate::windows_utils::storage::HandleReadExt;
+use eyre::ensure;
 use tracing::instrument;
 use uom::si::information::byte;
 use uom::si::usize::Information;
@@ -13,17 +14,56 @@ impl NtfsBootSector {
     ///
     /// # Errors
     ///
-    /// Returns an error if the drive handle cannot be read.
+    /// Returns an error if the drive handle cannot be read or the boot sector fails [`Self::validate`].
     // mfti[impl boot-sector.reads-512-byte-sector]
     #[instrument(skip_all)]
     pub fn try_from_handle(drive_handle: &NtfsDriveHandle) -> eyre::Result<Self> {
-        Ok(NtfsBootSector {
+        let rtn = NtfsBootSector {
             data: {
                 let mut data = [0u8; 512];
                 drive_handle.try_read_exact(0, data.as_mut_slice())?;
                 data
             },
-        })
+        };
+        rtn.validate()?;
+        Ok(rtn)
+    }
+
+    /// Sanity-check the geometry fields before they are used to seek to the MFT.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first implausible field: `bytes_per_sector` must be a power
+    /// of two in `[256, 4096]`, `sectors_per_cluster` a non-zero power of two, the MFT
+    /// must start inside the volume described by `total_sectors`, and the MFT record size
+    /// code must decode to a power of two in `[256, 65536]`.
+    pub fn validate(&self) -> eyre::Result<()> {
+        let bytes_per_sector = self.bytes_per_sector();
+        ensure!(
+            bytes_per_sector.is_power_of_two() && (256..=4096).contains(&bytes_per_sector),
+            "Implausible NTFS boot sector: bytes_per_sector is {bytes_per_sector}, expected a power of two in [256, 4096]"
+        );
+        let sectors_per_cluster = self.sectors_per_cluster();
+        ensure!(
+            sectors_per_cluster.is_power_of_two(),
+            "Implausible NTFS boot sector: sectors_per_cluster is {sectors_per_cluster}, expected a non-zero power of two"
+        );
+        let total_sectors = self.total_sectors();
+        let mft_sector = self
+            .mft_cluster_number()
+            .checked_mul(u64::from(sectors_per_cluster));
+        ensure!(
+            mft_sector.is_some_and(|mft_sector| mft_sector < total_sectors),
+            "Implausible NTFS boot sector: mft_cluster_number {} lies beyond the volume ({total_sectors} sectors of {bytes_per_sector} bytes)",
+            self.mft_cluster_number()
+        );
+        let mft_record_code = self.clusters_per_mft_record_code();
+        ensure!(
+            record_size_from_code(mft_record_code, self.bytes_per_cluster())
+                .is_some_and(|size| size.is_power_of_two() && (256..=65536).contains(&size)),
+            "Implausible NTFS boot sector: MFT record size code is {mft_record_code}, expected a size that is a power of two in [256, 65536]"
+        );
+        Ok(())
     }
 
     #[must_use]
@@ -36,6 +76,26 @@ impl NtfsBootSector {
         self.data[0x0d]
     }
 
+    #[must_use]
+    pub fn total_sectors(&self) -> u64 {
+        u64::from_le_bytes([
+            self.data[0x28],
+            self.data[0x29],
+            self.data[0x2a],
+            self.data[0x2b],
+            self.data[0x2c],
+            self.data[0x2d],
+            self.data[0x2e],
+            self.data[0x2f],
+        ])
+    }
+
+    /// Number of whole clusters in the volume.
+    #[must_use]
+    pub fn cluster_count(&self) -> u64 {
+        self.total_sectors() / u64::from(self.sectors_per_cluster().max(1))
+    }
+
     #[must_use]
     pub fn mft_cluster_number(&self) -> u64 {
         u64::from_le_bytes([
@@ -50,48 +110,140 @@ impl NtfsBootSector {
         ])
     }
 
+    /// Cluster holding `$MFTMirr`, the copy of the first four `$MFT` records.
+    #[must_use]
+    pub fn mft_mirror_cluster_number(&self) -> u64 {
+        u64::from_le_bytes([
+            self.data[0x38],
+            self.data[0x39],
+            self.data[0x3a],
+            self.data[0x3b],
+            self.data[0x3c],
+            self.data[0x3d],
+            self.data[0x3e],
+            self.data[0x3f],
+        ])
+    }
+
+    /// Signed MFT record size code at 0x40; see [`Self::bytes_per_mft_record`].
+    #[must_use]
+    pub fn clusters_per_mft_record_code(&self) -> i8 {
+        i8::from_le_bytes([self.data[0x40]])
+    }
+
+    /// Signed index record size code at 0x44, encoded like the MFT record size code.
+    #[must_use]
+    pub fn clusters_per_index_record_code(&self) -> i8 {
+        i8::from_le_bytes([self.data[0x44]])
+    }
+
+    #[must_use]
+    pub fn volume_serial_number(&self) -> u64 {
+        u64::from_le_bytes([
+            self.data[0x48],
+            self.data[0x49],
+            self.data[0x4a],
+            self.data[0x4b],
+            self.data[0x4c],
+            self.data[0x4d],
+            self.data[0x4e],
+            self.data[0x4f],
+        ])
+    }
+
     #[must_use]
     pub fn bytes_per_cluster(&self) -> usize {
         self.bytes_per_sector() as usize * self.sectors_per_cluster() as usize
     }
 
-    /// Returns the size of a single MFT file record as Information (bytes).
-    /// Per NTFS spec, at offset 0x40 there is a signed byte:
+    /// Size in bytes of a single MFT file record, decoded from the signed code at 0x40:
     /// - If negative, the record size is 2^abs(value) bytes.
     /// - If non-negative, it is `clusters_per_file_record` * `bytes_per_cluster`.
     ///
     /// # Panics
     ///
-    /// Panics if the exponent or cluster count fails to fit into `usize` (should not happen for valid sectors).
+    /// Panics if the decoded size overflows `usize`, which [`Self::validate`] rules out.
     // mfti[impl boot-sector.file-record-size-encoding]
     #[must_use]
+    pub fn bytes_per_mft_record(&self) -> usize {
+        record_size_from_code(
+            self.clusters_per_mft_record_code(),
+            self.bytes_per_cluster(),
+        )
+        .expect("MFT record size fits in usize")
+    }
+
+    /// Size in bytes of a single index (`$INDEX_ALLOCATION`) record, decoded like
+    /// [`Self::bytes_per_mft_record`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the decoded size overflows `usize`.
+    #[must_use]
+    pub fn bytes_per_index_record(&self) -> usize {
+        record_size_from_code(
+            self.clusters_per_index_record_code(),
+            self.bytes_per_cluster(),
+        )
+        .expect("index record size fits in usize")
+    }
+
+    /// Returns the size of a single MFT file record as Information (bytes).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Self::bytes_per_mft_record`].
+    #[must_use]
     pub fn file_record_size(&self) -> Information {
-        let val = i8::from_ne_bytes([self.data[0x40]]);
-        let bytes = if val < 0 {
-            let shift =
-                usize::try_from(-isize::from(val)).expect("record size exponent fits in usize");
-            (1usize) << shift
-        } else {
-            let cluster_count = usize::try_from(val).expect("cluster count fits in usize");
-            cluster_count * self.bytes_per_cluster()
-        };
-        Information::new::<byte>(bytes)
+        Information::new::<byte>(self.bytes_per_mft_record())
     }
 
     #[must_use]
     pub fn mft_location(&self) -> MftLocationOnDisk {
         self.into()
     }
+
+    #[must_use]
+    pub fn mft_mirror_location(&self) -> MftLocationOnDisk {
+        MftLocationOnDisk::from_cluster(self.mft_mirror_cluster_number(), self.bytes_per_cluster())
+    }
+}
+
+/// Decode a signed clusters-per-record code: negative values mean 2^|code| bytes,
+/// non-negative ones a whole number of clusters. `None` if the size overflows.
+fn record_size_from_code(code: i8, bytes_per_cluster: usize) -> Option<usize> {
+    if code < 0 {
+        1usize.checked_shl(u32::from(code.unsigned_abs()))
+    } else {
+        usize::from(code.unsigned_abs()).checked_mul(bytes_per_cluster)
+    }
 }
 
 impl std::fmt::Debug for NtfsBootSector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes_per_cluster = self.bytes_per_cluster();
         f.debug_struct("NtfsBootSector")
             .field("bytes_per_sector", &self.bytes_per_sector())
             .field("sectors_per_cluster", &self.sectors_per_cluster())
+            .field("total_sectors", &self.total_sectors())
+            .field("cluster_count", &self.cluster_count())
             .field("mft_cluster_number", &self.mft_cluster_number())
-            .field("file_record_size", &self.file_record_size())
-            .field("mft_location", &self.mft_location())
+            .field(
+                "mft_mirror_cluster_number",
+                &self.mft_mirror_cluster_number(),
+            )
+            .field(
+                "bytes_per_mft_record",
+                &record_size_from_code(self.clusters_per_mft_record_code(), bytes_per_cluster),
+            )
+            .field(
+                "bytes_per_index_record",
+                &record_size_from_code(self.clusters_per_index_record_code(), bytes_per_cluster),
+            )
+            .field(
+                "volume_serial_number",
+                &format_args!("{:016X}", self.volume_serial_number()),
+            )
             .finish()
     }
 }
@@ -139,4 +291,69 @@ mod tests {
         assert_eq!(bs.bytes_per_cluster(), 2048);
         assert_eq!(bs.file_record_size().get::<byte>(), 4096);
     }
+
+    #[test]
+    fn representative_boot_sector_parses_full_geometry() {
+        let mut data = [0u8; 512];
+        data[0x00..0x03].copy_from_slice(&[0xEB, 0x52, 0x90]);
+        data[0x03..0x0b].copy_from_slice(b"NTFS    ");
+        data[0x0b..0x0d].copy_from_slice(&512u16.to_le_bytes());
+        data[0x0d] = 8;
+        data[0x15] = 0xF8;
+        data[0x28..0x30].copy_from_slice(&0x0773_FFFFu64.to_le_bytes());
+        data[0x30..0x38].copy_from_slice(&0xC_0000u64.to_le_bytes());
+        data[0x38..0x40].copy_from_slice(&2u64.to_le_bytes());
+        data[0x40] = 0xF6; // -10 => 1 KiB records
+        data[0x44] = 0x01; // one cluster per index record
+        data[0x48..0x50].copy_from_slice(&0x1234_5678_9ABC_DEF0u64.to_le_bytes());
+        data[0x1fe..0x200].copy_from_slice(&[0x55, 0xAA]);
+        let bs = NtfsBootSector { data };
+
+        bs.validate().unwrap();
+        assert_eq!(bs.bytes_per_sector(), 512);
+        assert_eq!(bs.sectors_per_cluster(), 8);
+        assert_eq!(bs.bytes_per_cluster(), 4096);
+        assert_eq!(bs.total_sectors(), 0x0773_FFFF);
+        assert_eq!(bs.cluster_count(), 0x0773_FFFF / 8);
+        assert_eq!(bs.mft_cluster_number(), 0xC_0000);
+        assert_eq!(bs.mft_mirror_cluster_number(), 2);
+        assert_eq!(bs.clusters_per_mft_record_code(), -10);
+        assert_eq!(bs.bytes_per_mft_record(), 1024);
+        assert_eq!(bs.clusters_per_index_record_code(), 1);
+        assert_eq!(bs.bytes_per_index_record(), 4096);
+        assert_eq!(bs.volume_serial_number(), 0x1234_5678_9ABC_DEF0);
+        assert_eq!(bs.file_record_size().get::<byte>(), 1024);
+    }
+
+    #[test]
+    fn implausible_mft_record_size_code_fails_validation() {
+        let mut bs = mk_boot_sector(512, 8, 100, -100);
+        bs.data[0x28..0x30].copy_from_slice(&1_000_000u64.to_le_bytes());
+        let error = bs
+            .validate()
+            .expect_err("a 2^100 byte record must be rejected");
+        assert!(error.to_string().contains("MFT record size code is -100"));
+    }
+
+    #[test]
+    fn zeroed_boot_sector_fails_validation() {
+        let bs = NtfsBootSector { data: [0u8; 512] };
+        let error = bs
+            .validate()
+            .expect_err("zeroed boot sector must be rejected");
+        assert!(error.to_string().contains("bytes_per_sector is 0"));
+    }
+
+    #[test]
+    fn mft_beyond_volume_fails_validation() {
+        let mut bs = mk_boot_sector(512, 8, 1_000, -10);
+        bs.data[0x28..0x30].copy_from_slice(&4_000u64.to_le_bytes());
+        let error = bs
+            .validate()
+            .expect_err("MFT past the end must be rejected");
+        assert!(error.to_string().contains("mft_cluster_number 1000"));
+
+        bs.data[0x28..0x30].copy_from_slice(&1_000_000u64.to_le_bytes());
+        bs.validate().unwrap();
+    }
 }
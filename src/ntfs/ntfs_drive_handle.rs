@@ -1,10 +1,30 @@
+use crate::windows_utils::string::EasyPCWSTR;
 use eyre::Context;
 use eyre::eyre;
 use std::ops::Deref;
+use tracing::debug;
+use windows::Win32::Foundation::ERROR_MORE_DATA;
+use windows::Win32::Foundation::ERROR_NO_MORE_FILES;
 use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Foundation::MAX_PATH;
+use windows::Win32::Storage::FileSystem::CreateFileW;
+use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+use windows::Win32::Storage::FileSystem::FILE_SHARE_DELETE;
+use windows::Win32::Storage::FileSystem::FILE_SHARE_READ;
+use windows::Win32::Storage::FileSystem::FILE_SHARE_WRITE;
+use windows::Win32::Storage::FileSystem::FindFirstVolumeW;
+use windows::Win32::Storage::FileSystem::FindNextVolumeW;
+use windows::Win32::Storage::FileSystem::FindVolumeClose;
+use windows::Win32::Storage::FileSystem::GetVolumeInformationW;
+use windows::Win32::Storage::FileSystem::GetVolumePathNamesForVolumeNameW;
+use windows::Win32::Storage::FileSystem::OPEN_EXISTING;
 use windows::Win32::System::IO::DeviceIoControl;
+use windows::Win32::System::Ioctl::DISK_EXTENT;
 use windows::Win32::System::Ioctl::FSCTL_GET_NTFS_VOLUME_DATA;
+use windows::Win32::System::Ioctl::IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS;
 use windows::Win32::System::Ioctl::NTFS_VOLUME_DATA_BUFFER;
+use windows::Win32::System::Ioctl::VOLUME_DISK_EXTENTS;
+use windows::core::HRESULT;
 use windows::core::Owned;
 
 #[derive(Debug)]
@@ -75,3 +95,208 @@ fn validate_ntfs_filesystem(drive_handle: HANDLE) -> eyre::Result<()> {
         "Drive does not appear to be using NTFS filesystem. FSCTL_GET_NTFS_VOLUME_DATA failed. MFT dumping is only supported on NTFS volumes."
     ))
 }
+
+/// An NTFS volume discovered by [`enumerate_ntfs_volumes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeInfo {
+    /// Volume GUID path with a trailing backslash, e.g. `\\?\Volume{...}\`.
+    pub volume_guid_path: String,
+    /// Drive letters the volume is mounted at. Empty for volumes that are only
+    /// reachable through a mount point or the GUID path.
+    pub drive_letters: Vec<char>,
+    /// Every mount path of the volume, including folder mount points.
+    pub mount_paths: Vec<String>,
+    /// Physical disk number of the first extent, if the volume reports extents.
+    pub disk_number: Option<u32>,
+    /// Byte offset of the first extent on its disk, if the volume reports extents.
+    pub starting_offset: Option<u64>,
+}
+
+/// Enumerate every NTFS volume on the system, including volumes without a drive letter.
+///
+/// Volumes that cannot be inspected (e.g. ejected media) are skipped with a debug log.
+///
+/// # Errors
+///
+/// Returns an error if volume enumeration itself fails.
+pub fn enumerate_ntfs_volumes() -> eyre::Result<Vec<VolumeInfo>> {
+    let mut volume_name = [0u16; MAX_PATH as usize];
+    // SAFETY: the buffer is a valid, writable UTF-16 slice for the duration of the call.
+    let find_handle =
+        unsafe { FindFirstVolumeW(&mut volume_name) }.wrap_err("FindFirstVolumeW failed")?;
+
+    let mut rtn = Vec::new();
+    let result = loop {
+        let volume_guid_path = String::from_utf16_lossy(
+            &volume_name[..volume_name.iter().position(|&c| c == 0).unwrap_or(0)],
+        );
+        match inspect_volume(&volume_guid_path) {
+            Ok(Some(info)) => rtn.push(info),
+            Ok(None) => {}
+            Err(error) => {
+                debug!(volume = %volume_guid_path, ?error, "Skipping volume that could not be inspected");
+            }
+        }
+
+        // SAFETY: `find_handle` came from FindFirstVolumeW and has not been closed yet.
+        match unsafe { FindNextVolumeW(find_handle, &mut volume_name) } {
+            Ok(()) => {}
+            Err(error) if error.code() == HRESULT::from_win32(ERROR_NO_MORE_FILES.0) => {
+                break Ok(rtn);
+            }
+            Err(error) => break Err(eyre::Report::new(error).wrap_err("FindNextVolumeW failed")),
+        }
+    };
+
+    // SAFETY: `find_handle` is a live volume search handle and is closed exactly once.
+    unsafe { FindVolumeClose(find_handle) }.wrap_err("FindVolumeClose failed")?;
+    result
+}
+
+/// Describe `volume_guid_path`, returning `None` when the volume is not NTFS.
+fn inspect_volume(volume_guid_path: &str) -> eyre::Result<Option<VolumeInfo>> {
+    let root = volume_guid_path.easy_pcwstr()?;
+    let mut filesystem_name = [0u16; MAX_PATH as usize + 1];
+    // SAFETY: `root` is a null-terminated UTF-16 path ending in a backslash and the
+    // filesystem name buffer is writable for the duration of the call.
+    unsafe {
+        GetVolumeInformationW(
+            root.as_ref(),
+            None,
+            None,
+            None,
+            None,
+            Some(&mut filesystem_name),
+        )
+    }
+    .wrap_err("GetVolumeInformationW failed")?;
+    let filesystem_name = String::from_utf16_lossy(
+        &filesystem_name[..filesystem_name.iter().position(|&c| c == 0).unwrap_or(0)],
+    );
+    if filesystem_name != "NTFS" {
+        return Ok(None);
+    }
+
+    let mut path_names = vec![0u16; MAX_PATH as usize];
+    let mut required_len = 0u32;
+    loop {
+        // SAFETY: `root` is a valid volume GUID path and `path_names` is writable with the
+        // length passed via the slice.
+        let result = unsafe {
+            GetVolumePathNamesForVolumeNameW(
+                root.as_ref(),
+                Some(&mut path_names),
+                &raw mut required_len,
+            )
+        };
+        match result {
+            Ok(()) => break,
+            Err(error) if error.code() == HRESULT::from_win32(ERROR_MORE_DATA.0) => {
+                path_names.resize(required_len as usize, 0);
+            }
+            Err(error) => {
+                return Err(
+                    eyre::Report::new(error).wrap_err("GetVolumePathNamesForVolumeNameW failed")
+                );
+            }
+        }
+    }
+    // The buffer is a list of null-terminated strings ending with an empty string.
+    let mount_paths = path_names
+        .split(|&c| c == 0)
+        .take_while(|path| !path.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect::<Vec<_>>();
+    let drive_letters = mount_paths
+        .iter()
+        .filter_map(|path| match path.as_bytes() {
+            [letter, b':', b'\\'] if letter.is_ascii_alphabetic() => {
+                Some(char::from(*letter).to_ascii_uppercase())
+            }
+            _ => None,
+        })
+        .collect();
+
+    let (disk_number, starting_offset) = match query_first_disk_extent(volume_guid_path) {
+        Ok(extent) => (
+            Some(extent.DiskNumber),
+            Some(u64::try_from(extent.StartingOffset).unwrap_or_default()),
+        ),
+        Err(error) => {
+            debug!(volume = %volume_guid_path, ?error, "Volume did not report disk extents");
+            (None, None)
+        }
+    };
+
+    Ok(Some(VolumeInfo {
+        volume_guid_path: volume_guid_path.to_owned(),
+        drive_letters,
+        mount_paths,
+        disk_number,
+        starting_offset,
+    }))
+}
+
+/// Query the first disk extent of a volume via `IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS`.
+fn query_first_disk_extent(volume_guid_path: &str) -> eyre::Result<DISK_EXTENT> {
+    // CreateFileW opens the volume itself only when the trailing backslash is removed.
+    let device_path = volume_guid_path.trim_end_matches('\\').easy_pcwstr()?;
+    // SAFETY: `device_path` is a valid, null-terminated UTF-16 string. Zero desired access is
+    // sufficient for volume IOCTLs and does not require elevation.
+    let handle = unsafe {
+        Owned::new(CreateFileW(
+            device_path.as_ref(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES::default(),
+            None,
+        )?)
+    };
+
+    let mut extents = VOLUME_DISK_EXTENTS::default();
+    let mut bytes_returned = 0u32;
+    let buffer_size = u32::try_from(std::mem::size_of::<VOLUME_DISK_EXTENTS>())
+        .expect("VOLUME_DISK_EXTENTS fits in u32");
+    // SAFETY: the output struct is stack-allocated and `buffer_size` matches its size. A volume
+    // spanning several disks fails with ERROR_MORE_DATA, which is reported as an error.
+    unsafe {
+        DeviceIoControl(
+            *handle,
+            IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS,
+            None,
+            0,
+            Some((&raw mut extents).cast::<std::ffi::c_void>()),
+            buffer_size,
+            Some(&raw mut bytes_returned),
+            None,
+        )
+    }
+    .wrap_err("IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS failed")?;
+    Ok(extents.Extents[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::enumerate_ntfs_volumes;
+
+    #[cfg(windows)]
+    #[test]
+    fn enumerates_system_volume() -> eyre::Result<()> {
+        let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_owned());
+        let system_letter = system_drive
+            .chars()
+            .next()
+            .expect("SystemDrive is not empty")
+            .to_ascii_uppercase();
+
+        let volumes = enumerate_ntfs_volumes()?;
+        let system_volume = volumes
+            .iter()
+            .find(|volume| volume.drive_letters.contains(&system_letter))
+            .expect("system volume should be enumerated");
+        assert!(system_volume.volume_guid_path.starts_with(r"\\?\Volume{"));
+        Ok(())
+    }
+}
@@ -1,5 +1,243 @@
+use crate::windows_utils::invocation::to_args::ToArgs;
+use arbitrary::Arbitrary;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Context;
+use facet::Facet;
+use figue::{self as args};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::io;
+use std::io::BufWriter;
 use std::io::Write;
+use std::path::Path;
+
+/// Presentation-only transform applied to printed paths.
+#[derive(Facet, PartialEq, Eq, Debug, Arbitrary, Default, Clone, Copy)]
+#[facet(rename_all = "kebab-case")]
+pub struct PathStyle {
+    /// Path separator in printed paths
+    #[facet(args::named, default)]
+    pub slash: PathSlash,
+    /// Letter case of printed paths
+    #[facet(args::named, default)]
+    pub case: PathCase,
+}
+
+#[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[repr(u8)]
+#[strum(serialize_all = "kebab-case")]
+#[facet(rename_all = "kebab-case")]
+pub enum PathSlash {
+    #[default]
+    Back,
+    Forward,
+}
+
+#[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[repr(u8)]
+#[strum(serialize_all = "kebab-case")]
+#[facet(rename_all = "kebab-case")]
+pub enum PathCase {
+    #[default]
+    Keep,
+    Lower,
+}
+
+/// Destination for a command's results: stdout, or a file given by `--output-file`.
+#[derive(Facet, PartialEq, Eq, Debug, Arbitrary, Default, Clone)]
+#[facet(rename_all = "kebab-case")]
+pub struct OutputFile {
+    /// Write results to this file instead of stdout, creating parent directories as needed
+    #[facet(args::named)]
+    pub output_file: Option<String>,
+    /// Prepend a UTF-8 byte order mark to `--output-file` so Excel detects the encoding
+    #[facet(args::named, default)]
+    pub bom: bool,
+}
+
+impl OutputFile {
+    /// The UTF-8 encoding of U+FEFF.
+    pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+    /// Whether results go to stdout.
+    #[must_use]
+    pub fn is_stdout(&self) -> bool {
+        self.output_file.is_none()
+    }
+
+    /// Open the destination, truncating any existing file and writing the BOM when requested.
+    ///
+    /// Callers must flush the returned writer so buffered write errors are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--bom` is given without `--output-file`, or if the parent
+    /// directories or the file cannot be created.
+    pub fn open(&self) -> eyre::Result<Box<dyn Write>> {
+        let Some(output_file) = &self.output_file else {
+            eyre::ensure!(!self.bom, "--bom requires --output-file");
+            return Ok(Box::new(io::stdout().lock()));
+        };
+        let path = Path::new(output_file);
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let file = std::fs::File::create(path)
+            .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        if self.bom {
+            writer.write_all(Self::UTF8_BOM)?;
+        }
+        Ok(Box::new(writer))
+    }
+}
+
+impl ToArgs for OutputFile {
+    fn to_args(&self) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if let Some(output_file) = &self.output_file {
+            args.push("--output-file".into());
+            args.push(output_file.into());
+        }
+        if self.bom {
+            args.push("--bom".into());
+        }
+        args
+    }
+}
+
+/// Optional grouping of result paths under one header per drive.
+#[derive(Facet, PartialEq, Eq, Debug, Arbitrary, Default, Clone, Copy)]
+#[facet(rename_all = "kebab-case")]
+pub struct DriveGrouping {
+    /// Print results grouped under a `== C: (N matches) ==` header per drive, in drive letter order
+    #[facet(args::named, default)]
+    pub group_by_drive: bool,
+}
+
+impl DriveGrouping {
+    /// Write `rows` one per line under a header per drive, keeping their order within a drive.
+    ///
+    /// Paths without a drive letter are grouped under `?:`. Headers are colorized when
+    /// `colorize` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails.
+    pub fn write_groups<T, W, PathOf, Render>(
+        rows: &[T],
+        writer: &mut W,
+        colorize: bool,
+        path_of: PathOf,
+        mut render: Render,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        PathOf: Fn(&T) -> &str,
+        Render: FnMut(&T, &mut W) -> io::Result<()>,
+    {
+        let mut groups = BTreeMap::<char, Vec<&T>>::new();
+        for row in rows {
+            groups
+                .entry(drive_letter_of(path_of(row)))
+                .or_default()
+                .push(row);
+        }
+        for (index, (drive_letter, rows)) in groups.into_iter().enumerate() {
+            if index > 0 {
+                writeln!(writer)?;
+            }
+            let noun = if rows.len() == 1 { "match" } else { "matches" };
+            let header = format!("== {drive_letter}: ({} {noun}) ==", rows.len());
+            if colorize {
+                writeln!(writer, "{}", header.cyan().bold())?;
+            } else {
+                writeln!(writer, "{header}")?;
+            }
+            for row in rows {
+                render(row, writer)?;
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToArgs for DriveGrouping {
+    fn to_args(&self) -> Vec<OsString> {
+        if self.group_by_drive {
+            vec!["--group-by-drive".into()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The uppercase drive letter `path` starts with, or `?` when it has none.
+fn drive_letter_of(path: &str) -> char {
+    let mut chars = path.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => letter.to_ascii_uppercase(),
+        _ => '?',
+    }
+}
+
+impl PathStyle {
+    /// Restyle a resolved path string, borrowing it unchanged for the default style.
+    #[must_use]
+    pub fn apply<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        let mut rtn = Cow::Borrowed(path);
+        if self.slash == PathSlash::Forward && rtn.contains('\\') {
+            rtn = Cow::Owned(rtn.replace('\\', "/"));
+        }
+        if self.case == PathCase::Lower {
+            rtn = Cow::Owned(rtn.to_lowercase());
+        }
+        rtn
+    }
+}
+
+/// Per-drive match totals printed instead of paths by `--count-only`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriveMatchCounts {
+    counts: BTreeMap<char, usize>,
+}
+
+impl DriveMatchCounts {
+    pub fn add(&mut self, drive_letter: char, count: usize) {
+        *self
+            .counts
+            .entry(drive_letter.to_ascii_uppercase())
+            .or_default() += count;
+    }
+
+    /// Count one matched path against the drive letter it starts with, or `?` when it has none.
+    pub fn add_path(&mut self, path: &str) {
+        self.add(drive_letter_of(path), 1);
+    }
+
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Write one `D: count` line per drive followed by the grand total.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails.
+    pub fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        for (drive_letter, count) in &self.counts {
+            writeln!(writer, "{drive_letter}: {count}")?;
+        }
+        writeln!(writer, "total: {}", self.total())
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ResultListPresentation {
@@ -107,6 +345,12 @@ impl ResultListPresentation {
 
 #[cfg(test)]
 mod tests {
+    use super::DriveGrouping;
+    use super::DriveMatchCounts;
+    use super::OutputFile;
+    use super::PathCase;
+    use super::PathSlash;
+    use super::PathStyle;
     use super::ResultListPresentation;
     use std::io::Write;
 
@@ -151,4 +395,106 @@ mod tests {
         assert_eq!(String::from_utf8(output).unwrap(), "aa  cc\nbb  dd\n");
         Ok(())
     }
+
+    #[test]
+    fn drive_match_counts_write_per_drive_lines_and_total() -> std::io::Result<()> {
+        let mut counts = DriveMatchCounts::default();
+        counts.add_path(r"D:.txt");
+        counts.add_path(r"c:.txt");
+        counts.add_path(r"C:.txt");
+        counts.add('D', 2);
+        let mut output = Vec::new();
+
+        counts.write(&mut output)?;
+
+        assert_eq!(String::from_utf8(output).unwrap(), "C: 2\nD: 3\ntotal: 5\n");
+        Ok(())
+    }
+
+    #[test]
+    fn default_path_style_keeps_paths_unchanged() {
+        let styled = PathStyle::default().apply(r"C:\Users\Me\Notes.TXT");
+        assert!(matches!(styled, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(styled, r"C:\Users\Me\Notes.TXT");
+    }
+
+    #[test]
+    fn forward_slash_style_replaces_separators() {
+        let style = PathStyle {
+            slash: PathSlash::Forward,
+            ..PathStyle::default()
+        };
+        assert_eq!(
+            style.apply(r"C:\Users\Me\Notes.TXT"),
+            "C:/Users/Me/Notes.TXT"
+        );
+    }
+
+    #[test]
+    fn lower_case_style_lowercases_paths() {
+        let style = PathStyle {
+            case: PathCase::Lower,
+            ..PathStyle::default()
+        };
+        assert_eq!(
+            style.apply(r"C:\Users\Me\Notes.TXT"),
+            r"c:\users\me\notes.txt"
+        );
+    }
+
+    #[test]
+    fn grouped_results_list_each_drive_under_a_header_in_drive_order() -> std::io::Result<()> {
+        let rows = [r"D:\b", r"C:\a", r"d:\c", "relative", r"C:\z"];
+        let mut output = Vec::new();
+
+        DriveGrouping::write_groups(
+            &rows,
+            &mut output,
+            false,
+            |row| row,
+            |row, writer| write!(writer, "{row}"),
+        )?;
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "== ?: (1 match) ==\nrelative\n\n\
+             == C: (2 matches) ==\nC:\\a\nC:\\z\n\n\
+             == D: (2 matches) ==\nD:\\b\nd:\\c\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn output_file_creates_parents_and_writes_optional_bom() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for bom in [false, true] {
+            let path = dir.path().join(format!("nested-{bom}")).join("results.txt");
+            let output = OutputFile {
+                output_file: Some(path.to_string_lossy().into_owned()),
+                bom,
+            };
+            let mut writer = output.open()?;
+            writeln!(writer, r"C:\docs\é.txt")?;
+            writer.flush()?;
+            drop(writer);
+
+            let mut expected = if bom {
+                OutputFile::UTF8_BOM.to_vec()
+            } else {
+                Vec::new()
+            };
+            expected.extend_from_slice("C:\\docs\\é.txt\n".as_bytes());
+            assert_eq!(std::fs::read(&path)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bom_without_output_file_is_rejected() {
+        let output = OutputFile {
+            output_file: None,
+            bom: true,
+        };
+        assert!(output.open().is_err());
+    }
 }
@@ -2,14 +2,17 @@ mod query_filter_rules;
 mod query_group;
 mod query_index_match;
 mod query_limit;
+mod query_match_target;
 mod query_needle;
 mod query_plan;
 mod query_result_row;
 mod query_row_filter;
 mod query_rule;
 mod query_runtime;
+mod query_sample;
 mod query_scope;
 mod query_session;
+mod query_sort_order;
 mod query_string;
 mod search_index_query;
 
@@ -26,16 +29,19 @@ pub use query_group::QueryGroup;
 pub(crate) use query_index_match::MatchingRowIndices;
 pub(crate) use query_index_match::matching_row_indices_for_rule;
 pub use query_limit::QueryLimit;
+pub use query_match_target::QueryMatchTarget;
 pub use query_needle::QueryNeedle;
 pub use query_plan::QueryPlan;
 pub use query_result_row::QueryResultRow;
 pub use query_row_filter::QueryRowFilter;
 pub use query_rule::QueryRule;
 pub use query_runtime::QueryRuntime;
+pub use query_sample::QuerySample;
 pub(crate) use query_scope::QueryScope;
 pub(crate) use query_scope::resolve_query_scopes;
 pub use query_session::QuerySession;
 pub use query_session::QuerySessionBackend;
+pub use query_sort_order::QuerySortOrder;
 pub use query_string::QueryString;
 pub(crate) use search_index_query::visit_drive_search_index_rows;
 pub(crate) use search_index_query::visit_parsed_search_index_rows;
@@ -50,6 +50,14 @@ impl QueryGroup {
         self.matches_preprocessed(haystack, None)
     }
 
+    /// Whether every rule matches `file_name` on its own, ignoring parent directories.
+    #[must_use]
+    pub fn matches_name(&self, file_name: &str) -> bool {
+        self.rules
+            .iter()
+            .all(|rule| rule.is_match_all() || rule.matches(file_name))
+    }
+
     #[must_use]
     pub fn matches_segments_preprocessed<'a, I, F>(&self, make_segments: &F) -> bool
     where
@@ -0,0 +1,15 @@
+use arbitrary::Arbitrary;
+use facet::Facet;
+
+/// Which part of each indexed path the query rules are matched against.
+#[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[repr(u8)]
+#[strum(serialize_all = "kebab-case")]
+#[facet(rename_all = "kebab-case")]
+pub enum QueryMatchTarget {
+    /// Rules may match any path segment; suffix and exact rules match the final segment.
+    #[default]
+    Full,
+    /// Every rule must match the file name (the final path segment).
+    Name,
+}
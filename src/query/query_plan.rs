@@ -1,5 +1,6 @@
 use crate::query::DEFAULT_PROFILE_NAME;
 use crate::query::QueryLimit;
+use crate::query::QueryMatchTarget;
 use crate::query::QueryRule;
 use crate::query::QueryString;
 use crate::query::normalize_profile_name;
@@ -21,12 +22,18 @@ pub struct QueryPlan {
     /// Restrict results to these paths. Directories include descendants; files match exactly. Repeat `--in` to OR scopes.
     #[facet(args::named, default)]
     pub r#in: Vec<String>,
+    /// Keep only paths starting with this prefix (`/` and `\` are equivalent; case-insensitive on Windows)
+    #[facet(args::named)]
+    pub prefix: Option<String>,
     /// Apply profile-specific `.teamy_mft_rules` files in addition to global rules.
     #[facet(args::named, default)]
     pub profile: Option<String>,
     /// Drive letter pattern to match drives whose cached MFTs will be queried (e.g., "*", "C", "CD", "C,D"). Compatibility alias: `--drive`.
     #[facet(args::named, args::alias = "drive", default)]
     pub drive_letter_pattern: DriveLetterPattern,
+    /// Match rules against the full path or only the file name
+    #[facet(args::named, default)]
+    pub r#match: QueryMatchTarget,
     /// Maximum number of results to show
     #[facet(args::named, default)]
     pub limit: QueryLimit,
@@ -42,6 +49,9 @@ pub struct QueryPlan {
     /// Show only paths filtered out by `.teamy_mft_rules` filter rules
     #[facet(args::named, default)]
     pub only_filtered: bool,
+    /// Include entries whose parent chain never reaches the root, listed under `X:\$Orphan\`
+    #[facet(args::named, default)]
+    pub include_orphans: bool,
 }
 
 impl QueryPlan {
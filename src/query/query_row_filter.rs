@@ -1,3 +1,4 @@
+use crate::mft::path_resolve::is_orphan_path;
 use crate::query::QueryFilterRules;
 use crate::query::QueryPlan;
 use crate::query::QueryResultRow;
@@ -12,11 +13,13 @@ use std::path::Path;
 )]
 pub struct QueryRowFilter {
     scopes: Vec<QueryScope>,
+    prefix: Option<String>,
     filter_rules: Option<QueryFilterRules>,
     include_deleted: bool,
     only_deleted: bool,
     show_filtered: bool,
     only_filtered: bool,
+    include_orphans: bool,
 }
 
 impl QueryRowFilter {
@@ -26,11 +29,13 @@ impl QueryRowFilter {
     pub fn new(request: &QueryPlan, filter_rules: Option<QueryFilterRules>) -> eyre::Result<Self> {
         Ok(Self {
             scopes: resolve_query_scopes(&request.r#in)?,
+            prefix: request.prefix.as_deref().map(normalize_path_prefix),
             filter_rules,
             include_deleted: request.include_deleted,
             only_deleted: request.only_deleted,
             show_filtered: request.show_filtered,
             only_filtered: request.only_filtered,
+            include_orphans: request.include_orphans,
         })
     }
 
@@ -57,6 +62,13 @@ impl QueryRowFilter {
         self.scopes.is_empty() || self.scopes.iter().any(|scope| scope.matches_path(path))
     }
 
+    #[must_use]
+    pub fn matches_prefix(&self, path: &Path) -> bool {
+        self.prefix.as_ref().is_none_or(|prefix| {
+            normalize_path_prefix(&path.to_string_lossy()).starts_with(prefix.as_str())
+        })
+    }
+
     #[must_use]
     pub(crate) fn scopes(&self) -> &[QueryScope] {
         &self.scopes
@@ -67,7 +79,10 @@ impl QueryRowFilter {
         if !self.include_deleted_state(row.has_deleted_entries) {
             return None;
         }
-        if !self.matches_scope(row.path.as_path()) {
+        if !self.include_orphans && is_orphan_path(row.path.as_str()) {
+            return None;
+        }
+        if !self.matches_prefix(row.path.as_path()) || !self.matches_scope(row.path.as_path()) {
             return None;
         }
 
@@ -80,6 +95,15 @@ impl QueryRowFilter {
     }
 }
 
+fn normalize_path_prefix(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    if cfg!(windows) {
+        path.to_lowercase()
+    } else {
+        path
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::QueryRowFilter;
@@ -91,6 +115,14 @@ mod tests {
         QueryPlan::new("music")
     }
 
+    fn path_row(path: &str) -> QueryResultRow {
+        QueryResultRow {
+            path: Pathlike::from(path.to_owned()),
+            has_deleted_entries: false,
+            is_filtered: false,
+        }
+    }
+
     fn row(has_deleted_entries: bool) -> QueryResultRow {
         QueryResultRow {
             path: Pathlike::from(String::from(r"C:\music\track.flac")),
@@ -159,4 +191,49 @@ mod tests {
         assert!(filter.include_filtered_state(true));
         assert!(!filter.include_filtered_state(false));
     }
+
+    #[test]
+    fn orphan_rows_are_hidden_unless_included() {
+        let orphan = || path_row(r"C:\$Orphan\lost.txt");
+        let filter = QueryRowFilter::new(&request(), None).expect("filter should build");
+        assert!(filter.classify_and_match(orphan()).is_none());
+
+        let filter = QueryRowFilter::new(
+            &QueryPlan {
+                include_orphans: true,
+                ..request()
+            },
+            None,
+        )
+        .expect("filter should build");
+        assert!(filter.classify_and_match(orphan()).is_some());
+    }
+
+    #[test]
+    fn prefix_keeps_only_paths_under_the_prefix() {
+        let filter = QueryRowFilter::new(
+            &QueryPlan {
+                prefix: Some(String::from("C:/music/")),
+                ..request()
+            },
+            None,
+        )
+        .expect("filter should build");
+
+        assert!(
+            filter
+                .classify_and_match(path_row(r"C:\music\track.flac"))
+                .is_some()
+        );
+        assert!(
+            filter
+                .classify_and_match(path_row(r"C:\musicals\track.flac"))
+                .is_none()
+        );
+        assert!(
+            filter
+                .classify_and_match(path_row(r"D:\music\track.flac"))
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,97 @@
+use eyre::ensure;
+
+/// Deterministic fraction of result rows kept by `query --sample`.
+///
+/// Rows are kept when a stable hash of their path falls below the fraction,
+/// so repeated runs over the same index return the same subset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuerySample {
+    fraction: f64,
+}
+
+impl QuerySample {
+    /// # Errors
+    ///
+    /// Returns an error if `fraction` is not within `0.0..=1.0`.
+    pub fn new(fraction: f64) -> eyre::Result<Self> {
+        ensure!(
+            (0.0..=1.0).contains(&fraction),
+            "--sample must be between 0.0 and 1.0, got {fraction}"
+        );
+        Ok(Self { fraction })
+    }
+
+    #[must_use]
+    pub fn fraction(self) -> f64 {
+        self.fraction
+    }
+
+    #[must_use]
+    pub fn is_full(self) -> bool {
+        self.fraction >= 1.0
+    }
+
+    #[must_use]
+    pub fn keeps(self, path: &str) -> bool {
+        if self.is_full() {
+            return true;
+        }
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "the hash only needs to be mapped onto the unit interval"
+        )]
+        let position = fnv1a(path.as_bytes()) as f64 / u64::MAX as f64;
+        position < self.fraction
+    }
+}
+
+impl Default for QuerySample {
+    fn default() -> Self {
+        Self { fraction: 1.0 }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    // Fold the high bits down so short, similar paths still spread evenly.
+    hash ^ (hash >> 29)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuerySample;
+
+    #[test]
+    fn half_sample_keeps_roughly_half_of_known_paths() -> eyre::Result<()> {
+        let sample = QuerySample::new(0.5)?;
+        let total = 10_000;
+        let kept = (0..total)
+            .filter(|index| sample.keeps(&format!(r"C:\Repos\project-{index}\Cargo.toml")))
+            .count();
+
+        assert!(
+            (4_500..=5_500).contains(&kept),
+            "expected roughly half of {total} paths, kept {kept}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sampling_is_deterministic_and_bounded() -> eyre::Result<()> {
+        let sample = QuerySample::new(0.25)?;
+        let path = r"C:\Windows\System32\notepad.exe";
+
+        assert_eq!(sample.keeps(path), sample.keeps(path));
+        assert!(QuerySample::new(1.0)?.keeps(path));
+        assert!(!QuerySample::new(0.0)?.keeps(path));
+        assert!(QuerySample::new(1.5).is_err());
+        assert!(QuerySample::new(-0.1).is_err());
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@ use crate::query::visit_parsed_search_index_rows;
 use crate::search_index::load::MappedSearchIndex;
 use crate::search_index::search_index_bytes::ParsedSearchIndex;
 use crate::search_index::search_index_bytes::SearchIndexBytes;
+use crate::windows_utils::storage::DriveLetterPattern;
 use eyre::Context;
 use eyre::ContextCompat;
 use eyre::ensure;
@@ -143,6 +144,11 @@ impl QuerySession {
         let drive_letters = query_plan
             .drive_letter_pattern
             .into_drive_letters_for_scope_roots(scopes.iter().map(|scope| scope.root.as_path()))?;
+        ensure_any_published_index(
+            &self.sync_dir,
+            &query_plan.drive_letter_pattern,
+            &drive_letters,
+        )?;
         let filter_rules = QueryFilterRules::discover_for_drive_letters(
             &drive_letters,
             &self.sync_dir,
@@ -182,12 +188,34 @@ impl QuerySession {
     }
 }
 
+/// Fail early when none of the selected drives has a published index, so an
+/// unsynced cache reports how to fix it instead of an empty result set.
+fn ensure_any_published_index(
+    sync_dir: &std::path::Path,
+    drive_letter_pattern: &DriveLetterPattern,
+    drive_letters: &[char],
+) -> eyre::Result<()> {
+    let any_published = drive_letters.iter().any(|&drive| {
+        published_drive_paths(sync_dir, drive)
+            .base_index_path
+            .is_file()
+    });
+    ensure!(
+        any_published,
+        "No cached indexes in {} match drive pattern {:?}. Run `teamy-mft sync --drive {}` first.",
+        sync_dir.display(),
+        drive_letter_pattern.0,
+        drive_letter_pattern.0
+    );
+    Ok(())
+}
+
 impl CachedPublishedDriveQuery {
     fn load(drive: char, sync_dir: &std::path::Path) -> eyre::Result<Self> {
         let paths = published_drive_paths(sync_dir, drive);
         ensure!(
             paths.base_index_path.is_file(),
-            "Fast query requires {}. Run `teamy-mft sync index --drive-pattern {}` first.",
+            "Fast query requires {}. Run `teamy-mft sync --drive {}` first.",
             paths.base_index_path.display(),
             drive
         );
@@ -495,6 +523,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn published_session_reports_empty_sync_dir() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let mut session = QuerySession {
+            backend: super::QuerySessionBackend::Local,
+            sync_dir: temp_dir.path().to_path_buf(),
+            published_index_cache: std::collections::HashMap::new(),
+        };
+
+        let error = collect_visited_paths(&mut session, fixture_drive_plan("Cargo.toml"))
+            .expect_err("an empty sync dir should fail");
+        let message = error.to_string();
+
+        assert!(message.contains("No cached indexes in"));
+        assert!(message.contains(&temp_dir.path().display().to_string()));
+        assert!(message.contains("\"C\""));
+        assert!(message.contains("teamy-mft sync --drive C"));
+        Ok(())
+    }
+
     #[test]
     fn daemon_session_still_uses_runtime_backend_selection() -> eyre::Result<()> {
         let session = QuerySession::daemon_rpc()?;
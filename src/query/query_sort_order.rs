@@ -0,0 +1,87 @@
+use crate::query::QueryResultRow;
+use arbitrary::Arbitrary;
+use facet::Facet;
+
+/// Order in which `query` emits result rows.
+#[derive(Default, Facet, Arbitrary, Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[repr(u8)]
+#[strum(serialize_all = "kebab-case")]
+#[facet(rename_all = "kebab-case")]
+pub enum QuerySortOrder {
+    /// Stream rows in index order as they are matched.
+    #[default]
+    Index,
+    /// Collect every match and sort by path before applying `--limit`.
+    Path,
+}
+
+impl QuerySortOrder {
+    /// Whether rows can be emitted as they are matched without buffering.
+    #[must_use]
+    pub fn is_streaming(self) -> bool {
+        self == Self::Index
+    }
+
+    /// Sort `rows` in place. Rows with equal paths are ordered by their flags so the
+    /// output does not depend on which drive or index produced them first.
+    pub fn sort(self, rows: &mut [QueryResultRow]) {
+        match self {
+            Self::Index => {}
+            Self::Path => rows.sort_by(|left, right| {
+                left.path
+                    .cmp(&right.path)
+                    .then(left.has_deleted_entries.cmp(&right.has_deleted_entries))
+                    .then(left.is_filtered.cmp(&right.is_filtered))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuerySortOrder;
+    use crate::query::Pathlike;
+    use crate::query::QueryResultRow;
+
+    fn row(path: &str, has_deleted_entries: bool) -> QueryResultRow {
+        QueryResultRow {
+            path: Pathlike::from(path.to_owned()),
+            has_deleted_entries,
+            is_filtered: false,
+        }
+    }
+
+    #[test]
+    fn path_sort_is_stable_for_equal_paths_regardless_of_input_order() {
+        let forward = vec![
+            row(r"D:\b.txt", false),
+            row(r"C:\a.txt", true),
+            row(r"C:\a.txt", false),
+        ];
+        let mut reversed = forward.iter().rev().cloned().collect::<Vec<_>>();
+        let mut forward = forward;
+
+        QuerySortOrder::Path.sort(&mut forward);
+        QuerySortOrder::Path.sort(&mut reversed);
+
+        assert_eq!(forward, reversed);
+        assert_eq!(
+            forward,
+            vec![
+                row(r"C:\a.txt", false),
+                row(r"C:\a.txt", true),
+                row(r"D:\b.txt", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn index_sort_preserves_input_order() {
+        let mut rows = vec![row(r"D:\b.txt", false), row(r"C:\a.txt", false)];
+        let expected = rows.clone();
+
+        QuerySortOrder::Index.sort(&mut rows);
+
+        assert_eq!(rows, expected);
+    }
+}
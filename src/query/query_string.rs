@@ -75,6 +75,13 @@ impl QueryString {
         self.matches_preprocessed(haystack, None)
     }
 
+    #[must_use]
+    pub fn matches_name(&self, file_name: &str) -> bool {
+        self.groups
+            .iter()
+            .any(|group| group.matches_name(file_name))
+    }
+
     #[must_use]
     pub fn matches_segments_preprocessed<'a, I, F>(&self, make_segments: &F) -> bool
     where
@@ -1,6 +1,8 @@
 use crate::machine::config::published_drive_paths;
+use crate::mft::path_resolve::is_orphan_path;
 use crate::query::MatchingRowIndices;
 use crate::query::Pathlike;
+use crate::query::QueryMatchTarget;
 use crate::query::QueryPlan;
 use crate::query::QueryResultRow;
 use crate::query::matching_row_indices_for_rule;
@@ -145,6 +147,22 @@ fn visit_matching_row_indices(
         }
     };
 
+    // Postings match any path segment, so narrow them to rows whose file name matches.
+    let mut visit_row = |row_index: u32| -> eyre::Result<ControlFlow<(), ()>> {
+        if query_plan.r#match == QueryMatchTarget::Name {
+            let row = parsed_index.row_view(row_index as usize)?;
+            let file_name = row
+                .segment_views()
+                .next()
+                .map(|segment| segment.display_lossy())
+                .unwrap_or_default();
+            if !query_plan.query.matches_name(&file_name) {
+                return Ok(ControlFlow::Continue(()));
+            }
+        }
+        visit(row_index)
+    };
+
     match matched_rows {
         MatchingRowIndices::MatchAll { row_count } => {
             let _span = info_span!("visit_all_matched_row_indices").entered();
@@ -152,7 +170,7 @@ fn visit_matching_row_indices(
                 let control_flow = {
                     #[cfg(feature = "extended_observability_per_record")]
                     let _span = tracing::debug_span!("visit_all_matched_row_index").entered();
-                    visit(row_index)?
+                    visit_row(row_index)?
                 };
                 if control_flow == ControlFlow::Break(()) {
                     return Ok(ControlFlow::Break(()));
@@ -164,7 +182,7 @@ fn visit_matching_row_indices(
         MatchingRowIndices::RowIndices(row_indices) => {
             let _span = info_span!("visit_matched_row_indices").entered();
             for row_index in row_indices {
-                if visit(row_index)? == ControlFlow::Break(()) {
+                if visit_row(row_index)? == ControlFlow::Break(()) {
                     return Ok(ControlFlow::Break(()));
                 }
             }
@@ -197,6 +215,7 @@ pub(crate) fn visit_parsed_search_index_rows(
                 #[cfg(feature = "extended_observability_per_record")]
                 let _span = tracing::debug_span!("evaluate_deleted_row_filter").entered();
                 should_include_indexed_row(include_deleted, only_deleted, has_deleted_entries)
+                    && (query_plan.include_orphans || !is_orphan_path(path.as_str()))
             };
 
             if !should_include {
@@ -498,6 +517,7 @@ struct MatchingRowRef {
 #[cfg(test)]
 mod tests {
     use super::visit_matching_parsed_row_indices;
+    use crate::query::QueryMatchTarget;
     use crate::query::QueryPlan;
     use crate::query::resolve_query_scopes;
     use crate::search_index::format::SearchIndexHeader;
@@ -661,4 +681,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn name_match_ignores_parent_directory_hits() -> eyre::Result<()> {
+        let parsed = parse_index(&[
+            SearchIndexPathRow {
+                path: r"C:\flowers\readme.txt".into(),
+                has_deleted_entries: false,
+            },
+            SearchIndexPathRow {
+                path: r"C:\garden\flowers.txt".into(),
+                has_deleted_entries: false,
+            },
+        ])?;
+
+        let mut plan = QueryPlan::parse_inputs(&[String::from("flower")])?;
+        let visit_rows = |plan: &QueryPlan| -> eyre::Result<Vec<u32>> {
+            let mut matching_rows = Vec::new();
+            visit_matching_parsed_row_indices(&parsed, plan, &[], false, false, |row_index| {
+                matching_rows.push(row_index);
+                Ok(ControlFlow::Continue(()))
+            })?;
+            Ok(matching_rows)
+        };
+
+        assert_eq!(visit_rows(&plan)?, vec![0, 1]);
+        plan.r#match = QueryMatchTarget::Name;
+        assert_eq!(visit_rows(&plan)?, vec![1]);
+
+        Ok(())
+    }
 }
@@ -1,16 +1,20 @@
 use crate::read::physical_read_request::PhysicalReadRequest;
 use crate::read::physical_read_results::PhysicalReadResultEntry;
-use eyre::bail;
+use crate::read::read_error::ReadError;
 use std::any::type_name;
 use std::ptr::null_mut;
+use std::time::Duration;
 use tracing::trace;
 use tracing::warn;
 use uom::si::information::byte;
+use windows::Win32::Foundation::E_UNEXPECTED;
 use windows::Win32::Foundation::ERROR_IO_PENDING;
 use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Foundation::WAIT_TIMEOUT;
 use windows::Win32::Storage::FileSystem::ReadFile;
 use windows::Win32::System::IO::GetQueuedCompletionStatus;
 use windows::Win32::System::IO::OVERLAPPED;
+use windows::core::HRESULT;
 
 // NOTE on layout and safety for IOCP:
 // We intentionally embed OVERLAPPED as the FIRST field and mark the
@@ -29,6 +33,9 @@ use windows::Win32::System::IO::OVERLAPPED;
 //   the completion is dequeued by converting the raw pointer back with
 //   Box::from_raw exactly once.
 // - We never move/relocate the allocation after queueing the I/O.
+// - If a wait times out, requests that are still pending are never
+//   reclaimed: the kernel may still write into their buffers, so their
+//   allocations are intentionally leaked rather than freed early.
 #[repr(C)]
 pub struct ActivePhysicalReadRequest {
     pub overlapped: OVERLAPPED,
@@ -95,9 +102,9 @@ impl ActivePhysicalReadRequest {
     ///
     /// # Errors
     ///
-    /// Returns an eyre error if `ReadFile` fails to queue the request for
+    /// Returns a classified [`ReadError`] if `ReadFile` fails to queue the request for
     /// reasons other than `ERROR_IO_PENDING`.
-    pub fn send(self, file_handle: HANDLE) -> eyre::Result<()> {
+    pub fn send(self, file_handle: HANDLE) -> Result<(), ReadError> {
         let mut boxed = Box::new(self);
 
         // We pass a pointer to the embedded OVERLAPPED. Because
@@ -119,7 +126,8 @@ impl ActivePhysicalReadRequest {
             Ok(()) => {}
             Err(e) => {
                 if e.code() != ERROR_IO_PENDING.into() {
-                    bail!("ReadFile failed to queue request {boxed:?}: {e:?}");
+                    warn!(request = ?boxed, error = ?e, "ReadFile failed to queue request");
+                    return Err(ReadError::from_request_error(boxed.original, e));
                 }
             }
         }
@@ -131,18 +139,25 @@ impl ActivePhysicalReadRequest {
         Ok(())
     }
 
-    /// Waits for the next completion from `completion_port` and returns the
+    /// Waits up to `timeout` for the next completion from `completion_port` and returns the
     /// associated read payload along with the response index.
     ///
+    /// Returns `Ok(None)` if no completion arrived before the timeout elapsed.
+    ///
     /// # Errors
     ///
-    /// Returns an error if IOCP fails, or if a completion with a null
-    /// overlapped pointer appears (which violates the request invariants).
-    pub fn receive(completion_port: HANDLE) -> eyre::Result<(PhysicalReadResultEntry, usize)> {
+    /// Returns a [`ReadError`] if IOCP fails, the completed request failed, or a completion
+    /// with a null overlapped pointer appears (which violates the request invariants).
+    pub fn receive(
+        completion_port: HANDLE,
+        timeout: Duration,
+    ) -> Result<Option<(PhysicalReadResultEntry, usize)>, ReadError> {
         let mut bytes_transferred: u32 = 0;
         let mut completion_key: usize = 0;
         let mut lp_overlapped: *mut OVERLAPPED = null_mut();
-        trace!("Waiting for IOCP read completion");
+        // u32::MAX is INFINITE, so clamp just below it to keep the wait bounded.
+        let timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX - 1);
+        trace!(timeout_ms, "Waiting for IOCP read completion");
         // SAFETY: The raw pointers remain valid for the duration of the
         // call and are initialized before the call returns.
         let res = unsafe {
@@ -151,13 +166,19 @@ impl ActivePhysicalReadRequest {
                 &raw mut bytes_transferred,
                 &raw mut completion_key,
                 &raw mut lp_overlapped,
-                u32::MAX,
+                timeout_ms,
             )
         };
         match res {
             Ok(()) => {
                 if lp_overlapped.is_null() {
-                    bail!("IOCP returned success but OVERLAPPED ptr was null");
+                    return Err(ReadError::Io {
+                        request: None,
+                        source: windows::core::Error::new(
+                            E_UNEXPECTED,
+                            "IOCP returned success but OVERLAPPED ptr was null",
+                        ),
+                    });
                 }
                 // Recover original allocation using container_of pattern: lp_overlapped points to the first
                 // field (overlapped) so casting back to the parent type is sound under our invariants.
@@ -172,24 +193,30 @@ impl ActivePhysicalReadRequest {
                 if copy_len < data.len() {
                     data.truncate(copy_len);
                 }
-                Ok((
+                Ok(Some((
                     PhysicalReadResultEntry {
                         request: boxed_req.original,
                         data,
                     },
                     boxed_req.response_index,
-                ))
+                )))
             }
             Err(e) => {
-                if lp_overlapped.is_null() {
-                    Err(eyre::eyre!("GetQueuedCompletionStatus failed: {e:?}"))
+                if lp_overlapped.is_null() && e.code() == HRESULT::from_win32(WAIT_TIMEOUT.0) {
+                    Ok(None)
+                } else if lp_overlapped.is_null() {
+                    Err(ReadError::Io {
+                        request: None,
+                        source: e,
+                    })
                 } else {
                     // Same recovery path on error: take ownership back
                     // and allow the allocation to be freed when dropped.
                     let req_ptr = lp_overlapped.cast::<ActivePhysicalReadRequest>();
                     // SAFETY: Same as above, the pointer originates from `Box::into_raw`.
                     let boxed_req = unsafe { Box::from_raw(req_ptr) };
-                    Err(eyre::eyre!("I/O operation failed for {boxed_req:?}: {e:?}"))
+                    warn!(request = ?boxed_req, error = ?e, "I/O operation failed");
+                    Err(ReadError::from_request_error(boxed_req.original, e))
                 }
             }
         }
@@ -1,8 +1,10 @@
 use crate::read::physical_read_plan::PhysicalReadPlan;
 use crate::read::physical_read_request::PhysicalReadRequest;
 use std::collections::BTreeSet;
+use std::ops::Range;
 use tracing::instrument;
 use uom::ConstZero;
+use uom::si::information::byte;
 use uom::si::usize::Information;
 
 /// A plan for reading a file logically, including sparse segments.
@@ -33,6 +35,90 @@ impl LogicalReadPlan {
             .collect()
     }
 
+    /// Restrict the plan to the logical bytes not covered by `covered`.
+    ///
+    /// Segments that partially overlap a covered range are trimmed, shifting the physical
+    /// offset of the remaining piece so it still maps to the same device bytes.
+    #[must_use]
+    pub fn without_logical_ranges(&self, covered: &[Range<Information>]) -> LogicalReadPlan {
+        let mut segments = BTreeSet::new();
+        for segment in &self.segments {
+            let mut pieces = vec![segment.logical_offset..segment.logical_offset + segment.length];
+            for range in covered {
+                let mut remaining = Vec::with_capacity(pieces.len() + 1);
+                for piece in pieces {
+                    if range.end <= piece.start || range.start >= piece.end {
+                        remaining.push(piece);
+                        continue;
+                    }
+                    if piece.start < range.start {
+                        remaining.push(piece.start..range.start);
+                    }
+                    if range.end < piece.end {
+                        remaining.push(range.end..piece.end);
+                    }
+                }
+                pieces = remaining;
+            }
+            for piece in pieces {
+                let kind = match segment.kind {
+                    LogicalFileSegmentKind::Physical { physical_offset } => {
+                        LogicalFileSegmentKind::Physical {
+                            physical_offset: physical_offset
+                                + (piece.start - segment.logical_offset),
+                        }
+                    }
+                    LogicalFileSegmentKind::Sparse => LogicalFileSegmentKind::Sparse,
+                };
+                segments.insert(LogicalFileSegment {
+                    logical_offset: piece.start,
+                    length: piece.end - piece.start,
+                    kind,
+                });
+            }
+        }
+        LogicalReadPlan { segments }
+    }
+
+    /// Keep only the first `max_logical_size` logical bytes of the plan.
+    #[must_use]
+    pub fn truncated(&self, max_logical_size: Information) -> LogicalReadPlan {
+        let total_logical_size = self.total_logical_size();
+        if max_logical_size >= total_logical_size {
+            return self.clone();
+        }
+        self.without_logical_ranges(&[max_logical_size..total_logical_size])
+    }
+
+    /// Check that the segments tile `[0, total_logical_size)` with no gaps or overlaps.
+    ///
+    /// Sparse ranges must be explicit [`LogicalFileSegmentKind::Sparse`] segments; a hole
+    /// between segments means the run list was decoded incorrectly.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first gap or overlap found, in logical order.
+    pub fn validate(&self) -> Result<(), PlanError> {
+        let mut expected_offset = Information::ZERO;
+        for segment in &self.segments {
+            match segment.logical_offset.cmp(&expected_offset) {
+                std::cmp::Ordering::Greater => {
+                    return Err(PlanError::Gap {
+                        range: expected_offset..segment.logical_offset,
+                    });
+                }
+                std::cmp::Ordering::Less => {
+                    return Err(PlanError::Overlap {
+                        range: segment.logical_offset..expected_offset,
+                    });
+                }
+                std::cmp::Ordering::Equal => {}
+            }
+            expected_offset = segment.logical_offset + segment.length;
+        }
+        Ok(())
+    }
+
     #[must_use]
     pub fn total_logical_size(&self) -> Information {
         self.segments
@@ -43,6 +129,32 @@ impl LogicalReadPlan {
     }
 }
 
+/// A [`LogicalReadPlan`] whose segments do not tile the logical file contiguously.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// No segment covers these logical bytes.
+    Gap { range: Range<Information> },
+    /// More than one segment covers these logical bytes.
+    Overlap { range: Range<Information> },
+}
+
+impl std::fmt::Display for PlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (problem, range) = match self {
+            PlanError::Gap { range } => ("gap", range),
+            PlanError::Overlap { range } => ("overlap", range),
+        };
+        write!(
+            f,
+            "Logical read plan has a {problem} at bytes {}..{}",
+            range.start.get::<byte>(),
+            range.end.get::<byte>()
+        )
+    }
+}
+
+impl std::error::Error for PlanError {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LogicalFileSegment {
     pub logical_offset: Information,
@@ -77,3 +189,113 @@ impl PartialOrd for LogicalFileSegment {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bytes(value: usize) -> Information {
+        Information::new::<byte>(value)
+    }
+
+    #[test]
+    fn without_logical_ranges_keeps_only_uncovered_bytes() {
+        let plan = LogicalReadPlan {
+            segments: [
+                LogicalFileSegment {
+                    logical_offset: bytes(0),
+                    length: bytes(4096),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: bytes(8192),
+                    },
+                },
+                LogicalFileSegment {
+                    logical_offset: bytes(4096),
+                    length: bytes(4096),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: bytes(65536),
+                    },
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        // Half of the plan was already written: the first segment plus 1 KiB of the second.
+        let remaining =
+            plan.without_logical_ranges(&[bytes(0)..bytes(4096), bytes(4096)..bytes(5120)]);
+
+        assert_eq!(
+            remaining.segments.into_iter().collect::<Vec<_>>(),
+            vec![LogicalFileSegment {
+                logical_offset: bytes(5120),
+                length: bytes(3072),
+                kind: LogicalFileSegmentKind::Physical {
+                    physical_offset: bytes(65536 + 1024),
+                },
+            }]
+        );
+        assert_eq!(
+            plan.without_logical_ranges(&[bytes(0)..bytes(4096)])
+                .as_physical_read_plan()
+                .total_size(),
+            bytes(4096)
+        );
+    }
+
+    fn plan(segments: &[(usize, usize, Option<usize>)]) -> LogicalReadPlan {
+        LogicalReadPlan {
+            segments: segments
+                .iter()
+                .map(|&(logical, length, physical)| LogicalFileSegment {
+                    logical_offset: bytes(logical),
+                    length: bytes(length),
+                    kind: physical.map_or(LogicalFileSegmentKind::Sparse, |physical| {
+                        LogicalFileSegmentKind::Physical {
+                            physical_offset: bytes(physical),
+                        }
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_contiguous_plan_with_sparse_segment() {
+        let plan = plan(&[
+            (0, 4096, Some(8192)),
+            (4096, 1024, None),
+            (5120, 2048, Some(0)),
+        ]);
+
+        assert_eq!(plan.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_overlap() {
+        let plan = plan(&[(0, 4096, Some(8192)), (2048, 4096, Some(65536))]);
+
+        assert_eq!(
+            plan.validate(),
+            Err(PlanError::Overlap {
+                range: bytes(2048)..bytes(4096)
+            })
+        );
+    }
+
+    #[test]
+    fn validate_reports_gap() {
+        let plan = plan(&[(0, 4096, Some(8192)), (6144, 1024, Some(65536))]);
+
+        assert_eq!(
+            plan.validate(),
+            Err(PlanError::Gap {
+                range: bytes(4096)..bytes(6144)
+            })
+        );
+        assert_eq!(
+            plan.validate().unwrap_err().to_string(),
+            "Logical read plan has a gap at bytes 4096..6144"
+        );
+    }
+}
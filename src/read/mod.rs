@@ -3,4 +3,6 @@ pub mod logical_read_plan;
 pub mod physical_read_plan;
 pub mod physical_read_request;
 pub mod physical_read_results;
+pub mod physical_read_tuning;
 pub mod physical_reader;
+pub mod read_error;
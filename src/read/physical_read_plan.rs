@@ -1,7 +1,9 @@
 use crate::read::physical_read_request::PhysicalReadRequest;
 use crate::read::physical_read_results::PhysicalReadResults;
 use crate::read::physical_reader::PhysicalReader;
+use crate::read::read_error::ReadError;
 use std::collections::BTreeSet;
+use std::time::Duration;
 use tracing::info_span;
 use tracing::instrument;
 use tracing::warn;
@@ -25,6 +27,7 @@ pub enum ZeroLengthPushBehaviour {
 }
 
 const DEFAULT_MAX_IN_FLIGHT_IO: usize = 32;
+const DEFAULT_IO_COMPLETION_TIMEOUT: Duration = Duration::from_secs(30);
 impl IntoIterator for PhysicalReadPlan {
     type Item = PhysicalReadRequest;
     type IntoIter = std::collections::btree_set::IntoIter<PhysicalReadRequest>;
@@ -158,32 +161,52 @@ impl PhysicalReadPlan {
 
     /// Read the requested ranges from the given file handle.
     ///
+    /// Each wait for an IO completion is bounded by `TEAMY_MFT_IO_TIMEOUT_SECS` (default 30s).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if opening the file, enqueuing IO operations, or reading fails,
+    /// or if the device stops completing reads within the timeout.
+    pub fn read(self, filename: impl Param<PCWSTR>) -> Result<PhysicalReadResults, ReadError> {
+        self.read_with_queue_depth(filename, None)
+    }
+
+    /// Like [`Self::read`], but with an explicit maximum number of in-flight reads.
+    ///
+    /// `None` falls back to `TEAMY_MFT_MAX_IN_FLIGHT_IO` or the built-in default.
+    ///
     /// # Errors
     ///
-    /// Returns an error if opening the file, enqueuing IO operations, or reading fails.
+    /// Returns an error if the volume cannot be opened or any read fails.
     #[instrument(skip_all)]
-    pub fn read(self, filename: impl Param<PCWSTR>) -> eyre::Result<PhysicalReadResults> {
+    pub fn read_with_queue_depth(
+        self,
+        filename: impl Param<PCWSTR>,
+        queue_depth: Option<usize>,
+    ) -> Result<PhysicalReadResults, ReadError> {
         if self.is_empty() {
             return Ok(PhysicalReadResults::new());
         }
-        let max_in_flight = max_in_flight_io();
+        let max_in_flight = queue_depth.unwrap_or_else(max_in_flight_io);
+        let completion_timeout = io_completion_timeout();
         let request_count = self.requests.len();
         let total_size = self.total_size().get::<byte>();
-        let reader = {
+        let mut reader = {
             let _span = info_span!(
                 "create_physical_reader",
                 request_count,
                 total_physical_bytes = total_size,
                 max_in_flight,
+                completion_timeout_secs = completion_timeout.as_secs(),
             )
             .entered();
-            PhysicalReader::try_new(filename, self.requests, max_in_flight)?
+            PhysicalReader::try_new(filename, [], max_in_flight, completion_timeout)?
         };
-        reader.read_all()
+        reader.read_plan(self)
     }
 }
 
-fn max_in_flight_io() -> usize {
+pub(crate) fn max_in_flight_io() -> usize {
     let Ok(value) = std::env::var("TEAMY_MFT_MAX_IN_FLIGHT_IO") else {
         return DEFAULT_MAX_IN_FLIGHT_IO;
     };
@@ -210,6 +233,33 @@ fn max_in_flight_io() -> usize {
     }
 }
 
+pub(crate) fn io_completion_timeout() -> Duration {
+    let Ok(value) = std::env::var("TEAMY_MFT_IO_TIMEOUT_SECS") else {
+        return DEFAULT_IO_COMPLETION_TIMEOUT;
+    };
+
+    match value.parse::<u64>() {
+        Ok(0) => {
+            warn!(
+                env_value = %value,
+                default_secs = DEFAULT_IO_COMPLETION_TIMEOUT.as_secs(),
+                "Ignoring TEAMY_MFT_IO_TIMEOUT_SECS=0; using default"
+            );
+            DEFAULT_IO_COMPLETION_TIMEOUT
+        }
+        Ok(parsed) => Duration::from_secs(parsed),
+        Err(error) => {
+            warn!(
+                env_value = %value,
+                %error,
+                default_secs = DEFAULT_IO_COMPLETION_TIMEOUT.as_secs(),
+                "Ignoring invalid TEAMY_MFT_IO_TIMEOUT_SECS; using default"
+            );
+            DEFAULT_IO_COMPLETION_TIMEOUT
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::read::physical_read_plan::PhysicalReadPlan;
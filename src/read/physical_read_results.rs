@@ -1,18 +1,21 @@
 use crate::read::logical_read_plan::LogicalFileSegment;
 use crate::read::logical_read_plan::LogicalReadPlan;
 use crate::read::physical_read_request::PhysicalReadRequest;
+use crate::read::read_error::ReadError;
 use humansize::BINARY;
 use std::collections::BTreeSet;
 use std::io::Cursor;
 use std::io::Seek;
 use std::io::SeekFrom;
 use std::io::Write;
+use std::ops::Range;
 use teamy_uom_extensions::HumanInformationExt;
 use tracing::debug;
 #[cfg(feature = "extended_observability")]
 use tracing::debug_span;
 use tracing::info_span;
 use tracing::trace;
+use uom::ConstZero;
 use uom::si::information::byte;
 use uom::si::usize::Information;
 
@@ -102,6 +105,52 @@ impl PhysicalReadResults {
         }
     }
 
+    /// Check that every physical logical segment in `logical_plan` is fully covered by read results.
+    ///
+    /// Walks the plan the same way [`Self::iter`] does, but instead of yielding data it records
+    /// the logical offset ranges that no entry covers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the uncovered logical offset ranges, in logical order, if any exist.
+    pub fn verify(&self, logical_plan: &LogicalReadPlan) -> Result<(), Vec<Range<Information>>> {
+        let mut gaps = Vec::new();
+        for segment in &logical_plan.segments {
+            let Some(physical_segment) = segment.as_physical_read_request() else {
+                continue;
+            };
+            let physical_end = physical_segment.offset + physical_segment.length;
+            let to_logical = |physical: Information| {
+                segment.logical_offset + (physical - physical_segment.offset)
+            };
+            let mut cursor = physical_segment.offset;
+            while cursor < physical_end {
+                let probe = PhysicalReadResultEntry {
+                    request: PhysicalReadRequest::new(cursor, Information::new::<byte>(usize::MAX)),
+                    data: vec![],
+                };
+                let containing = self
+                    .entries
+                    .range(..=&probe)
+                    .next_back()
+                    .filter(|entry| cursor < entry.request.offset + entry.request.length);
+                if let Some(entry) = containing {
+                    cursor = (entry.request.offset + entry.request.length).min(physical_end);
+                    continue;
+                }
+                let gap_end = self
+                    .entries
+                    .range(&probe..)
+                    .map(|entry| entry.request.offset)
+                    .find(|offset| *offset > cursor)
+                    .map_or(physical_end, |offset| offset.min(physical_end));
+                gaps.push(to_logical(cursor)..to_logical(gap_end));
+                cursor = gap_end;
+            }
+        }
+        if gaps.is_empty() { Ok(()) } else { Err(gaps) }
+    }
+
     /// Reads planned data into a writer.
     ///
     /// This is the execution layer on top of [`Self::read_into_iter`].
@@ -114,7 +163,7 @@ impl PhysicalReadResults {
         &self,
         logical_plan: &LogicalReadPlan,
         writer: &mut W,
-    ) -> eyre::Result<()> {
+    ) -> Result<(), ReadError> {
         for step in self.iter(logical_plan) {
             let step = step?;
             write_step(writer, &step)?;
@@ -145,7 +194,7 @@ impl PhysicalReadResults {
         &self,
         logical_plan: &LogicalReadPlan,
         output_path: impl AsRef<std::path::Path>,
-    ) -> eyre::Result<()> {
+    ) -> Result<(), ReadError> {
         let output_path = output_path.as_ref();
         let _span = info_span!(
             "write_physical_read_results_to_path",
@@ -205,18 +254,53 @@ impl PhysicalReadResults {
     /// # Errors
     ///
     /// Returns an error if expected physical data is missing.
-    pub fn to_vec(&self, logical_plan: &LogicalReadPlan) -> eyre::Result<Vec<u8>> {
+    pub fn to_vec(&self, logical_plan: &LogicalReadPlan) -> Result<Vec<u8>, ReadError> {
         let mut bytes = vec![0u8; logical_plan.total_logical_size().get::<byte>()];
         let mut cursor = Cursor::new(bytes.as_mut_slice());
         self.write(logical_plan, &mut cursor)?;
         Ok(bytes)
     }
+
+    /// CRC-32 of the reassembled logical output, equal to hashing [`Self::to_vec`].
+    ///
+    /// Completions arrive out of order, so this hashes after the entries have been sorted,
+    /// streaming chunks in logical order and zero-filling sparse gaps without materializing
+    /// the whole output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if expected physical data is missing.
+    pub fn crc32(&self, logical_plan: &LogicalReadPlan) -> Result<u32, ReadError> {
+        const ZEROS: [u8; 4096] = [0; 4096];
+        let mut hasher = crc32fast::Hasher::new();
+        let mut hash_zeros = |mut length: usize| {
+            while length > 0 {
+                let chunk = length.min(ZEROS.len());
+                hasher.update(&ZEROS[..chunk]);
+                length -= chunk;
+            }
+        };
+        let mut cursor = Information::ZERO;
+        for step in self.iter(logical_plan) {
+            let step = step?;
+            if step.logical_offset > cursor {
+                hash_zeros((step.logical_offset - cursor).get::<byte>());
+            }
+            hasher.update(step.bytes);
+            cursor = step.logical_offset + step.length();
+        }
+        let total = logical_plan.total_logical_size();
+        if total > cursor {
+            hash_zeros((total - cursor).get::<byte>());
+        }
+        Ok(hasher.finalize())
+    }
 }
 
 fn write_step<W: Seek + Write>(
     writer: &mut W,
     step: &PhysicalReadResultsIterValue<'_>,
-) -> eyre::Result<()> {
+) -> Result<(), ReadError> {
     #[cfg(feature = "extended_observability")]
     let _span = debug_span!("write_logical_mft_step").entered();
 
@@ -226,7 +310,7 @@ fn write_step<W: Seek + Write>(
 }
 
 impl<'a> Iterator for PhysicalReadResultsIter<'a> {
-    type Item = eyre::Result<PhysicalReadResultsIterValue<'a>>;
+    type Item = Result<PhysicalReadResultsIterValue<'a>, ReadError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
@@ -257,20 +341,15 @@ impl<'a> Iterator for PhysicalReadResultsIter<'a> {
                     data: vec![],
                 };
                 let entry = self.entries.range(..=probe).next_back();
-                let Some(entry) = entry else {
+                let Some(entry) = entry.filter(|entry| {
+                    entry.request.offset <= physical_offset_current
+                        && physical_offset_current < entry.request.offset + entry.request.length
+                }) else {
                     self.done = true;
-                    return Some(Err(eyre::eyre!(
-                        "Missing physical read data at offset {physical_offset_current:?} - no entries available"
-                    )));
+                    return Some(Err(ReadError::MissingCoverage {
+                        physical_offset: physical_offset_current,
+                    }));
                 };
-                if !(entry.request.offset <= physical_offset_current
-                    && physical_offset_current < entry.request.offset + entry.request.length)
-                {
-                    self.done = true;
-                    return Some(Err(eyre::eyre!(
-                        "Missing physical read data at offset {physical_offset_current:?} - not contained in any entry"
-                    )));
-                }
 
                 let offset_within_entry = physical_offset_current - entry.request.offset;
                 let bytes_available = entry.request.length - offset_within_entry;
@@ -321,6 +400,7 @@ mod test {
     use crate::read::physical_read_results::PhysicalReadResultEntry;
     use crate::read::physical_read_results::PhysicalReadResults;
     use crate::read::physical_read_results::PhysicalReadResultsIterValue;
+    use crate::read::read_error::ReadError;
     use uom::si::information::byte;
     use uom::si::usize::Information;
 
@@ -380,6 +460,59 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn crc32_matches_hash_of_reassembled_output() -> eyre::Result<()> {
+        let read_plan = LogicalReadPlan {
+            segments: [
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(0),
+                    length: Information::new::<byte>(4),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(2000),
+                    },
+                },
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(4),
+                    length: Information::new::<byte>(5000),
+                    kind: LogicalFileSegmentKind::Sparse,
+                },
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(5004),
+                    length: Information::new::<byte>(3),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(1000),
+                    },
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let read_results = PhysicalReadResults {
+            entries: [
+                PhysicalReadResultEntry {
+                    request: PhysicalReadRequest {
+                        offset: Information::new::<byte>(1000),
+                        length: Information::new::<byte>(3),
+                    },
+                    data: b"XYZ".to_vec(),
+                },
+                PhysicalReadResultEntry {
+                    request: PhysicalReadRequest {
+                        offset: Information::new::<byte>(2000),
+                        length: Information::new::<byte>(4),
+                    },
+                    data: b"ABCD".to_vec(),
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let expected = crc32fast::hash(&read_results.to_vec(&read_plan)?);
+        assert_eq!(read_results.crc32(&read_plan)?, expected);
+        Ok(())
+    }
+
     #[test]
     fn writes_from_predecessor_when_aligned_overread() -> eyre::Result<()> {
         // Logical segment expects data at physical offset 100 of length 10.
@@ -463,7 +596,7 @@ mod test {
 
         let plan = read_results
             .iter(&read_plan)
-            .collect::<eyre::Result<Vec<PhysicalReadResultsIterValue<'_>>>>()?;
+            .collect::<Result<Vec<PhysicalReadResultsIterValue<'_>>, ReadError>>()?;
 
         assert_eq!(plan.len(), 1);
         let step = plan[0];
@@ -505,8 +638,87 @@ mod test {
 
         let err = read_results
             .iter(&read_plan)
-            .collect::<eyre::Result<Vec<PhysicalReadResultsIterValue<'_>>>>()
+            .collect::<Result<Vec<PhysicalReadResultsIterValue<'_>>, ReadError>>()
             .expect_err("expected missing data error");
         assert!(err.to_string().contains("Missing physical read data"));
+        assert!(matches!(
+            err,
+            ReadError::MissingCoverage { physical_offset } if physical_offset == Information::new::<byte>(104)
+        ));
+    }
+
+    #[test]
+    fn verify_reports_uncovered_logical_ranges() {
+        let read_plan = LogicalReadPlan {
+            segments: [
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(0),
+                    length: Information::new::<byte>(8),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(100),
+                    },
+                },
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(8),
+                    length: Information::new::<byte>(4),
+                    kind: LogicalFileSegmentKind::Sparse,
+                },
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(12),
+                    length: Information::new::<byte>(4),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<byte>(500),
+                    },
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        // Covers 100..104 only; 104..108 and the whole second physical segment are missing.
+        let read_results = PhysicalReadResults {
+            entries: [PhysicalReadResultEntry {
+                request: PhysicalReadRequest {
+                    offset: Information::new::<byte>(100),
+                    length: Information::new::<byte>(4),
+                },
+                data: vec![1, 2, 3, 4],
+            }]
+            .into_iter()
+            .collect(),
+        };
+
+        let gaps = read_results
+            .verify(&read_plan)
+            .expect_err("expected uncovered ranges");
+        assert_eq!(
+            gaps,
+            vec![
+                Information::new::<byte>(4)..Information::new::<byte>(8),
+                Information::new::<byte>(12)..Information::new::<byte>(16),
+            ]
+        );
+
+        let complete = PhysicalReadResults {
+            entries: [
+                PhysicalReadResultEntry {
+                    request: PhysicalReadRequest {
+                        offset: Information::new::<byte>(64),
+                        length: Information::new::<byte>(64),
+                    },
+                    data: vec![0; 64],
+                },
+                PhysicalReadResultEntry {
+                    request: PhysicalReadRequest {
+                        offset: Information::new::<byte>(500),
+                        length: Information::new::<byte>(4),
+                    },
+                    data: vec![0; 4],
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(complete.verify(&read_plan), Ok(()));
     }
 }
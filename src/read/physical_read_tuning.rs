@@ -0,0 +1,62 @@
+use eyre::ensure;
+use tracing::warn;
+use uom::si::information::byte;
+use uom::si::information::mebibyte;
+use uom::si::usize::Information;
+
+const SECTOR_SIZE_BYTES: usize = 512;
+
+/// How a physical read plan is split and executed against a volume.
+///
+/// Optimal values differ by device: NVMe drives benefit from larger chunks and deeper queues,
+/// spinning disks from smaller ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicalReadTuning {
+    /// Maximum size of a single read request.
+    pub chunk_size: Information,
+    /// Maximum overlapped reads in flight; `None` uses `TEAMY_MFT_MAX_IN_FLIGHT_IO` or the built-in default.
+    pub queue_depth: Option<usize>,
+    /// Read only the first N records of the stream instead of all of it; `None` reads everything.
+    pub max_entries: Option<usize>,
+}
+
+impl Default for PhysicalReadTuning {
+    fn default() -> Self {
+        Self {
+            chunk_size: Information::new::<mebibyte>(1),
+            queue_depth: None,
+            max_entries: None,
+        }
+    }
+}
+
+impl PhysicalReadTuning {
+    /// Build tuning from optional overrides, keeping the defaults for anything unset.
+    ///
+    /// A chunk size that is not a multiple of the 512-byte sector size is accepted with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk size or queue depth is zero.
+    pub fn new(chunk_size_bytes: Option<usize>, queue_depth: Option<usize>) -> eyre::Result<Self> {
+        let mut rtn = Self::default();
+        if let Some(chunk_size_bytes) = chunk_size_bytes {
+            ensure!(
+                chunk_size_bytes > 0,
+                "--chunk-size must be greater than zero"
+            );
+            if !chunk_size_bytes.is_multiple_of(SECTOR_SIZE_BYTES) {
+                warn!(
+                    chunk_size_bytes,
+                    "Chunk size is not a multiple of the {SECTOR_SIZE_BYTES}-byte sector size; reads may be split or rejected"
+                );
+            }
+            rtn.chunk_size = Information::new::<byte>(chunk_size_bytes);
+        }
+        if let Some(queue_depth) = queue_depth {
+            ensure!(queue_depth > 0, "--queue-depth must be greater than zero");
+            rtn.queue_depth = Some(queue_depth);
+        }
+        Ok(rtn)
+    }
+}
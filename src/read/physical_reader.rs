@@ -1,13 +1,20 @@
 use crate::read::active_physical_read_request::ActivePhysicalReadRequest;
+use crate::read::physical_read_plan::PhysicalReadPlan;
+use crate::read::physical_read_plan::io_completion_timeout;
+use crate::read::physical_read_plan::max_in_flight_io;
 use crate::read::physical_read_request::PhysicalReadRequest;
 use crate::read::physical_read_results::PhysicalReadResultEntry;
 use crate::read::physical_read_results::PhysicalReadResults;
-use eyre::Context;
-use eyre::ContextCompat;
+use crate::read::read_error::ReadError;
 use std::collections::BTreeSet;
+use std::time::Duration;
 use tracing::info_span;
 use tracing::instrument;
 use tracing::trace;
+use tracing::warn;
+use uom::si::information::byte;
+use uom::si::usize::Information;
+use windows::Win32::Foundation::E_UNEXPECTED;
 use windows::Win32::Foundation::HANDLE;
 use windows::Win32::Storage::FileSystem::CreateFileW;
 use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_NORMAL;
@@ -16,24 +23,93 @@ use windows::Win32::Storage::FileSystem::FILE_GENERIC_READ;
 use windows::Win32::Storage::FileSystem::FILE_SHARE_DELETE;
 use windows::Win32::Storage::FileSystem::FILE_SHARE_READ;
 use windows::Win32::Storage::FileSystem::FILE_SHARE_WRITE;
+use windows::Win32::Storage::FileSystem::GetFileSizeEx;
 use windows::Win32::Storage::FileSystem::OPEN_EXISTING;
+use windows::Win32::System::IO::CancelIoEx;
 use windows::Win32::System::IO::CreateIoCompletionPort;
 use windows::core::Owned;
 use windows::core::PCWSTR;
 use windows::core::Param;
 
+/// Overlapped reader bound to one file or volume handle and its completion port.
+///
+/// The handle and port are opened once, so any number of plans against the same volume can
+/// be read through [`PhysicalReader::read_plan`]. Both are closed when the reader is dropped.
 #[derive(Debug)]
 pub struct PhysicalReader {
     remaining: Vec<PhysicalReadRequest>,
     results: Vec<Option<PhysicalReadResultEntry>>,
     in_flight: usize,
     max_in_flight: usize,
+    /// How long to wait for a single completion before giving up
+    completion_timeout: Duration,
+    /// Length of the file when the handle reports one; volume handles do not, so every
+    /// short completion against a volume is an error
+    end_of_file: Option<Information>,
     /// File handle to read from
     file_handle: Owned<HANDLE>,
     /// IO Completion Port handle
     iocp_handle: Owned<HANDLE>,
 }
 
+/// Source of completed read requests.
+///
+/// Abstracted so the completion handling can be exercised without a real device.
+pub trait PhysicalReadCompletionSource {
+    /// Wait up to `timeout` for the next completion; `Ok(None)` means the wait timed out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting fails or the completed IO reports an error.
+    fn next_completion(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(PhysicalReadResultEntry, usize)>, ReadError>;
+}
+
+impl PhysicalReadCompletionSource for HANDLE {
+    fn next_completion(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(PhysicalReadResultEntry, usize)>, ReadError> {
+        ActivePhysicalReadRequest::receive(*self, timeout)
+    }
+}
+
+/// Wait for the next completion, converting a timeout into a descriptive error.
+///
+/// A completion that delivers fewer bytes than requested is only accepted when it stops at
+/// `end_of_file`; anywhere else it would leave a zeroed gap in the reassembled output.
+///
+/// # Errors
+///
+/// Returns the source's error, [`ReadError::IoPending`] if no completion arrives within
+/// `timeout`, or [`ReadError::ShortRead`] for a truncated completion before `end_of_file`.
+pub fn await_completion(
+    source: &mut impl PhysicalReadCompletionSource,
+    timeout: Duration,
+    in_flight: usize,
+    end_of_file: Option<Information>,
+) -> Result<(PhysicalReadResultEntry, usize), ReadError> {
+    let (entry, response_index) = source
+        .next_completion(timeout)?
+        .ok_or(ReadError::IoPending {
+            in_flight,
+            timeout: Some(timeout),
+        })?;
+    let bytes_transferred = entry.data.len();
+    let reached_end_of_file = end_of_file.is_some_and(|end_of_file| {
+        entry.request.offset + Information::new::<byte>(bytes_transferred) >= end_of_file
+    });
+    if bytes_transferred < entry.request.length.get::<byte>() && !reached_end_of_file {
+        return Err(ReadError::ShortRead {
+            request: entry.request,
+            bytes_transferred,
+        });
+    }
+    Ok((entry, response_index))
+}
+
 #[derive(Debug)]
 pub enum PhysicalReaderEnqueueResult {
     Enqueued,
@@ -42,40 +118,75 @@ pub enum PhysicalReaderEnqueueResult {
 }
 
 impl PhysicalReader {
+    /// Open a reader for `filename` that can serve multiple plans.
+    ///
+    /// `queue_depth` of `None` falls back to `TEAMY_MFT_MAX_IN_FLIGHT_IO` or the built-in
+    /// default, and each completion wait is bounded by `TEAMY_MFT_IO_TIMEOUT_SECS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadError::OpenFailed`] if the file cannot be opened or the completion port
+    /// cannot be created.
+    pub fn open(
+        filename: impl Param<PCWSTR>,
+        queue_depth: Option<usize>,
+    ) -> Result<Self, ReadError> {
+        let max_in_flight = queue_depth.unwrap_or_else(max_in_flight_io);
+        Self::try_new(filename, [], max_in_flight, io_completion_timeout())
+    }
+
     /// Create a reader that queues overlapping reads against the specified file.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be opened or the completion port cannot be created.
+    /// Returns [`ReadError::OpenFailed`] if the file cannot be opened or the completion port
+    /// cannot be created.
     #[instrument(skip_all)]
     pub fn try_new(
         filename: impl Param<PCWSTR>,
         requests: impl IntoIterator<Item = PhysicalReadRequest>,
         max_in_flight: usize,
-    ) -> eyre::Result<Self> {
+        completion_timeout: Duration,
+    ) -> Result<Self, ReadError> {
         // SAFETY: `CreateFileW` is called with valid path parameters and flags for overlapped IO.
         let file_handle = unsafe {
-            Owned::new(CreateFileW(
-                filename,
-                FILE_GENERIC_READ.0,
-                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
-                None,
-                OPEN_EXISTING,
-                FILE_ATTRIBUTE_NORMAL | FILE_FLAG_OVERLAPPED,
-                None,
-            )?)
+            Owned::new(
+                CreateFileW(
+                    filename,
+                    FILE_GENERIC_READ.0,
+                    FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                    None,
+                    OPEN_EXISTING,
+                    FILE_ATTRIBUTE_NORMAL | FILE_FLAG_OVERLAPPED,
+                    None,
+                )
+                .map_err(ReadError::OpenFailed)?,
+            )
         };
 
+        let mut file_size = 0_i64;
+        // SAFETY: `file_handle` is a valid open handle and `file_size` outlives the call.
+        let end_of_file = unsafe { GetFileSizeEx(*file_handle, &raw mut file_size) }
+            .ok()
+            .and_then(|()| usize::try_from(file_size).ok())
+            .filter(|file_size| *file_size > 0)
+            .map(Information::new::<byte>);
+
         let remaining: Vec<PhysicalReadRequest> = requests.into_iter().collect();
         let results = (0..remaining.len()).map(|_| None).collect();
         // SAFETY: `CreateIoCompletionPort` is provided a valid handle and acceptable parameters.
-        let completion_port =
-            unsafe { Owned::new(CreateIoCompletionPort(*file_handle, None, 0, 0)?) };
+        let completion_port = unsafe {
+            Owned::new(
+                CreateIoCompletionPort(*file_handle, None, 0, 0).map_err(ReadError::OpenFailed)?,
+            )
+        };
         Ok(Self {
             remaining,
             results,
             in_flight: 0,
             max_in_flight,
+            completion_timeout,
+            end_of_file,
             file_handle,
             iocp_handle: completion_port,
         })
@@ -86,7 +197,7 @@ impl PhysicalReader {
     /// # Errors
     ///
     /// Propagates errors returned by [`PhysicalReader::try_enqueue`].
-    pub fn enqueue_until_saturation(&mut self) -> eyre::Result<()> {
+    pub fn enqueue_until_saturation(&mut self) -> Result<(), ReadError> {
         trace!(
             in_flight = self.in_flight,
             remaining = self.remaining.len(),
@@ -97,12 +208,34 @@ impl PhysicalReader {
         Ok(())
     }
 
+    /// Read every request in `plan` through this reader's handle and completion port.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReadError::IoPending`] if a previous read left requests in flight, or the
+    /// error of a failed queue or completion.
+    pub fn read_plan(&mut self, plan: PhysicalReadPlan) -> Result<PhysicalReadResults, ReadError> {
+        if self.in_flight > 0 {
+            return Err(ReadError::IoPending {
+                in_flight: self.in_flight,
+                timeout: None,
+            });
+        }
+        self.remaining = plan.into_iter().collect();
+        self.results = (0..self.remaining.len()).map(|_| None).collect();
+        self.drain()
+    }
+
     /// Drain the queue and return the aggregated read results.
     ///
     /// # Errors
     ///
     /// Returns an error if queueing or completion handling fails.
-    pub fn read_all(mut self) -> eyre::Result<PhysicalReadResults> {
+    pub fn read_all(mut self) -> Result<PhysicalReadResults, ReadError> {
+        self.drain()
+    }
+
+    fn drain(&mut self) -> Result<PhysicalReadResults, ReadError> {
         let _span = info_span!(
             "drain_physical_reader_iocp",
             request_count = self.remaining.len(),
@@ -120,28 +253,51 @@ impl PhysicalReader {
         }
         trace!("All IOCP reads completed");
 
-        let entries = self
-            .results
+        let entries = std::mem::take(&mut self.results)
             .into_iter()
             .enumerate()
-            .map(|(i, o)| o.wrap_err_with(|| format!("Missing response index {i}")))
-            .collect::<eyre::Result<BTreeSet<_>>>()?;
+            .map(|(i, o)| {
+                o.ok_or_else(|| ReadError::Io {
+                    request: None,
+                    source: windows::core::Error::new(
+                        E_UNEXPECTED,
+                        format!("Missing response index {i}"),
+                    ),
+                })
+            })
+            .collect::<Result<BTreeSet<_>, _>>()?;
         Ok(PhysicalReadResults { entries })
     }
 
     /// Await a single IO completion result.
     ///
+    /// On timeout the outstanding reads are cancelled, but their request allocations
+    /// stay leaked because the kernel may still complete into them.
+    ///
     /// # Errors
     ///
-    /// Returns an error if waiting for the completion port fails.
-    pub fn receive_result(&mut self) -> eyre::Result<()> {
-        match ActivePhysicalReadRequest::receive(*self.iocp_handle) {
+    /// Returns an error if waiting for the completion port fails or times out, or if the
+    /// completion is a [`ReadError::ShortRead`].
+    pub fn receive_result(&mut self) -> Result<(), ReadError> {
+        let mut completion_port = *self.iocp_handle;
+        match await_completion(
+            &mut completion_port,
+            self.completion_timeout,
+            self.in_flight,
+            self.end_of_file,
+        ) {
             Ok((entry, response_index)) => {
                 self.results[response_index] = Some(entry);
                 self.in_flight -= 1;
                 Ok(())
             }
-            Err(e) => Err(e),
+            Err(e) => {
+                // SAFETY: `file_handle` is a valid open handle owned by this reader.
+                if let Err(cancel_error) = unsafe { CancelIoEx(*self.file_handle, None) } {
+                    warn!(?cancel_error, "Failed to cancel outstanding IOCP reads");
+                }
+                Err(e)
+            }
         }
     }
 
@@ -150,7 +306,7 @@ impl PhysicalReader {
     /// # Errors
     ///
     /// Fails if sending the read request to the file handle fails.
-    pub fn try_enqueue(&mut self) -> eyre::Result<PhysicalReaderEnqueueResult> {
+    pub fn try_enqueue(&mut self) -> Result<PhysicalReaderEnqueueResult, ReadError> {
         if self.in_flight >= self.max_in_flight {
             return Ok(PhysicalReaderEnqueueResult::Full);
         }
@@ -160,9 +316,7 @@ impl PhysicalReader {
 
         let response_index = self.results.len() - self.remaining.len() - 1;
         let request = ActivePhysicalReadRequest::new(request, response_index);
-        request
-            .send(*self.file_handle)
-            .wrap_err("Failed to send read request")?;
+        request.send(*self.file_handle)?;
         self.in_flight += 1;
         trace!(
             in_flight = self.in_flight,
@@ -173,3 +327,163 @@ impl PhysicalReader {
         Ok(PhysicalReaderEnqueueResult::Enqueued)
     }
 }
+
+impl Drop for PhysicalReader {
+    fn drop(&mut self) {
+        if self.in_flight == 0 {
+            return;
+        }
+        // SAFETY: `file_handle` is still open; the handles themselves are closed by `Owned`.
+        if let Err(cancel_error) = unsafe { CancelIoEx(*self.file_handle, None) } {
+            warn!(
+                ?cancel_error,
+                in_flight = self.in_flight,
+                "Failed to cancel outstanding IOCP reads on drop"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::windows_utils::string::EasyPCWSTR;
+    use std::collections::VecDeque;
+
+    struct MockCompletionSource {
+        completions: VecDeque<Option<(PhysicalReadResultEntry, usize)>>,
+    }
+
+    impl PhysicalReadCompletionSource for MockCompletionSource {
+        fn next_completion(
+            &mut self,
+            _timeout: Duration,
+        ) -> Result<Option<(PhysicalReadResultEntry, usize)>, ReadError> {
+            Ok(self.completions.pop_front().flatten())
+        }
+    }
+
+    #[test]
+    fn await_completion_returns_completed_entry() {
+        let entry = PhysicalReadResultEntry {
+            request: PhysicalReadRequest::new(
+                Information::new::<byte>(0),
+                Information::new::<byte>(512),
+            ),
+            data: vec![0; 512],
+        };
+        let mut source = MockCompletionSource {
+            completions: VecDeque::from([Some((entry.clone(), 3))]),
+        };
+
+        let (received, response_index) =
+            await_completion(&mut source, Duration::from_secs(1), 1, None).unwrap();
+
+        assert_eq!(received, entry);
+        assert_eq!(response_index, 3);
+    }
+
+    #[test]
+    fn await_completion_reports_in_flight_count_on_timeout() {
+        let mut source = MockCompletionSource {
+            completions: VecDeque::from([None]),
+        };
+
+        let error = await_completion(&mut source, Duration::from_millis(10), 4, None).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ReadError::IoPending {
+                in_flight: 4,
+                timeout: Some(_)
+            }
+        ));
+        assert!(
+            error
+                .to_string()
+                .contains("4 read request(s) still in flight")
+        );
+    }
+
+    #[test]
+    fn await_completion_detects_short_completion() {
+        let request = PhysicalReadRequest::new(
+            Information::new::<byte>(4096),
+            Information::new::<byte>(1024),
+        );
+        let short = PhysicalReadResultEntry {
+            request,
+            data: vec![0; 512],
+        };
+        let mut source = MockCompletionSource {
+            completions: VecDeque::from([Some((short.clone(), 0)), Some((short, 0))]),
+        };
+
+        let error = await_completion(&mut source, Duration::from_secs(1), 1, None).unwrap_err();
+        assert!(matches!(
+            error,
+            ReadError::ShortRead {
+                bytes_transferred: 512,
+                ..
+            }
+        ));
+        assert!(error.to_string().contains("offset 4096"));
+
+        let (at_end_of_file, _) = await_completion(
+            &mut source,
+            Duration::from_secs(1),
+            1,
+            Some(Information::new::<byte>(4608)),
+        )
+        .unwrap();
+        assert_eq!(at_end_of_file.request, request);
+    }
+
+    #[test]
+    fn opening_a_missing_file_is_open_failed() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let filename = dir.path().join("missing.bin").as_os_str().easy_pcwstr()?;
+
+        let error = PhysicalReader::open(&filename, None).unwrap_err();
+
+        assert!(matches!(error, ReadError::OpenFailed(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn one_reader_serves_multiple_plans() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("volume.bin");
+        let contents = (0..=250_u8).cycle().take(4096).collect::<Vec<_>>();
+        std::fs::write(&path, &contents)?;
+        let filename = path.as_os_str().easy_pcwstr()?;
+        let request = |offset: usize, length: usize| {
+            PhysicalReadRequest::new(
+                Information::new::<byte>(offset),
+                Information::new::<byte>(length),
+            )
+        };
+
+        let mut reader = PhysicalReader::open(&filename, Some(2))?;
+        let first =
+            reader.read_plan([request(0, 512), request(1024, 512)].into_iter().collect())?;
+        let second = reader.read_plan([request(3072, 1024)].into_iter().collect())?;
+
+        let first = first
+            .entries
+            .iter()
+            .map(|entry| entry.data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            first,
+            vec![contents[..512].to_vec(), contents[1024..1536].to_vec()]
+        );
+        let second = second
+            .entries
+            .iter()
+            .map(|entry| entry.data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(second, vec![contents[3072..].to_vec()]);
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use crate::read::physical_read_request::PhysicalReadRequest;
+use std::time::Duration;
+use uom::si::information::byte;
+use uom::si::usize::Information;
+use windows::Win32::Foundation::ERROR_INVALID_PARAMETER;
+use windows::Win32::Foundation::ERROR_OPERATION_ABORTED;
+use windows::core::HRESULT;
+
+/// Why a physical read failed.
+///
+/// Returned by [`crate::read::physical_reader::PhysicalReader`] and
+/// [`crate::read::physical_read_results::PhysicalReadResults`] so embedders can react to a
+/// specific cause, e.g. retrying only on [`ReadError::ShortRead`]. It converts into
+/// `eyre::Report` like any other error.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The file or volume could not be opened, or its completion port could not be created.
+    OpenFailed(windows::core::Error),
+    /// Reads are still outstanding: no completion arrived within `timeout`, or a new plan was
+    /// started before the previous one drained (`timeout` is `None`).
+    IoPending {
+        in_flight: usize,
+        timeout: Option<Duration>,
+    },
+    /// The device rejected a request whose offset or length is not a multiple of 512 bytes.
+    Unaligned { request: PhysicalReadRequest },
+    /// A completion delivered fewer bytes than the request asked for before the end of the file.
+    ShortRead {
+        request: PhysicalReadRequest,
+        bytes_transferred: usize,
+    },
+    /// No read result covers this physical offset of the logical plan.
+    MissingCoverage { physical_offset: Information },
+    /// The request was aborted, e.g. by `CancelIoEx`, before it completed.
+    Cancelled { request: PhysicalReadRequest },
+    /// Queueing, waiting for, or completing a read failed for another reason.
+    Io {
+        request: Option<PhysicalReadRequest>,
+        source: windows::core::Error,
+    },
+    /// Writing the reassembled output failed.
+    Write(std::io::Error),
+}
+
+impl ReadError {
+    /// Classify a failure reported by `ReadFile` or the completion port for `request`.
+    #[must_use]
+    pub fn from_request_error(request: PhysicalReadRequest, source: windows::core::Error) -> Self {
+        let is_aligned = |value: Information| value.get::<byte>().is_multiple_of(512);
+        if source.code() == HRESULT::from_win32(ERROR_OPERATION_ABORTED.0) {
+            Self::Cancelled { request }
+        } else if source.code() == HRESULT::from_win32(ERROR_INVALID_PARAMETER.0)
+            && !(is_aligned(request.offset) && is_aligned(request.length))
+        {
+            Self::Unaligned { request }
+        } else {
+            Self::Io {
+                request: Some(request),
+                source,
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::OpenFailed(source) => write!(f, "Failed to open file for reading: {source}"),
+            ReadError::IoPending {
+                in_flight,
+                timeout: Some(timeout),
+            } => write!(
+                f,
+                "Timed out after {timeout:?} waiting for an IO completion with {in_flight} read request(s) still in flight; the volume may be unresponsive"
+            ),
+            ReadError::IoPending {
+                in_flight,
+                timeout: None,
+            } => write!(
+                f,
+                "Cannot start a new read plan with {in_flight} read request(s) still in flight"
+            ),
+            ReadError::Unaligned { request } => write!(
+                f,
+                "Read of {} bytes at offset {} was rejected because it is not 512-byte aligned",
+                request.length.get::<byte>(),
+                request.offset.get::<byte>()
+            ),
+            ReadError::ShortRead {
+                request,
+                bytes_transferred,
+            } => write!(
+                f,
+                "Short read at offset {}: received {bytes_transferred} of {} bytes",
+                request.offset.get::<byte>(),
+                request.length.get::<byte>()
+            ),
+            ReadError::MissingCoverage { physical_offset } => write!(
+                f,
+                "Missing physical read data at offset {}",
+                physical_offset.get::<byte>()
+            ),
+            ReadError::Cancelled { request } => write!(
+                f,
+                "Read at offset {} was cancelled",
+                request.offset.get::<byte>()
+            ),
+            ReadError::Io {
+                request: Some(request),
+                source,
+            } => write!(
+                f,
+                "Read of {} bytes at offset {} failed: {source}",
+                request.length.get::<byte>(),
+                request.offset.get::<byte>()
+            ),
+            ReadError::Io {
+                request: None,
+                source,
+            } => write!(f, "IO completion failed: {source}"),
+            ReadError::Write(source) => write!(f, "Failed writing read results: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::OpenFailed(source) | ReadError::Io { source, .. } => Some(source),
+            ReadError::Write(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ReadError {
+    fn from(source: std::io::Error) -> Self {
+        ReadError::Write(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReadError;
+    use crate::read::physical_read_request::PhysicalReadRequest;
+    use uom::si::information::byte;
+    use uom::si::usize::Information;
+    use windows::Win32::Foundation::ERROR_INVALID_PARAMETER;
+    use windows::Win32::Foundation::ERROR_OPERATION_ABORTED;
+    use windows::Win32::Foundation::ERROR_READ_FAULT;
+    use windows::core::HRESULT;
+
+    fn request(offset: usize, length: usize) -> PhysicalReadRequest {
+        PhysicalReadRequest::new(
+            Information::new::<byte>(offset),
+            Information::new::<byte>(length),
+        )
+    }
+
+    fn win32_error(code: u32) -> windows::core::Error {
+        windows::core::Error::from_hresult(HRESULT::from_win32(code))
+    }
+
+    #[test]
+    fn request_errors_are_classified_by_cause() {
+        assert!(matches!(
+            ReadError::from_request_error(request(0, 512), win32_error(ERROR_OPERATION_ABORTED.0)),
+            ReadError::Cancelled { .. }
+        ));
+        assert!(matches!(
+            ReadError::from_request_error(
+                request(100, 512),
+                win32_error(ERROR_INVALID_PARAMETER.0)
+            ),
+            ReadError::Unaligned { .. }
+        ));
+        assert!(matches!(
+            ReadError::from_request_error(request(0, 512), win32_error(ERROR_INVALID_PARAMETER.0)),
+            ReadError::Io {
+                request: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            ReadError::from_request_error(request(0, 512), win32_error(ERROR_READ_FAULT.0)),
+            ReadError::Io { .. }
+        ));
+    }
+}
@@ -1,20 +1,34 @@
 mod drive_sync_info;
 mod if_exists_output_behaviour;
+mod sync_dry_run;
 mod sync_executor;
 mod sync_index;
+mod sync_manifest;
 mod sync_mft;
 mod sync_path;
 mod sync_plan;
+mod sync_progress;
+mod sync_summary;
+mod sync_timings;
 
 pub use drive_sync_info::DriveSyncInfo;
 pub use drive_sync_info::resolve_drive_infos;
 pub use drive_sync_info::resolve_drive_infos_in_dir;
 pub use drive_sync_info::resolve_drive_infos_in_dir_for_letters;
 pub use if_exists_output_behaviour::IfExistsOutputBehaviour;
+pub use sync_dry_run::DryRunDriveSummary;
 pub use sync_executor::execute_sync;
 pub use sync_index::SyncIndex;
+pub use sync_manifest::SYNC_MANIFEST_FILE_NAME;
+pub use sync_manifest::SyncManifest;
+pub use sync_manifest::SyncManifestDrive;
 pub use sync_mft::SyncMft;
 pub use sync_mft::read_physical_mft_stream_with_info;
 pub use sync_path::sync_path_into_published_overlay;
 pub use sync_path::sync_path_recursively_into_published_overlay;
 pub use sync_plan::SyncPlan;
+pub use sync_progress::SyncPhase;
+pub use sync_progress::SyncProgress;
+pub use sync_progress::sync_with_progress;
+pub use sync_summary::SyncSummary;
+pub use sync_timings::SyncTimings;
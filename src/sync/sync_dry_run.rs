@@ -0,0 +1,128 @@
+use crate::mft::mft_physical_read::build_physical_read_plan;
+use crate::mft::mft_physical_read::plan_physical_stream;
+use crate::mft::mft_record_number::MftRecordNumber;
+use crate::read::logical_read_plan::LogicalReadPlan;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
+use humansize::BINARY;
+use std::fmt::Display;
+use std::path::PathBuf;
+use teamy_uom_extensions::HumanInformationExt;
+use thousands::Separable;
+use uom::si::usize::Information;
+
+/// What syncing one drive's MFT would read and write, computed without the bulk read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunDriveSummary {
+    pub drive_letter: char,
+    pub output_path: PathBuf,
+    pub logical_size: Information,
+    pub physical_read_count: usize,
+    pub physical_read_size: Information,
+}
+
+impl DryRunDriveSummary {
+    /// Parse the drive's boot sector and `$MFT` record and summarize the resulting read plans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drive cannot be opened or its `$MFT` record cannot be parsed.
+    pub fn plan(
+        drive_letter: char,
+        output_path: PathBuf,
+        tuning: &PhysicalReadTuning,
+    ) -> eyre::Result<Self> {
+        let logical_read_plan = plan_physical_stream(
+            drive_letter,
+            MftRecordNumber::DOLLAR_MFT,
+            None,
+            tuning.max_entries,
+        )?;
+        Ok(Self::from_logical_read_plan(
+            drive_letter,
+            output_path,
+            &logical_read_plan,
+            tuning,
+        ))
+    }
+
+    #[must_use]
+    pub fn from_logical_read_plan(
+        drive_letter: char,
+        output_path: PathBuf,
+        logical_read_plan: &LogicalReadPlan,
+        tuning: &PhysicalReadTuning,
+    ) -> Self {
+        let physical_read_plan = build_physical_read_plan(logical_read_plan, tuning.chunk_size);
+        Self {
+            drive_letter: drive_letter.to_ascii_uppercase(),
+            output_path,
+            logical_size: logical_read_plan.total_logical_size(),
+            physical_read_count: physical_read_plan.len(),
+            physical_read_size: physical_read_plan.total_size(),
+        }
+    }
+}
+
+impl Display for DryRunDriveSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} logical MFT in {} physical reads ({}) -> {}",
+            self.drive_letter,
+            self.logical_size.format_human(BINARY),
+            self.physical_read_count.separate_with_commas(),
+            self.physical_read_size.format_human(BINARY),
+            self.output_path.display()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DryRunDriveSummary;
+    use crate::read::logical_read_plan::LogicalFileSegment;
+    use crate::read::logical_read_plan::LogicalFileSegmentKind;
+    use crate::read::logical_read_plan::LogicalReadPlan;
+    use crate::read::physical_read_tuning::PhysicalReadTuning;
+    use uom::si::information::byte;
+    use uom::si::information::mebibyte;
+    use uom::si::usize::Information;
+
+    #[test]
+    fn summary_reports_plan_without_creating_output() {
+        let plan = LogicalReadPlan {
+            segments: [
+                LogicalFileSegment {
+                    logical_offset: Information::new::<byte>(0),
+                    length: Information::new::<mebibyte>(2),
+                    kind: LogicalFileSegmentKind::Physical {
+                        physical_offset: Information::new::<mebibyte>(16),
+                    },
+                },
+                LogicalFileSegment {
+                    logical_offset: Information::new::<mebibyte>(2),
+                    length: Information::new::<mebibyte>(1),
+                    kind: LogicalFileSegmentKind::Sparse,
+                },
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let output_path = dir.path().join("C.mft");
+
+        let summary = DryRunDriveSummary::from_logical_read_plan(
+            'c',
+            output_path.clone(),
+            &plan,
+            &PhysicalReadTuning::default(),
+        );
+
+        assert_eq!(summary.drive_letter, 'C');
+        assert_eq!(summary.logical_size, Information::new::<mebibyte>(3));
+        assert_eq!(summary.physical_read_count, 2);
+        assert_eq!(summary.physical_read_size, Information::new::<mebibyte>(2));
+        assert!(summary.to_string().contains("C.mft"));
+        assert!(!output_path.exists());
+    }
+}
@@ -1,28 +1,66 @@
 use crate::cancellation::CancellationToken;
+use crate::mft::mft_physical_read::PhysicalMftReadResult;
 use crate::sync::DriveSyncInfo;
-use crate::sync::IfExistsOutputBehaviour;
 use crate::sync::SyncIndex;
 use crate::sync::SyncMft;
-use futures::TryStreamExt;
+use crate::sync::SyncPlan;
+use crate::sync::SyncSummary;
+use crate::sync::sync_manifest::record_synced_drives;
+use futures::StreamExt as _;
 use std::collections::BTreeSet;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use tracing::Instrument;
 use tracing::Span;
 use tracing::info_span;
 
+/// Every drive whose snapshot was written is recorded in the sync dir's `manifest.json`.
+///
+/// Returns the per-drive summary, which may list failed drives; callers publish the drives
+/// that succeeded before calling [`SyncSummary::finish`] with `plan.keep_going`.
+///
 /// # Errors
 ///
-/// Returns an error if the sync fails, likely caused by IO problems.
+/// Returns an error if the sync fails, likely caused by IO problems, or if the manifest
+/// cannot be updated.
 pub async fn execute_sync(
     drive_infos: Vec<DriveSyncInfo>,
-    if_exists: &IfExistsOutputBehaviour,
+    plan: &SyncPlan,
     cancel: &CancellationToken,
-) -> eyre::Result<()> {
+) -> eyre::Result<SyncSummary> {
+    let if_exists = &plan.if_exists;
+    let tuning = plan.read_tuning()?;
+    eyre::ensure!(
+        !(plan.verify_after && plan.resume),
+        "`--verify-after` compares against the hash of an in-memory read, so it cannot be combined with `--resume`"
+    );
     // The two stages have different skip/overwrite/abort filtering rules, so
     // they must each run their own preflight over the same initial drive set.
     let mft_drive_infos = SyncMft::invoke_preflight(drive_infos.clone(), if_exists)?;
     let index_drive_infos = SyncIndex::invoke_preflight(drive_infos, if_exists)?;
 
+    // Resumable reads stream straight into `*.mft.partial` files rather than memory,
+    // so every index is built from the cached `.mft` once the reads finish.
+    if plan.resume {
+        let summary = SyncMft::invoke_resumable(
+            mft_drive_infos.clone(),
+            &tuning,
+            cancel,
+            plan.no_elevate,
+            plan.resume,
+            None,
+        )?;
+        publish_resumable_reads(
+            &mft_drive_infos,
+            index_drive_infos,
+            &summary,
+            cancel,
+            |synced| record_synced_drives(synced.iter().copied()),
+        )?;
+        return Ok(summary);
+    }
+
     // Drives present in both sets can build the index directly from the fresh
     // in-memory `PhysicalMftReadResult` produced by the MFT stage, avoiding a
     // write-then-read roundtrip through the cached `.mft` file.
@@ -50,9 +88,18 @@ pub async fn execute_sync(
     let mft_span = info_span!("dispatch mft sync work");
     let mft_data = {
         let _guard = mft_span.enter();
-        SyncMft::invoke(mft_drive_infos)?
+        SyncMft::invoke(
+            mft_drive_infos.clone(),
+            tuning,
+            plan.no_elevate,
+            plan.verify_after,
+        )?
     };
 
+    // Each drive's outcome is recorded rather than propagated so one failing drive
+    // does not cancel the reads still in flight for the others.
+    let summary = Arc::new(Mutex::new(SyncSummary::default()));
+    let summary_for_stream = Arc::clone(&summary);
     let in_memory_index_drive_letters_for_stream = Arc::clone(&in_memory_index_drive_letters);
     let in_memory_indexing = async move {
         // Consume completed MFT reads as they arrive and fan index construction out
@@ -62,48 +109,27 @@ pub async fn execute_sync(
             "Collecting MFT sync and in-memory index results"
         );
         mft_data
-            .try_for_each_concurrent(None, move |(drive_info, physical_mft)| {
+            .for_each_concurrent(None, move |(drive_letter, mft)| {
                 let in_memory_index_drive_letters =
                     Arc::clone(&in_memory_index_drive_letters_for_stream);
+                let summary = Arc::clone(&summary_for_stream);
                 async move {
-                    if !in_memory_index_drive_letters.contains(&drive_info.drive_letter) {
-                        return Ok(());
-                    }
-
-                    let parent_span = Span::current();
-                    tokio::task::spawn_blocking(move || -> eyre::Result<()> {
-                        let _parent_guard = parent_span.enter();
-                        let _guard = info_span!(
-                            "build_in_memory_search_index_for_drive",
-                            drive = %drive_info.drive_letter,
-                            index_path = %drive_info.index_output_path.display(),
-                        )
-                        .entered();
-                        let mft_file = physical_mft.to_mft_file()?;
-                        SyncIndex::invoke_for_mft_file(&drive_info, &mft_file)?;
+                    let result = match mft {
+                        Ok((drive_info, physical_mft))
+                            if in_memory_index_drive_letters.contains(&drive_info.drive_letter) =>
                         {
-                            let _guard = info_span!(
-                                "drop_in_memory_index_inputs",
-                                drive = %drive_info.drive_letter,
-                                physical_segments = physical_mft.physical_read_results.entries.len(),
-                                logical_segments = physical_mft.logical_read_plan.segments.len(),
-                                mft_entries = mft_file.record_count(),
-                            )
-                            .entered();
-                            drop(mft_file);
-                            drop(physical_mft);
-                        };
-                        Ok(())
-                    })
-                    .await
-                    .map_err(|error| {
-                        eyre::eyre!("Failed joining in-memory index task: {error}")
-                    })??;
-
-                    Ok(())
+                            build_in_memory_index(drive_info, physical_mft).await
+                        }
+                        Ok(_) => Ok(()),
+                        Err(error) => Err(error),
+                    };
+                    summary
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .record(drive_letter, &result);
                 }
             })
-            .await
+            .await;
     }
     .instrument(mft_span);
 
@@ -122,7 +148,85 @@ pub async fn execute_sync(
         SyncIndex::invoke(fallback_index_drive_infos, cancel)
     };
 
-    tokio::try_join!(in_memory_indexing, disk_indexing)?;
+    let ((), disk_indexing_result) = tokio::join!(in_memory_indexing, disk_indexing);
+    disk_indexing_result?;
+
+    let summary = Arc::try_unwrap(summary)
+        .map_err(|_| eyre::eyre!("Sync summary still shared after all drives finished"))?
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner);
+    let synced_drive_letters = summary.succeeded().collect::<BTreeSet<_>>();
+    record_synced_drives(
+        mft_drive_infos
+            .iter()
+            .filter(|info| synced_drive_letters.contains(&info.drive_letter)),
+    )?;
+    Ok(summary)
+}
+
+/// Record the manifest entries and build the search indexes of the drives a resumable read
+/// did not fail, from their cached `.mft` files.
+///
+/// `record_manifest` receives the drives of `mft_drive_infos` whose snapshots were written.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be updated or an index cannot be built.
+pub(crate) fn publish_resumable_reads(
+    mft_drive_infos: &[DriveSyncInfo],
+    index_drive_infos: Vec<DriveSyncInfo>,
+    summary: &SyncSummary,
+    cancel: &CancellationToken,
+    record_manifest: impl FnOnce(&[&DriveSyncInfo]) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let failed_drive_letters = summary
+        .failed()
+        .map(|(drive_letter, _)| drive_letter)
+        .collect::<BTreeSet<_>>();
+    record_manifest(
+        &mft_drive_infos
+            .iter()
+            .filter(|info| !failed_drive_letters.contains(&info.drive_letter))
+            .collect::<Vec<_>>(),
+    )?;
+    let index_drive_infos = index_drive_infos
+        .into_iter()
+        .filter(|info| !failed_drive_letters.contains(&info.drive_letter))
+        .collect::<Vec<_>>();
+    SyncIndex::invoke(index_drive_infos, cancel)
+}
+
+async fn build_in_memory_index(
+    drive_info: DriveSyncInfo,
+    physical_mft: PhysicalMftReadResult,
+) -> eyre::Result<()> {
+    let parent_span = Span::current();
+    tokio::task::spawn_blocking(move || -> eyre::Result<()> {
+        let _parent_guard = parent_span.enter();
+        let _guard = info_span!(
+            "build_in_memory_search_index_for_drive",
+            drive = %drive_info.drive_letter,
+            index_path = %drive_info.index_output_path.display(),
+        )
+        .entered();
+        let mft_file = physical_mft.to_mft_file()?;
+        SyncIndex::invoke_for_mft_file(&drive_info, &mft_file)?;
+        {
+            let _guard = info_span!(
+                "drop_in_memory_index_inputs",
+                drive = %drive_info.drive_letter,
+                physical_segments = physical_mft.physical_read_results.entries.len(),
+                logical_segments = physical_mft.logical_read_plan.segments.len(),
+                mft_entries = mft_file.record_count(),
+            )
+            .entered();
+            drop(mft_file);
+            drop(physical_mft);
+        };
+        Ok(())
+    })
+    .await
+    .map_err(|error| eyre::eyre!("Failed joining in-memory index task: {error}"))??;
 
     Ok(())
 }
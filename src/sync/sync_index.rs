@@ -9,6 +9,7 @@ use crate::sync::IfExistsOutputBehaviour;
 use eyre::Context;
 use eyre::bail;
 use itertools::Itertools;
+use std::path::PathBuf;
 use tracing::debug;
 use tracing::info;
 use tracing::info_span;
@@ -157,6 +158,20 @@ impl SyncIndex {
                 )
             })?;
 
+        // Orphans are indexed under `X:\$Orphan\` and hidden unless a query includes them.
+        let root_prefix = PathBuf::from(format!("{drive_name}:\\"));
+        let orphan_rows = files
+            .orphan_entries()
+            .iter()
+            .map(|orphan| SearchIndexPathRow {
+                path: orphan
+                    .path(&root_prefix)
+                    .to_string_lossy()
+                    .into_owned()
+                    .into(),
+                has_deleted_entries: orphan.is_deleted,
+            })
+            .collect::<Vec<_>>();
         Ok(files
             .0
             .into_iter()
@@ -165,6 +180,7 @@ impl SyncIndex {
                 path: path.path.to_string_lossy().into_owned().into(),
                 has_deleted_entries: path.has_deleted_entries(),
             })
+            .chain(orphan_rows)
             .collect())
     }
 
@@ -0,0 +1,225 @@
+use crate::machine::config::current_unix_ms;
+use crate::machine::config::is_compressed_mft_path;
+use crate::ntfs::ntfs_boot_sector::NtfsBootSector;
+use crate::ntfs::ntfs_drive_handle::NtfsDriveHandle;
+use crate::ntfs::ntfs_drive_handle::VolumeInfo;
+use crate::ntfs::ntfs_drive_handle::enumerate_ntfs_volumes;
+use crate::sync::DriveSyncInfo;
+use crate::windows_utils::handle::get_read_only_drive_handle;
+use eyre::Context;
+use facet::Facet;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fs::File;
+use std::path::Path;
+use tracing::debug;
+
+/// File name of the manifest written next to the synced `.mft` snapshots.
+pub const SYNC_MANIFEST_FILE_NAME: &str = "manifest.json";
+const SYNC_MANIFEST_TEMP_FILE_EXTENSION: &str = "json.tmp";
+
+/// Provenance record of the snapshots captured by `sync`, one entry per drive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Facet)]
+pub struct SyncManifest {
+    pub drives: Vec<SyncManifestDrive>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct SyncManifestDrive {
+    pub drive_letter: char,
+    /// Physical disk holding the volume's first extent, when the volume reports extents.
+    pub disk_number: Option<u32>,
+    /// Byte offset of the volume's first extent on that disk.
+    pub starting_offset: Option<u64>,
+    pub bytes_per_cluster: u64,
+    /// Size of the logical `$MFT` stream, before any compression of the output file.
+    pub mft_logical_size: u64,
+    pub output_file: String,
+    /// Hex SHA-256 of the output file exactly as written.
+    pub sha256: String,
+    pub captured_at_unix_ms: u64,
+}
+
+impl SyncManifestDrive {
+    /// Describe the snapshot at `output_path`, hashing it and measuring the logical MFT size
+    /// (decompressing `.mft.zst` outputs to do so).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output file cannot be read or decompressed.
+    pub fn describe_output(
+        drive_letter: char,
+        volume: Option<&VolumeInfo>,
+        bytes_per_cluster: u64,
+        output_path: &Path,
+        captured_at_unix_ms: u64,
+    ) -> eyre::Result<Self> {
+        let open = || {
+            File::open(output_path)
+                .wrap_err_with(|| format!("Failed to open {}", output_path.display()))
+        };
+        let mut hasher = Sha256::new();
+        let file_len = std::io::copy(&mut open()?, &mut hasher)
+            .wrap_err_with(|| format!("Failed hashing {}", output_path.display()))?;
+        let mft_logical_size = if is_compressed_mft_path(output_path) {
+            std::io::copy(
+                &mut zstd::stream::Decoder::new(open()?)?,
+                &mut std::io::sink(),
+            )
+            .wrap_err_with(|| format!("Failed decompressing {}", output_path.display()))?
+        } else {
+            file_len
+        };
+        Ok(Self {
+            drive_letter,
+            disk_number: volume.and_then(|volume| volume.disk_number),
+            starting_offset: volume.and_then(|volume| volume.starting_offset),
+            bytes_per_cluster,
+            mft_logical_size,
+            output_file: output_path.display().to_string(),
+            sha256: format!("{:x}", hasher.finalize()),
+            captured_at_unix_ms,
+        })
+    }
+}
+
+impl SyncManifest {
+    /// Load the manifest at `path`, or an empty one if it does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        facet_json::from_str::<Self>(&std::fs::read_to_string(path)?)
+            .map_err(|error| eyre::eyre!("Failed parsing {}: {error}", path.display()))
+    }
+
+    /// Replace the entry for `drive.drive_letter`, keeping entries ordered by drive letter.
+    pub fn upsert(&mut self, drive: SyncManifestDrive) {
+        self.drives
+            .retain(|existing| existing.drive_letter != drive.drive_letter);
+        self.drives.push(drive);
+        self.drives.sort_by_key(|drive| drive.drive_letter);
+    }
+
+    /// Write the manifest via a temporary file so readers never observe a partial manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temporary file cannot be written or renamed over `path`.
+    pub fn save(&self, path: &Path) -> eyre::Result<()> {
+        let temp_path = path.with_extension(SYNC_MANIFEST_TEMP_FILE_EXTENSION);
+        std::fs::write(&temp_path, facet_json::to_vec_pretty(self)?).wrap_err_with(|| {
+            format!("Failed writing temporary manifest {}", temp_path.display())
+        })?;
+        std::fs::rename(&temp_path, path)
+            .wrap_err_with(|| format!("Failed atomically replacing manifest {}", path.display()))
+    }
+}
+
+/// Add or refresh the manifest entry of every drive in `drive_infos`, whose snapshots were
+/// just written.
+pub(crate) fn record_synced_drives<'a>(
+    drive_infos: impl IntoIterator<Item = &'a DriveSyncInfo>,
+) -> eyre::Result<()> {
+    let mut drive_infos = drive_infos.into_iter().peekable();
+    if drive_infos.peek().is_none() {
+        return Ok(());
+    }
+    let volumes = enumerate_ntfs_volumes()?;
+    for info in drive_infos {
+        let drive_handle: NtfsDriveHandle = get_read_only_drive_handle(info.drive_letter)?
+            .try_into()
+            .wrap_err("Failed to convert drive handle to NtfsDriveHandle")?;
+        let boot_sector = NtfsBootSector::try_from_handle(&drive_handle)?;
+        let volume = volumes
+            .iter()
+            .find(|volume| volume.drive_letters.contains(&info.drive_letter));
+        let drive = SyncManifestDrive::describe_output(
+            info.drive_letter,
+            volume,
+            boot_sector.bytes_per_cluster() as u64,
+            &info.mft_output_path,
+            current_unix_ms(),
+        )?;
+
+        let manifest_path = info.mft_output_path.with_file_name(SYNC_MANIFEST_FILE_NAME);
+        let mut manifest = SyncManifest::load(&manifest_path)?;
+        manifest.upsert(drive);
+        manifest.save(&manifest_path)?;
+        debug!(
+            drive = %info.drive_letter,
+            manifest = %manifest_path.display(),
+            "Recorded drive in sync manifest"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncManifest;
+    use super::SyncManifestDrive;
+    use crate::ntfs::ntfs_drive_handle::VolumeInfo;
+    use sha2::Digest;
+    use sha2::Sha256;
+
+    #[test]
+    fn manifest_records_synced_drive_fields() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let raw = vec![0xA5u8; 4 * 1024];
+        let plain_path = dir.path().join("C.mft");
+        let compressed_path = dir.path().join("D.mft.zst");
+        std::fs::write(&plain_path, &raw)?;
+        std::fs::write(
+            &compressed_path,
+            zstd::stream::encode_all(raw.as_slice(), 0)?,
+        )?;
+        let volume = VolumeInfo {
+            volume_guid_path: String::from(r"\\?\Volume{00000000-0000-0000-0000-000000000000}\"),
+            drive_letters: vec!['C'],
+            mount_paths: vec![String::from(r"C:\")],
+            disk_number: Some(0),
+            starting_offset: Some(1_048_576),
+        };
+
+        let manifest_path = dir.path().join(super::SYNC_MANIFEST_FILE_NAME);
+        let mut manifest = SyncManifest::load(&manifest_path)?;
+        manifest.upsert(SyncManifestDrive::describe_output(
+            'D',
+            None,
+            4096,
+            &compressed_path,
+            2,
+        )?);
+        manifest.upsert(SyncManifestDrive::describe_output(
+            'C',
+            Some(&volume),
+            4096,
+            &plain_path,
+            1,
+        )?);
+        manifest.save(&manifest_path)?;
+
+        let loaded = SyncManifest::load(&manifest_path)?;
+        assert_eq!(loaded, manifest);
+        let [c, d] = loaded.drives.as_slice() else {
+            panic!("expected two manifest entries, got {:?}", loaded.drives);
+        };
+        assert_eq!(c.drive_letter, 'C');
+        assert_eq!(c.disk_number, Some(0));
+        assert_eq!(c.starting_offset, Some(1_048_576));
+        assert_eq!(c.bytes_per_cluster, 4096);
+        assert_eq!(c.mft_logical_size, 4 * 1024);
+        assert_eq!(c.output_file, plain_path.display().to_string());
+        assert_eq!(c.sha256, format!("{:x}", Sha256::digest(&raw)));
+        assert_eq!(c.captured_at_unix_ms, 1);
+        assert_eq!(d.disk_number, None);
+        assert_eq!(d.mft_logical_size, 4 * 1024);
+        assert_ne!(d.sha256, c.sha256);
+        Ok(())
+    }
+}
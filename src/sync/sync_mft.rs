@@ -1,15 +1,31 @@
+use crate::cancellation::CancellationToken;
+use crate::machine::config::is_compressed_mft_path;
 use crate::mft::mft_physical_read::PhysicalMftReadResult;
-use crate::mft::mft_physical_read::read_physical_mft;
+use crate::mft::mft_physical_read::read_physical_mft_with_tuning;
+use crate::mft::mft_resumable_read::read_physical_mft_resumable;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
 use crate::sync::DriveSyncInfo;
 use crate::sync::IfExistsOutputBehaviour;
+use crate::sync::SyncProgress;
+use crate::sync::SyncSummary;
+use crate::sync::SyncTimings;
+use crate::sync::sync_progress::DriveProgressReporter;
 use crate::windows_utils::elevation::enable_backup_privileges;
-use crate::windows_utils::elevation::ensure_elevated;
-use async_stream::try_stream;
+use crate::windows_utils::elevation::ensure_elevated_for_raw_reads;
+use crate::windows_utils::elevation::is_elevated;
+use crate::windows_utils::elevation::raw_volume_access_error;
+use async_stream::stream;
+use crossbeam_channel::Sender;
 use eyre::Context;
 use eyre::bail;
+use eyre::ensure;
 use futures::StreamExt as _;
 use futures::stream;
 use itertools::Itertools;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
 use tokio_stream::Stream;
 use tracing::debug;
 use tracing::info;
@@ -53,20 +69,115 @@ impl SyncMft {
         Ok(rtn)
     }
 
+    /// Sync MFT data from drives in resumable batches, writing each drive's `.mft` via a
+    /// `.mft.partial` file that a later run can continue from.
+    ///
+    /// A failing drive does not stop the others; its error is recorded in the returned summary.
+    /// An earlier `.mft.partial` is only continued when `resume` is set.
+    /// Each persisted batch is reported to `progress` when given.
+    /// Does not call the preflight check.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if elevation fails or backup privileges cannot be enabled.
+    pub fn invoke_resumable(
+        drive_infos: Vec<DriveSyncInfo>,
+        tuning: &PhysicalReadTuning,
+        cancel: &CancellationToken,
+        no_elevate: bool,
+        resume: bool,
+        progress: Option<&Sender<SyncProgress>>,
+    ) -> eyre::Result<SyncSummary> {
+        let explain_access_errors = Self::prepare_raw_reads(no_elevate)?;
+        Ok(Self::read_resumable_drives(
+            &drive_infos,
+            explain_access_errors,
+            progress,
+            &mut |drive_info, on_batch| {
+                read_physical_mft_resumable(
+                    drive_info.drive_letter,
+                    &drive_info.mft_output_path,
+                    tuning,
+                    resume,
+                    cancel,
+                    on_batch,
+                )
+            },
+        ))
+    }
+
+    /// Elevate and enable backup privileges before raw volume reads.
+    ///
+    /// Returns whether access errors should be explained, which is the case when running
+    /// unelevated under `no_elevate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if elevation fails or backup privileges cannot be enabled.
+    pub(crate) fn prepare_raw_reads(no_elevate: bool) -> eyre::Result<bool> {
+        ensure_elevated_for_raw_reads(no_elevate)?;
+        enable_backup_privileges()
+            .wrap_err("Failed to enable backup privileges")
+            .map_err(raw_volume_access_error)?;
+        Ok(no_elevate && !is_elevated())
+    }
+
+    /// Run `read_drive` for each drive in turn, reporting its batches to `progress` and
+    /// recording its outcome in the returned summary.
+    pub(crate) fn read_resumable_drives(
+        drive_infos: &[DriveSyncInfo],
+        explain_access_errors: bool,
+        progress: Option<&Sender<SyncProgress>>,
+        read_drive: &mut dyn FnMut(&DriveSyncInfo, &mut dyn FnMut(u64, u64)) -> eyre::Result<()>,
+    ) -> SyncSummary {
+        let mut summary = SyncSummary::default();
+        for drive_info in drive_infos {
+            let _span = info_span!(
+                "read_resumable_mft_for_drive",
+                drive = %drive_info.drive_letter,
+                output_path = %drive_info.mft_output_path.display(),
+            )
+            .entered();
+            let mut reporter = DriveProgressReporter::new(drive_info.drive_letter, progress);
+            let result = read_drive(drive_info, &mut |bytes_done, bytes_total| {
+                reporter.reading(bytes_done, bytes_total);
+            })
+            .wrap_err_with(|| {
+                format!(
+                    "Failed reading MFT data for drive {}",
+                    drive_info.drive_letter
+                )
+            });
+            let result = if explain_access_errors {
+                result.map_err(raw_volume_access_error)
+            } else {
+                result
+            };
+            reporter.finish(&result);
+            summary.record(drive_info.drive_letter, &result);
+        }
+        summary
+    }
+
     /// Sync MFT data from drives.
     ///
+    /// Each stream item is one drive's outcome; a drive whose read or write fails yields its
+    /// error without ending the stream for the remaining drives. With `verify_after`, a drive
+    /// whose written snapshot does not match its captured read also yields an error.
     /// Does not call the preflight check.
     ///
     /// # Errors
     ///
-    /// Returns an error if the sync directory cannot be retrieved, elevation fails,
-    /// or if reading/writing MFT data fails.
+    /// Returns an error if elevation fails or backup privileges cannot be enabled.
     pub fn invoke(
         drive_infos: Vec<DriveSyncInfo>,
-    ) -> eyre::Result<impl Stream<Item = eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)>>>
-    {
-        ensure_elevated()?;
-        enable_backup_privileges().wrap_err("Failed to enable backup privileges")?;
+        tuning: PhysicalReadTuning,
+        no_elevate: bool,
+        verify_after: bool,
+    ) -> eyre::Result<
+        impl Stream<Item = (char, eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)>)>,
+    > {
+        let explain_access_errors = Self::prepare_raw_reads(no_elevate)?;
 
         info!(
             "Found {} drives to sync MFT files for: {}",
@@ -74,40 +185,114 @@ impl SyncMft {
             drive_infos.iter().map(|info| info.drive_letter).join(", ")
         );
 
-        Ok(try_stream! {
+        Ok(stream! {
             tracing::debug!("Syncing MFTs from disks to files");
-            let physical_mft_stream = read_physical_mft_stream_with_info(drive_infos);
+            let physical_mft_stream = read_physical_mft_stream_with_info(drive_infos, tuning);
             tokio::pin!(physical_mft_stream);
-            while let Some(mft) = physical_mft_stream.next().await {
-                let (drive_info, mft_result) = mft?;
-                tracing::debug!(
-                    drive = %drive_info.drive_letter,
-                    output_path = %drive_info.mft_output_path.display(),
-                    "Writing MFT snapshot for drive"
-                );
-                mft_result.write_to_path(&drive_info.mft_output_path).wrap_err_with(|| {
-                    format!(
-                        "Failed writing MFT snapshot for drive {} to {}",
-                        drive_info.drive_letter,
-                        drive_info.mft_output_path.display()
-                    )
-                })?;
-                yield (drive_info, mft_result);
+            while let Some((drive_letter, mft)) = physical_mft_stream.next().await {
+                let mft = if explain_access_errors {
+                    mft.map_err(raw_volume_access_error)
+                } else {
+                    mft
+                };
+                yield (drive_letter, mft.and_then(|(drive_info, mft_result)| {
+                    Self::write_snapshot(drive_info, mft_result, verify_after)
+                }));
             }
         })
     }
+
+    fn write_snapshot(
+        drive_info: DriveSyncInfo,
+        mut mft_result: PhysicalMftReadResult,
+        verify_after: bool,
+    ) -> eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)> {
+        tracing::debug!(
+            drive = %drive_info.drive_letter,
+            output_path = %drive_info.mft_output_path.display(),
+            "Writing MFT snapshot for drive"
+        );
+        let mut file_write = mft_result.timings.file_write;
+        SyncTimings::measure(&mut file_write, || {
+            mft_result.write_to_path(&drive_info.mft_output_path)
+        })
+        .wrap_err_with(|| {
+            format!(
+                "Failed writing MFT snapshot for drive {} to {}",
+                drive_info.drive_letter,
+                drive_info.mft_output_path.display()
+            )
+        })?;
+        mft_result.timings.file_write = file_write;
+        mft_result.timings.log_for_drive(drive_info.drive_letter);
+        if verify_after {
+            let expected_crc32 = mft_result
+                .physical_read_results
+                .crc32(&mft_result.logical_read_plan)?;
+            verify_snapshot(&drive_info.mft_output_path, expected_crc32).wrap_err_with(|| {
+                format!(
+                    "Failed verifying MFT snapshot for drive {}",
+                    drive_info.drive_letter
+                )
+            })?;
+            info!(
+                drive = %drive_info.drive_letter,
+                crc32 = format_args!("{expected_crc32:08x}"),
+                "Verified MFT snapshot for drive {}",
+                drive_info.drive_letter
+            );
+        }
+        Ok((drive_info, mft_result))
+    }
+}
+
+/// Re-hash the snapshot at `output_path` and compare it with `expected_crc32`, the CRC32 of
+/// the captured read. `.mft.zst` snapshots are hashed after decompression.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot cannot be read or its CRC32 differs from `expected_crc32`.
+pub fn verify_snapshot(output_path: &Path, expected_crc32: u32) -> eyre::Result<()> {
+    let file = File::open(output_path)
+        .wrap_err_with(|| format!("Failed to open {}", output_path.display()))?;
+    let mut reader: Box<dyn Read> = if is_compressed_mft_path(output_path) {
+        Box::new(zstd::stream::Decoder::new(file)?)
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = vec![0_u8; 1024 * 1024];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .wrap_err_with(|| format!("Failed reading {}", output_path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let actual_crc32 = hasher.finalize();
+    ensure!(
+        actual_crc32 == expected_crc32,
+        "{} has CRC32 {actual_crc32:08x} but the captured read hashed to {expected_crc32:08x}",
+        output_path.display()
+    );
+    Ok(())
 }
 
+/// Read each drive's MFT concurrently, yielding every drive's letter with its outcome.
 pub fn read_physical_mft_stream_with_info(
     drive_infos: impl IntoIterator<Item = DriveSyncInfo>,
-) -> impl Stream<Item = eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)>> {
+    tuning: PhysicalReadTuning,
+) -> impl Stream<Item = (char, eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)>)> {
     let drive_infos = drive_infos.into_iter().collect::<Vec<_>>();
     let concurrency = drive_infos.len().max(1);
 
     stream::iter(drive_infos)
         .map(|drive_info| async move {
+            let drive_letter = drive_info.drive_letter;
             let parent_span = tracing::Span::current();
-            tokio::task::spawn_blocking(
+            let result = tokio::task::spawn_blocking(
                 move || -> eyre::Result<(DriveSyncInfo, PhysicalMftReadResult)> {
                     let _parent_guard = parent_span.enter();
                     let _span = info_span!(
@@ -115,18 +300,51 @@ pub fn read_physical_mft_stream_with_info(
                         drive = %drive_info.drive_letter,
                     )
                     .entered();
-                    let physical_mft_read_result = read_physical_mft(drive_info.drive_letter)
-                        .wrap_err_with(|| {
-                            format!(
-                                "Failed reading MFT data for drive {}",
-                                drive_info.drive_letter
-                            )
-                        })?;
+                    let physical_mft_read_result =
+                        read_physical_mft_with_tuning(drive_info.drive_letter, &tuning)
+                            .wrap_err_with(|| {
+                                format!(
+                                    "Failed reading MFT data for drive {}",
+                                    drive_info.drive_letter
+                                )
+                            })?;
                     eyre::Ok((drive_info, physical_mft_read_result))
                 },
             )
             .await
-            .map_err(|error| eyre::eyre!("Failed joining MFT read task: {error}"))?
+            .map_err(|error| eyre::eyre!("Failed joining MFT read task: {error}"))
+            .and_then(|result| result);
+            (drive_letter, result)
         })
         .buffer_unordered(concurrency)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::verify_snapshot;
+
+    #[test]
+    fn verify_snapshot_detects_a_mutated_file() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let contents = (0..=250_u8).cycle().take(8192).collect::<Vec<_>>();
+        let expected_crc32 = crc32fast::hash(&contents);
+        let plain = dir.path().join("C.mft");
+        std::fs::write(&plain, &contents)?;
+        let compressed = dir.path().join("D.mft.zst");
+        std::fs::write(&compressed, zstd::encode_all(contents.as_slice(), 0)?)?;
+
+        verify_snapshot(&plain, expected_crc32)?;
+        verify_snapshot(&compressed, expected_crc32)?;
+
+        let mut mutated = contents;
+        mutated[4096] ^= 0xFF;
+        std::fs::write(&plain, &mutated)?;
+        let error = verify_snapshot(&plain, expected_crc32).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("but the captured read hashed to")
+        );
+        Ok(())
+    }
+}
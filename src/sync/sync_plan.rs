@@ -1,8 +1,16 @@
+use crate::machine::config::COMPRESSED_MFT_CACHE_FILE_EXTENSION;
+use crate::machine::config::MFT_CACHE_FILE_EXTENSION;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
 use crate::sync::IfExistsOutputBehaviour;
 use crate::windows_utils::storage::DriveLetterPattern;
 use arbitrary::Arbitrary;
+use eyre::ensure;
+use eyre::eyre;
 use facet::Facet;
 use figue::{self as args};
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
 
 #[derive(Facet, PartialEq, Debug, Arbitrary, Default, Clone)]
 pub struct SyncPlan {
@@ -14,6 +22,42 @@ pub struct SyncPlan {
     #[facet(args::named, default)]
     pub if_exists: IfExistsOutputBehaviour,
 
+    /// Write a drive's MFT to a custom path instead of the sync dir (e.g. `C=D:\captures\c.mft`). Repeat `--map` for several drives.
+    #[facet(args::named, default)]
+    pub map: Vec<String>,
+
+    /// Write zstd-compressed `<letter>.mft.zst` snapshots instead of plain `<letter>.mft` files
+    #[facet(args::named, default)]
+    pub compress: bool,
+
+    /// Maximum bytes per physical read request (default 1 MiB); should be a multiple of 512
+    #[facet(args::named)]
+    pub chunk_size: Option<usize>,
+
+    /// Maximum overlapped physical reads in flight per drive (default 32, or `TEAMY_MFT_MAX_IN_FLIGHT_IO`)
+    #[facet(args::named)]
+    pub queue_depth: Option<usize>,
+
+    /// Read only the first N MFT records of each drive; the snapshot is truncated accordingly (for smoke tests)
+    #[facet(args::named)]
+    pub max_entries: Option<usize>,
+
+    /// After writing each snapshot, re-hash it and fail the drive if it differs from the CRC32 of the captured read
+    #[facet(args::named, default)]
+    pub verify_after: bool,
+
+    /// Read MFTs in resumable batches, continuing from a `*.mft.partial` left by an interrupted run
+    #[facet(args::named, default)]
+    pub resume: bool,
+
+    /// Exit successfully even if some drives fail; failures are still listed in the final summary
+    #[facet(args::named, default)]
+    pub keep_going: bool,
+
+    /// Skip relaunching as administrator and attempt raw volume reads with the current privileges
+    #[facet(args::named, default)]
+    pub no_elevate: bool,
+
     /// When syncing a path, recurse through a directory subtree and refresh overlay rows for all descendants.
     #[facet(args::named, default)]
     pub recursive: bool,
@@ -22,3 +66,119 @@ pub struct SyncPlan {
     #[facet(args::positional, default)]
     pub path: Option<String>,
 }
+
+impl SyncPlan {
+    /// Physical read tuning from `--chunk-size`, `--queue-depth` and `--max-entries`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them is zero.
+    pub fn read_tuning(&self) -> eyre::Result<PhysicalReadTuning> {
+        ensure!(
+            self.max_entries != Some(0),
+            "--max-entries must be greater than zero"
+        );
+        Ok(PhysicalReadTuning {
+            max_entries: self.max_entries,
+            ..PhysicalReadTuning::new(self.chunk_size, self.queue_depth)?
+        })
+    }
+
+    /// The sync-dir MFT snapshot path for a drive without a `--map` override,
+    /// honouring `--compress`.
+    #[must_use]
+    pub fn default_mft_output_path(&self, sync_dir: &Path, drive_letter: char) -> PathBuf {
+        sync_dir.join(format!(
+            "{drive_letter}{}",
+            if self.compress {
+                COMPRESSED_MFT_CACHE_FILE_EXTENSION
+            } else {
+                MFT_CACHE_FILE_EXTENSION
+            }
+        ))
+    }
+
+    /// Parse the `--map` entries into per-drive MFT output paths.
+    ///
+    /// Drives without a mapping keep the default `<letter>.mft` path in the sync dir.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is not `<letter>=<path>`, a drive is mapped twice,
+    /// or a mapped drive is not among `drive_letters`.
+    pub fn mft_output_overrides(
+        &self,
+        drive_letters: &[char],
+    ) -> eyre::Result<HashMap<char, PathBuf>> {
+        let mut rtn = HashMap::with_capacity(self.map.len());
+        for entry in &self.map {
+            let (letter, path) = entry
+                .split_once('=')
+                .ok_or_else(|| eyre!("Invalid --map entry '{entry}', expected <letter>=<path>"))?;
+            let mut letters = letter.trim().trim_end_matches(':').chars();
+            let (Some(letter), None) = (letters.next(), letters.next()) else {
+                eyre::bail!("Invalid --map entry '{entry}', expected a single drive letter");
+            };
+            ensure!(
+                letter.is_ascii_alphabetic(),
+                "Invalid drive letter in --map entry '{entry}'"
+            );
+            let letter = letter.to_ascii_uppercase();
+            let path = path.trim();
+            ensure!(
+                !path.is_empty(),
+                "Missing output path in --map entry '{entry}'"
+            );
+            ensure!(
+                drive_letters.contains(&letter),
+                "--map entry '{entry}' targets drive {letter}, which is not in the drive pattern '{}'",
+                self.drive_letter_pattern
+            );
+            ensure!(
+                rtn.insert(letter, PathBuf::from(path)).is_none(),
+                "Drive {letter} is mapped more than once"
+            );
+        }
+        Ok(rtn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncPlan;
+    use std::path::PathBuf;
+
+    #[test]
+    fn mapped_drives_override_the_default_output_path() -> eyre::Result<()> {
+        let plan = SyncPlan {
+            map: vec![
+                r"C=D:\captures\c.mft".to_owned(),
+                r"e:=D:\captures\e.mft".to_owned(),
+            ],
+            ..SyncPlan::default()
+        };
+
+        let overrides = plan.mft_output_overrides(&['C', 'D', 'E'])?;
+        assert_eq!(
+            overrides.get(&'C'),
+            Some(&PathBuf::from(r"D:\captures\c.mft"))
+        );
+        assert_eq!(
+            overrides.get(&'E'),
+            Some(&PathBuf::from(r"D:\captures\e.mft"))
+        );
+        assert_eq!(overrides.get(&'D'), None);
+        Ok(())
+    }
+
+    #[test]
+    fn mapping_outside_the_drive_pattern_is_rejected() {
+        let plan = SyncPlan {
+            map: vec![r"F=D:\captures\f.mft".to_owned()],
+            ..SyncPlan::default()
+        };
+
+        let error = plan.mft_output_overrides(&['C']).unwrap_err();
+        assert!(error.to_string().contains("not in the drive pattern"));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::cancellation::CancellationToken;
+use crate::mft::mft_resumable_read::read_physical_mft_resumable;
+use crate::read::physical_read_tuning::PhysicalReadTuning;
+use crate::sync::DriveSyncInfo;
+use crate::sync::SyncMft;
+use crate::sync::SyncSummary;
+use crate::sync::sync_executor::publish_resumable_reads;
+use crate::sync::sync_manifest::record_synced_drives;
+use crossbeam_channel::Sender;
+
+/// Stage of a drive's sync reported through [`SyncProgress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// MFT bytes are being read into the drive's `.mft.partial` file.
+    Reading,
+    /// The drive's `.mft` has been written.
+    Finished,
+    /// The drive failed; its error is listed in the returned [`SyncSummary`].
+    Failed,
+}
+
+/// Progress message sent to embedders of [`sync_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncProgress {
+    pub drive: char,
+    pub phase: SyncPhase,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Sends one drive's progress, remembering the latest byte counts for its final message.
+#[derive(Debug)]
+pub(crate) struct DriveProgressReporter<'a> {
+    drive: char,
+    sender: Option<&'a Sender<SyncProgress>>,
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+impl<'a> DriveProgressReporter<'a> {
+    pub(crate) fn new(drive: char, sender: Option<&'a Sender<SyncProgress>>) -> Self {
+        Self {
+            drive,
+            sender,
+            bytes_done: 0,
+            bytes_total: 0,
+        }
+    }
+
+    pub(crate) fn reading(&mut self, bytes_done: u64, bytes_total: u64) {
+        self.bytes_done = bytes_done;
+        self.bytes_total = bytes_total;
+        self.send(SyncPhase::Reading);
+    }
+
+    pub(crate) fn finish(&self, result: &eyre::Result<()>) {
+        self.send(if result.is_ok() {
+            SyncPhase::Finished
+        } else {
+            SyncPhase::Failed
+        });
+    }
+
+    fn send(&self, phase: SyncPhase) {
+        let Some(sender) = self.sender else {
+            return;
+        };
+        // A dropped receiver only means the embedder stopped listening; keep syncing.
+        let _ = sender.send(SyncProgress {
+            drive: self.drive,
+            phase,
+            bytes_done: self.bytes_done,
+            bytes_total: self.bytes_total,
+        });
+    }
+}
+
+/// Sync MFTs for `drive_infos` in resumable batches, sending each drive's progress to
+/// `progress` as batches are persisted.
+///
+/// An earlier `.mft.partial` is only continued when `resume` is set. Like the normal sync,
+/// every drive whose snapshot was written is recorded in the manifest and gets its search
+/// index rebuilt. Does not call the preflight check.
+///
+/// # Errors
+///
+/// Returns an error if elevation fails, backup privileges cannot be enabled, the manifest
+/// cannot be updated, or an index cannot be built.
+pub fn sync_with_progress(
+    drive_infos: Vec<DriveSyncInfo>,
+    tuning: &PhysicalReadTuning,
+    resume: bool,
+    cancel: &CancellationToken,
+    progress: &Sender<SyncProgress>,
+) -> eyre::Result<SyncSummary> {
+    let explain_access_errors = SyncMft::prepare_raw_reads(false)?;
+    sync_with_progress_using(
+        drive_infos,
+        explain_access_errors,
+        cancel,
+        progress,
+        &mut |drive_info, on_batch| {
+            read_physical_mft_resumable(
+                drive_info.drive_letter,
+                &drive_info.mft_output_path,
+                tuning,
+                resume,
+                cancel,
+                on_batch,
+            )
+        },
+        |synced| record_synced_drives(synced.iter().copied()),
+    )
+}
+
+fn sync_with_progress_using(
+    drive_infos: Vec<DriveSyncInfo>,
+    explain_access_errors: bool,
+    cancel: &CancellationToken,
+    progress: &Sender<SyncProgress>,
+    read_drive: &mut dyn FnMut(&DriveSyncInfo, &mut dyn FnMut(u64, u64)) -> eyre::Result<()>,
+    record_manifest: impl FnOnce(&[&DriveSyncInfo]) -> eyre::Result<()>,
+) -> eyre::Result<SyncSummary> {
+    let summary = SyncMft::read_resumable_drives(
+        &drive_infos,
+        explain_access_errors,
+        Some(progress),
+        read_drive,
+    );
+    publish_resumable_reads(
+        &drive_infos,
+        drive_infos.clone(),
+        &summary,
+        cancel,
+        record_manifest,
+    )?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DriveProgressReporter;
+    use super::SyncPhase;
+    use super::SyncProgress;
+    use super::sync_with_progress_using;
+    use crate::cancellation::CancellationToken;
+    use crate::mft::testing::SYNTHETIC_ROOT_RECORD;
+    use crate::mft::testing::SyntheticMftBuilder;
+    use crate::sync::resolve_drive_infos_in_dir_for_letters;
+
+    #[test]
+    fn progress_messages_arrive_in_order() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut reporter = DriveProgressReporter::new('C', Some(&sender));
+
+        // Stand-in for a resumable read of three 100-byte batches.
+        let read = |on_batch: &mut dyn FnMut(u64, u64)| -> eyre::Result<()> {
+            for bytes_done in [0, 100, 200, 300] {
+                on_batch(bytes_done, 300);
+            }
+            Ok(())
+        };
+        let result = read(&mut |done, total| reporter.reading(done, total));
+        reporter.finish(&result);
+        drop(sender);
+
+        let message = |phase, bytes_done| SyncProgress {
+            drive: 'C',
+            phase,
+            bytes_done,
+            bytes_total: 300,
+        };
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            vec![
+                message(SyncPhase::Reading, 0),
+                message(SyncPhase::Reading, 100),
+                message(SyncPhase::Reading, 200),
+                message(SyncPhase::Reading, 300),
+                message(SyncPhase::Finished, 300),
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_drives_are_reported_and_left_out_of_the_index_and_manifest() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let drive_infos = resolve_drive_infos_in_dir_for_letters(dir.path(), ['C', 'D'])?;
+        let mut builder = SyntheticMftBuilder::new();
+        builder.file(SYNTHETIC_ROOT_RECORD, "kept.txt");
+        let mft = builder.build();
+        let mft_len = mft.len() as u64;
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut manifest_drives = Vec::new();
+        let summary = sync_with_progress_using(
+            drive_infos.clone(),
+            false,
+            &CancellationToken::new(),
+            &sender,
+            &mut |drive_info, on_batch| {
+                eyre::ensure!(drive_info.drive_letter == 'C', "volume is offline");
+                on_batch(0, mft_len);
+                std::fs::write(&drive_info.mft_output_path, &mft)?;
+                on_batch(mft_len, mft_len);
+                Ok(())
+            },
+            |synced| {
+                manifest_drives.extend(synced.iter().map(|info| info.drive_letter));
+                Ok(())
+            },
+        )?;
+        drop(sender);
+
+        assert_eq!(summary.succeeded().collect::<Vec<_>>(), vec!['C']);
+        assert_eq!(
+            summary
+                .failed()
+                .map(|(drive_letter, _)| drive_letter)
+                .collect::<Vec<_>>(),
+            vec!['D']
+        );
+        let message = |drive, phase, bytes_done, bytes_total| SyncProgress {
+            drive,
+            phase,
+            bytes_done,
+            bytes_total,
+        };
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            vec![
+                message('C', SyncPhase::Reading, 0, mft_len),
+                message('C', SyncPhase::Reading, mft_len, mft_len),
+                message('C', SyncPhase::Finished, mft_len, mft_len),
+                message('D', SyncPhase::Failed, 0, 0),
+            ]
+        );
+        assert_eq!(manifest_drives, vec!['C']);
+        assert!(drive_infos[0].index_output_path.is_file());
+        assert!(!drive_infos[1].index_output_path.exists());
+        Ok(())
+    }
+}
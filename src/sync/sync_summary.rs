@@ -0,0 +1,110 @@
+use std::fmt;
+use tracing::error;
+use tracing::info;
+
+/// Per-drive outcome of a full-drive sync, reported once every drive has finished.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub drives: Vec<(char, Result<(), String>)>,
+}
+
+impl SyncSummary {
+    pub fn record(&mut self, drive_letter: char, result: &eyre::Result<()>) {
+        self.drives.push((
+            drive_letter,
+            result
+                .as_ref()
+                .copied()
+                .map_err(|error| format!("{error:#}")),
+        ));
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = (char, &str)> {
+        self.drives.iter().filter_map(|(drive_letter, result)| {
+            result
+                .as_ref()
+                .err()
+                .map(|error| (*drive_letter, error.as_str()))
+        })
+    }
+
+    pub fn succeeded(&self) -> impl Iterator<Item = char> {
+        self.drives
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(drive_letter, _)| *drive_letter)
+    }
+
+    /// Log one line per drive, failures at error level.
+    pub fn log(&self) {
+        for (drive_letter, result) in &self.drives {
+            match result {
+                Ok(()) => info!(drive = %drive_letter, "Drive {drive_letter} synced"),
+                Err(error) => error!(drive = %drive_letter, "Drive {drive_letter} failed: {error}"),
+            }
+        }
+    }
+
+    /// Log the summary and turn any failed drive into an error unless `keep_going` is set.
+    ///
+    /// Returns the summary sorted by drive letter so callers can act on the drives that
+    /// succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming every failed drive when at least one failed and `keep_going`
+    /// is `false`.
+    pub fn finish(mut self, keep_going: bool) -> eyre::Result<Self> {
+        self.drives.sort_by_key(|(drive_letter, _)| *drive_letter);
+        self.log();
+        if self.failed().next().is_none() || keep_going {
+            return Ok(self);
+        }
+        eyre::bail!("{self}");
+    }
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed = self.failed().collect::<Vec<_>>();
+        write!(
+            f,
+            "Sync failed for {} of {} drive(s)",
+            failed.len(),
+            self.drives.len()
+        )?;
+        for (drive_letter, error) in failed {
+            write!(f, "\n  {drive_letter}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncSummary;
+
+    #[test]
+    fn one_failing_drive_fails_the_run_unless_keep_going() {
+        let mut summary = SyncSummary::default();
+        summary.record(
+            'D',
+            &Err(eyre::eyre!("Failed reading MFT data for drive D")),
+        );
+        summary.record('C', &Ok(()));
+
+        assert_eq!(
+            summary.failed().collect::<Vec<_>>(),
+            vec![('D', "Failed reading MFT data for drive D")]
+        );
+        let error = summary
+            .clone()
+            .finish(false)
+            .expect_err("a failed drive should fail the run");
+        assert_eq!(
+            error.to_string(),
+            "Sync failed for 1 of 2 drive(s)\n  D: Failed reading MFT data for drive D"
+        );
+        assert!(summary.finish(true).is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+use std::time::Instant;
+use teamy_uom_extensions::HumanTimeExt;
+use tracing::info;
+use uom::si::f64::Time;
+use uom::si::time::second;
+
+/// Elapsed time spent in each phase of syncing one drive's MFT.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncTimings {
+    /// Opening the volume and reading the NTFS boot sector.
+    pub boot_sector_read: Time,
+    /// Reading the `$MFT` record and decoding its attributes into a logical read plan.
+    pub record_parse: Time,
+    /// Executing the physical read plan against the volume.
+    pub physical_read: Time,
+    /// Writing the reconstructed MFT snapshot to disk.
+    pub file_write: Time,
+}
+
+impl Default for SyncTimings {
+    fn default() -> Self {
+        let zero = Time::new::<second>(0.0);
+        Self {
+            boot_sector_read: zero,
+            record_parse: zero,
+            physical_read: zero,
+            file_write: zero,
+        }
+    }
+}
+
+impl SyncTimings {
+    /// Run `phase`, adding its elapsed time to `slot`.
+    pub fn measure<T>(slot: &mut Time, phase: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let rtn = phase();
+        *slot += Time::new::<second>(start.elapsed().as_secs_f64());
+        rtn
+    }
+
+    /// Sum of all recorded phases.
+    #[must_use]
+    pub fn total(&self) -> Time {
+        self.boot_sector_read + self.record_parse + self.physical_read + self.file_write
+    }
+
+    /// Log the phase breakdown for `drive_letter` at info level.
+    pub fn log_for_drive(&self, drive_letter: char) {
+        info!(
+            drive = %drive_letter,
+            "Drive {drive_letter} sync took {total}: boot sector {boot_sector}, record parse {record_parse}, physical read {physical_read}, file write {file_write}",
+            total = self.total().format_human(),
+            boot_sector = self.boot_sector_read.format_human(),
+            record_parse = self.record_parse.format_human(),
+            physical_read = self.physical_read.format_human(),
+            file_write = self.file_write.format_human(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncTimings;
+    use std::time::Duration;
+    use uom::si::time::second;
+
+    #[test]
+    fn measure_populates_every_phase() {
+        let mut timings = SyncTimings::default();
+        let pause = || std::thread::sleep(Duration::from_millis(2));
+        SyncTimings::measure(&mut timings.boot_sector_read, pause);
+        SyncTimings::measure(&mut timings.record_parse, pause);
+        let bytes = SyncTimings::measure(&mut timings.physical_read, || {
+            pause();
+            vec![0u8; 1024]
+        });
+        SyncTimings::measure(&mut timings.file_write, || drop(bytes));
+        SyncTimings::measure(&mut timings.file_write, pause);
+
+        for phase in [
+            timings.boot_sector_read,
+            timings.record_parse,
+            timings.physical_read,
+            timings.file_write,
+        ] {
+            assert!(phase.get::<second>() >= 0.002);
+        }
+        assert!(timings.total().get::<second>() >= 0.008);
+    }
+}
@@ -1,5 +1,7 @@
 use eyre::Context;
+use std::ops::DerefMut;
 use tracing::info;
+use windows::Win32::Foundation::HANDLE;
 use windows::Win32::Foundation::LUID;
 use windows::Win32::Security::AdjustTokenPrivileges;
 use windows::Win32::Security::LookupPrivilegeValueW;
@@ -12,19 +14,20 @@ use windows::Win32::Security::TOKEN_PRIVILEGES;
 use windows::Win32::Security::TOKEN_QUERY;
 use windows::Win32::System::Threading::GetCurrentProcess;
 use windows::Win32::System::Threading::OpenProcessToken;
+use windows::core::Owned;
 
 /// Enables backup and security privileges for the current process.
 ///
 /// Permits raw disk reads.
 pub fn enable_backup_privileges() -> eyre::Result<()> {
-    // Get current process token
-    let mut token = windows::Win32::Foundation::HANDLE::default();
+    // Get current process token; closed on drop
+    let mut token = unsafe { Owned::new(HANDLE::default()) };
     let current_process = unsafe { GetCurrentProcess() };
     unsafe {
         OpenProcessToken(
             current_process,
             TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY,
-            &mut token,
+            token.deref_mut(),
         )
     }
     .wrap_err_with(|| "Failed to open process token")?;
@@ -48,7 +51,7 @@ pub fn enable_backup_privileges() -> eyre::Result<()> {
             // Adjust token privileges
             let _ = unsafe {
                 AdjustTokenPrivileges(
-                    token,
+                    *token,
                     false,
                     Some(&privileges),
                     size_of::<TOKEN_PRIVILEGES>() as u32,
@@ -59,10 +62,6 @@ pub fn enable_backup_privileges() -> eyre::Result<()> {
         }
     }
 
-    // Close token handle
-    unsafe { windows::Win32::Foundation::CloseHandle(token) }
-        .wrap_err_with(|| "Failed to close token handle")?;
-
     info!("Successfully enabled backup privileges");
     Ok(())
 }
@@ -1,22 +1,52 @@
 use eyre::eyre;
-use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::Foundation::HANDLE;
 use windows::Win32::System::Threading::GetExitCodeProcess;
 use windows::Win32::System::Threading::INFINITE;
 use windows::Win32::System::Threading::WaitForSingleObject;
+use windows::core::Owned;
 
+/// Process handle of an elevated child; closed on drop whether or not it was waited on.
 #[derive(Debug)]
 pub struct ElevatedChildProcess {
-    pub h_process: HANDLE,
+    pub h_process: Owned<HANDLE>,
 }
 
 impl ElevatedChildProcess {
     pub fn wait(self) -> eyre::Result<u32> {
-        unsafe { WaitForSingleObject(self.h_process, INFINITE) };
+        unsafe { WaitForSingleObject(*self.h_process, INFINITE) };
         let mut code = 0u32;
-        unsafe { GetExitCodeProcess(self.h_process, &mut code) }
+        unsafe { GetExitCodeProcess(*self.h_process, &mut code) }
             .map_err(|e| eyre!("Failed to get exit code: {}", e))?;
-        unsafe { CloseHandle(self.h_process) }?;
         Ok(code)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ElevatedChildProcess;
+    use windows::Win32::Foundation::GetHandleInformation;
+    use windows::Win32::System::Threading::GetCurrentProcessId;
+    use windows::Win32::System::Threading::OpenProcess;
+    use windows::Win32::System::Threading::PROCESS_QUERY_LIMITED_INFORMATION;
+    use windows::core::Owned;
+
+    #[test]
+    fn dropping_an_unwaited_child_closes_its_handle() -> eyre::Result<()> {
+        let handle = unsafe {
+            OpenProcess(
+                PROCESS_QUERY_LIMITED_INFORMATION,
+                false,
+                GetCurrentProcessId(),
+            )
+        }?;
+        let mut flags = 0;
+        assert!(unsafe { GetHandleInformation(handle, &mut flags) }.is_ok());
+
+        drop(ElevatedChildProcess {
+            h_process: unsafe { Owned::new(handle) },
+        });
+
+        assert!(unsafe { GetHandleInformation(handle, &mut flags) }.is_err());
+        Ok(())
+    }
+}
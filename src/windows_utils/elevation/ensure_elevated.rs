@@ -4,6 +4,11 @@ use eyre::bail;
 use tracing::info;
 use tracing::warn;
 
+/// Explanation attached to errors caused by missing privileges for raw volume reads.
+pub const RAW_VOLUME_ACCESS_HINT: &str = "Reading raw NTFS volumes requires administrator privileges and SeBackupPrivilege. \
+     Run teamy-mft from an elevated terminal (\"Run as administrator\"), \
+     or omit --no-elevate to let it relaunch itself elevated.";
+
 /// Check if we're elevated, and relaunch if not
 pub fn ensure_elevated() -> eyre::Result<()> {
     if is_elevated() {
@@ -23,3 +28,45 @@ pub fn ensure_elevated() -> eyre::Result<()> {
         }
     }
 }
+
+/// Elevate before raw volume reads, or with `no_elevate` skip the relaunch and let the
+/// reads fail on their own.
+///
+/// # Errors
+///
+/// Returns an error explaining the required privileges if relaunching as administrator fails.
+pub fn ensure_elevated_for_raw_reads(no_elevate: bool) -> eyre::Result<()> {
+    if no_elevate {
+        if !is_elevated() {
+            warn!(
+                "--no-elevate was passed but the process is not elevated; raw volume reads will likely fail"
+            );
+        }
+        return Ok(());
+    }
+    ensure_elevated().map_err(raw_volume_access_error)
+}
+
+/// Attach [`RAW_VOLUME_ACCESS_HINT`] to an error, keeping the original error as its cause.
+#[must_use]
+pub fn raw_volume_access_error(error: eyre::Report) -> eyre::Report {
+    error.wrap_err(RAW_VOLUME_ACCESS_HINT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::raw_volume_access_error;
+
+    #[test]
+    fn raw_volume_access_error_explains_privileges_and_keeps_cause() {
+        let error = raw_volume_access_error(eyre::eyre!(
+            "Failed to relaunch as administrator: The operation was canceled by the user."
+        ));
+
+        let message = error.to_string();
+        assert!(message.contains("administrator privileges"));
+        assert!(message.contains("SeBackupPrivilege"));
+        assert!(message.contains("elevated terminal"));
+        assert!(format!("{error:#}").contains("canceled by the user"));
+    }
+}
@@ -7,6 +7,7 @@ use windows::Win32::UI::Shell::SEE_MASK_NOCLOSEPROCESS;
 use windows::Win32::UI::Shell::SHELLEXECUTEINFOW;
 use windows::Win32::UI::Shell::ShellExecuteExW;
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+use windows::core::Owned;
 
 /// Runs an invocable with administrative privileges using ShellExecuteExW.
 pub fn run_as_admin(invocable: &impl Invocable) -> eyre::Result<ElevatedChildProcess> {
@@ -36,6 +37,7 @@ pub fn run_as_admin(invocable: &impl Invocable) -> eyre::Result<ElevatedChildPro
     };
     unsafe { ShellExecuteExW(&mut sei) }.wrap_err("Failed to run as administrator")?;
     Ok(ElevatedChildProcess {
-        h_process: sei.hProcess,
+        // SEE_MASK_NOCLOSEPROCESS hands ownership of the process handle to the caller.
+        h_process: unsafe { Owned::new(sei.hProcess) },
     })
 }
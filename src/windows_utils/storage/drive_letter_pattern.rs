@@ -56,6 +56,26 @@ impl DriveLetterPattern {
         Ok(rtn)
     }
 
+    /// Resolve the pattern into exactly one drive letter, for commands that act on a single drive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is the wildcard, is invalid, or names more than one drive.
+    pub fn into_single_drive_letter(&self) -> eyre::Result<char> {
+        let input = self.as_ref().trim().trim_end_matches(':');
+        ensure!(
+            input != "*",
+            "Expected a single drive letter, got the wildcard '*'"
+        );
+        let drive_letters = DriveLetterPattern(input.to_owned()).into_drive_letters()?;
+        ensure!(
+            drive_letters.len() == 1,
+            "Expected a single drive letter, got '{}'",
+            self.as_ref()
+        );
+        Ok(drive_letters[0])
+    }
+
     /// Resolve the pattern, inferring drives from scope roots when the pattern
     /// is the wildcard default.
     ///
@@ -181,6 +201,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn single_drive_letter_rejects_wildcard_and_multiple_drives() -> eyre::Result<()> {
+        assert_eq!(
+            DriveLetterPattern("c:".to_string()).into_single_drive_letter()?,
+            'C'
+        );
+        assert!(
+            DriveLetterPattern("*".to_string())
+                .into_single_drive_letter()
+                .is_err()
+        );
+        assert!(
+            DriveLetterPattern("CD".to_string())
+                .into_single_drive_letter()
+                .is_err()
+        );
+        Ok(())
+    }
+
     #[test]
     fn default_pattern_infers_drives_from_scope_roots() -> eyre::Result<()> {
         let drive_letters = DriveLetterPattern::default().into_drive_letters_for_scope_roots([